@@ -1,46 +1,31 @@
 // Copyright (c) 2022 GreenYun Organization
 // SPDX-License-Identifier: MIT
 
-extern crate bincode;
-extern crate bytes;
+extern crate futures;
 extern crate short_msg;
 extern crate tokio;
+extern crate tokio_util;
 
 use std::env::args;
 
-use bytes::BytesMut;
-use tokio::{
-    io::{AsyncReadExt, AsyncWriteExt},
-    net::TcpStream,
-};
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_util::codec::Framed;
 
-use short_msg::smpp::prelude::*;
+use short_msg::smpp::{codec::SmppCodec, prelude::*};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let server = args().nth(1).expect("At least one argument should be specified.");
 
-    let config = bincode::config::standard().with_big_endian().with_fixed_int_encoding();
-
-    let mut stream = TcpStream::connect(server).await?;
-
-    let header = Header::new(Id::EnquireLink, Status::ESME_ROK, 1);
+    let stream = TcpStream::connect(server).await?;
+    let mut framed = Framed::new(stream, SmppCodec::new());
 
     {
-        // let enquire_link = EnquireLink {};
-        let enquire_link = bincode::encode_to_vec(header.clone(), config)?;
-
-        stream.write_all(&enquire_link).await?;
-
-        let data_len = stream.read_u32().await?;
-
-        let mut buf = BytesMut::with_capacity(data_len as usize);
-        stream.read_buf(&mut buf).await?;
-
-        let mut resp = buf.to_vec();
-        resp.splice(0..0, bincode::encode_to_vec(data_len, config)?);
-        let (resp, _) = bincode::decode_from_slice::<Header, _>(&resp, config)?;
+        let header = Header::new(Id::EnquireLink, Status::ESME_ROK, 1);
+        framed.send((header, Pdu::EnquireLink(EnquireLink {}))).await?;
 
+        let (resp, _) = framed.next().await.ok_or("connection closed")??;
         println!("{:?}", resp);
     }
 
@@ -55,39 +40,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             address_range: COctet::new("")?,
         };
 
-        let bind_transceiver =
-            Header::new_with_body(Id::BindTransceiver, Status::ESME_ROK, 2, bind_transceiver).unwrap();
+        let header = Header::new(Id::BindTransceiver, Status::ESME_ROK, 2);
+        framed.send((header, Pdu::BindTransceiver(bind_transceiver))).await?;
 
-        stream.write_all(&bind_transceiver).await?;
-
-        let data_len = stream.read_u32().await?;
-
-        let mut buf = BytesMut::with_capacity(data_len as usize);
-        stream.read_buf(&mut buf).await?;
-
-        let mut resp = buf.to_vec();
-        resp.splice(0..0, bincode::encode_to_vec(data_len, config)?);
-        let (header_resp, _) = bincode::decode_from_slice::<Header, _>(&resp, config)?;
+        let (header_resp, pdu) = framed.next().await.ok_or("connection closed")??;
         println!("{:?}", header_resp);
 
-        if matches!(header_resp.command_id, Id::BindTransceiverResp)
-            && matches!(header_resp.command_status, Status::ESME_ROK)
-        {
-            let (resp, _) = bincode::decode_from_slice::<BindTransceiverResp, _>(&resp[16..], config)?;
+        if let (Status::ESME_ROK, Pdu::BindTransceiverResp(resp)) = (header_resp.command_status, pdu) {
             println!("{:?}", resp);
 
             let header = Header::new(Id::Unbind, Status::ESME_ROK, 3);
-            let unbind = bincode::encode_to_vec(header, config)?;
-
-            stream.write_all(&unbind).await?;
-
-            let data_len = stream.read_u32().await?;
-            let mut buf = BytesMut::with_capacity(data_len as usize);
-            stream.read_buf(&mut buf).await?;
+            framed.send((header, Pdu::Unbind(Unbind {}))).await?;
 
-            let mut resp = buf.to_vec();
-            resp.splice(0..0, bincode::encode_to_vec(data_len, config)?);
-            let (resp, _) = bincode::decode_from_slice::<Header, _>(&resp, config)?;
+            let (resp, _) = framed.next().await.ok_or("connection closed")??;
             println!("{:?}", resp);
         }
     }
@@ -0,0 +1,712 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+//! An asynchronous ESME client.
+//!
+//! [`EsmeClient`] binds to an SMSC (v5: MC) over any byte stream, submits
+//! messages and surfaces the messages delivered by the SMSC (v5: MC) as a
+//! [`Deliveries`] stream. The connection is served by two background tasks,
+//! one reading and one writing, so that requests may be issued concurrently.
+//! A third task keeps an idle link alive with ***enquire_link*** and closes
+//! the session once the SMSC (v5: MC) stops answering; what happened to the
+//! session is reported on the [`Events`] stream.
+//!
+//! A [`Supervisor`] keeps a client bound across connection losses.
+//!
+//! Besides binding on its own, a client may wait for the SMSC (v5: MC) to
+//! connect and request the bind with an ***outbind***, see
+//! [`EsmeClient::accept_outbind`].
+
+use std::{
+    fmt, io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_util::{
+    stream::{SplitSink, SplitStream},
+    Stream, StreamExt,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{mpsc, watch},
+    time::{self, Instant},
+};
+use tokio_util::codec::Framed;
+
+use super::{
+    codec::{self, SmppCodec},
+    pdu::{
+        command::{
+            Bind, BindResp, DataSm, DataSmResp, DeliverSm, DeliverSmResp, EnquireLink, EnquireLinkResp, GenericNack,
+            Id, OutBind, Status, SubmitSm, SubmitSmResp, Unbind, UnbindResp,
+        },
+        typedef::COctet,
+        Header, Pdu,
+    },
+    session::{write_loop, PendingRequests, RequestError, Role, Sequence, StateMachine, Window},
+};
+
+pub use super::session::BindMode;
+pub use supervisor::{Backoff, Supervisor};
+
+mod supervisor;
+
+/// What an [`EsmeClient`] does with a new message while its window is full or
+/// the SMSC (v5: MC) has asked it to slow down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowPolicy {
+    /// Wait until the message can be sent.
+    Block,
+    /// Fail with [`Error::WindowFull`].
+    Reject,
+}
+
+/// Settings of an [`EsmeClient`].
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// How long to wait for the response to a request before giving up.
+    pub request_timeout: Duration,
+    /// The largest number of ***submit_sm*** and ***data_sm*** awaiting a
+    /// response at the same time.
+    pub window_size: usize,
+    pub window_policy: WindowPolicy,
+    /// How long to hold new messages back after the SMSC (v5: MC) responds
+    /// with `ESME_RTHROTTLED` or `ESME_RMSGQFUL`. The period doubles with
+    /// each further such response, up to `max_throttle_backoff`.
+    pub throttle_backoff: Duration,
+    pub max_throttle_backoff: Duration,
+    /// How long the link may stay silent before an ***enquire_link*** is sent.
+    /// `None` disables the keepalive.
+    pub enquire_link_interval: Option<Duration>,
+    /// How long to wait for the ***enquire_link_resp*** before the link is
+    /// considered dead and the session is closed.
+    pub enquire_link_timeout: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(30),
+            window_size: 10,
+            window_policy: WindowPolicy::Block,
+            throttle_backoff: Duration::from_secs(1),
+            max_throttle_backoff: Duration::from_secs(60),
+            enquire_link_interval: Some(Duration::from_secs(30)),
+            enquire_link_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// An ESME session bound to an SMSC (v5: MC).
+///
+/// Dropping a client that has not been [unbound](EsmeClient::unbind) sends an
+/// ***unbind*** to the SMSC (v5: MC); the connection is closed once the
+/// ***unbind_resp*** arrives, or after [`Config::request_timeout`].
+pub struct EsmeClient {
+    inner: Arc<Inner>,
+    bind_resp: BindResp,
+    deliveries: Option<Deliveries>,
+    events: Option<Events>,
+    unbound: AtomicBool,
+}
+
+impl EsmeClient {
+    /// Connects to the SMSC (v5: MC) at `addr` and binds in the given mode.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the connection cannot be
+    /// established or the bind request is rejected.
+    pub async fn connect<A: ToSocketAddrs>(addr: A, mode: BindMode, bind: Bind, config: Config) -> Result<Self, Error> {
+        let stream = TcpStream::connect(addr).await?;
+
+        Self::bind(stream, mode, bind, config).await
+    }
+
+    /// Binds in the given mode over an already established connection.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the bind request is rejected or
+    /// the connection is closed before the response arrives.
+    pub async fn bind<T>(io: T, mode: BindMode, bind: Bind, config: Config) -> Result<Self, Error>
+    where
+        T: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (sink, stream) = Framed::new(io, SmppCodec::new()).split();
+
+        Self::start(sink, stream, StateMachine::new(Role::Esme), mode, bind, config).await
+    }
+
+    /// Accepts a connection from the SMSC (v5: MC) on `listener` and serves
+    /// it as described in [`outbind`](EsmeClient::outbind).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if no connection can be accepted,
+    /// or as described in [`outbind`](EsmeClient::outbind).
+    pub async fn accept_outbind(
+        listener: &TcpListener,
+        expected: &OutBind,
+        bind: Bind,
+        config: Config,
+    ) -> Result<Self, Error> {
+        let (stream, _) = listener.accept().await?;
+
+        Self::outbind(stream, expected, bind, config).await
+    }
+
+    /// Serves a session initiated by the SMSC (v5: MC) over a connection it
+    /// has established.
+    ///
+    /// The SMSC (v5: MC) opens the session with an ***outbind***, whose
+    /// *system_id* and *password* must match `expected`. The client then binds
+    /// as a receiver on the same connection.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidOutbind`] if the SMSC (v5:
+    /// MC) does not open the session with a matching ***outbind***, or an
+    /// error if none arrives within [`Config::request_timeout`] or the bind
+    /// request is rejected.
+    pub async fn outbind<T>(io: T, expected: &OutBind, bind: Bind, config: Config) -> Result<Self, Error>
+    where
+        T: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (sink, mut stream) = Framed::new(io, SmppCodec::new()).split();
+        let mut state = StateMachine::new(Role::Esme);
+
+        let first = time::timeout(config.request_timeout, stream.next())
+            .await
+            .map_err(|_| Error::Timeout)?;
+
+        match first {
+            Some(Ok((header, Pdu::Outbind(outbind))))
+                if state.inbound(&header).is_ok()
+                    && outbind.system_id.as_ref() == expected.system_id.as_ref()
+                    && outbind.password.as_ref() == expected.password.as_ref() => {}
+            Some(Err(e)) => return Err(e.into()),
+            None => return Err(Error::Closed),
+            Some(Ok(_)) => return Err(Error::InvalidOutbind),
+        }
+
+        Self::start(sink, stream, state, BindMode::Receiver, bind, config).await
+    }
+
+    async fn start<T>(
+        sink: SplitSink<Framed<T, SmppCodec>, (Header, Pdu)>,
+        stream: SplitStream<Framed<T, SmppCodec>>,
+        state: StateMachine,
+        mode: BindMode,
+        bind: Bind,
+        config: Config,
+    ) -> Result<Self, Error>
+    where
+        T: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (outbound, outbound_rx) = mpsc::unbounded_channel();
+        let (deliveries, deliveries_rx) = mpsc::unbounded_channel();
+        let (events, events_rx) = mpsc::unbounded_channel();
+
+        let inner = Arc::new(Inner {
+            outbound,
+            events: Mutex::new(Some(events)),
+            closed: watch::Sender::new(false),
+            last_activity: Mutex::new(Instant::now()),
+            state: Mutex::new(state),
+            sequence: Sequence::new(),
+            pending: Arc::new(PendingRequests::new()),
+            window: Window::new(config.window_size, config.throttle_backoff, config.max_throttle_backoff),
+            config,
+        });
+
+        tokio::spawn(write_loop(sink, outbound_rx, inner.closed.subscribe()));
+        tokio::spawn(read_loop(stream, inner.clone(), deliveries));
+
+        let mut client = Self {
+            inner,
+            bind_resp: BindResp {
+                system_id: COctet::default(),
+                sc_interface_version: None,
+            },
+            deliveries: Some(Deliveries { rx: deliveries_rx }),
+            events: Some(Events { rx: events_rx }),
+            unbound: AtomicBool::new(true),
+        };
+
+        let bind_resp = match client.inner.request(mode.request(bind)).await {
+            Ok((_, Pdu::BindTransmitterResp(resp) | Pdu::BindReceiverResp(resp) | Pdu::BindTransceiverResp(resp))) => {
+                Ok(resp)
+            }
+            Ok((_, pdu)) => Err(Error::UnexpectedResponse(pdu.command_id())),
+            Err(e) => Err(e),
+        };
+
+        client.bind_resp = match bind_resp {
+            Ok(resp) => resp,
+            Err(e) => {
+                // Nothing is unbound when an unbound client is dropped, so the
+                // background tasks have to be stopped here.
+                client.inner.close();
+                return Err(e);
+            }
+        };
+        *client.unbound.get_mut() = false;
+
+        if let Some(interval) = client.inner.config.enquire_link_interval {
+            tokio::spawn(keepalive(client.inner.clone(), interval));
+        }
+
+        Ok(client)
+    }
+
+    /// The response of the SMSC (v5: MC) to the bind request.
+    #[must_use]
+    pub const fn bind_resp(&self) -> &BindResp {
+        &self.bind_resp
+    }
+
+    /// Submits a short message and waits for the SMSC (v5: MC) to accept it.
+    ///
+    /// The message takes a slot in the window of the client until the
+    /// response arrives; see [`Config::window_policy`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the SMSC (v5: MC) rejects the
+    /// message, the window is full under [`WindowPolicy::Reject`], or the
+    /// connection is closed before the response arrives.
+    pub async fn submit(&self, submit_sm: SubmitSm) -> Result<SubmitSmResp, Error> {
+        match self.inner.windowed_request(Pdu::SubmitSm(submit_sm)).await? {
+            (_, Pdu::SubmitSmResp(resp)) => Ok(resp),
+            (_, pdu) => Err(Error::UnexpectedResponse(pdu.command_id())),
+        }
+    }
+
+    /// Sends a ***data_sm*** and waits for the SMSC (v5: MC) to accept it.
+    ///
+    /// Like [`submit`](EsmeClient::submit), the message takes a slot in the
+    /// window of the client until the response arrives.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the SMSC (v5: MC) rejects the
+    /// message, the window is full under [`WindowPolicy::Reject`], or the
+    /// connection is closed before the response arrives.
+    pub async fn data(&self, data_sm: DataSm) -> Result<DataSmResp, Error> {
+        match self.inner.windowed_request(Pdu::DataSm(data_sm)).await? {
+            (_, Pdu::DataSmResp(resp)) => Ok(resp),
+            (_, pdu) => Err(Error::UnexpectedResponse(pdu.command_id())),
+        }
+    }
+
+    /// Takes the stream of messages delivered by the SMSC (v5: MC). Returns
+    /// `None` if it has been taken before.
+    ///
+    /// Every ***deliver_sm*** is acknowledged with a ***deliver_sm_resp***
+    /// before it is yielded. Messages are buffered until they are read.
+    pub fn deliveries(&mut self) -> Option<Deliveries> {
+        self.deliveries.take()
+    }
+
+    /// Takes the stream of [`Event`]s of the session. Returns `None` if it has
+    /// been taken before.
+    pub fn events(&mut self) -> Option<Events> {
+        self.events.take()
+    }
+
+    /// Returns `true` once the session has been closed, by either side.
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        *self.inner.closed.borrow()
+    }
+
+    /// Unbinds from the SMSC (v5: MC) and waits for the ***unbind_resp***.
+    /// The connection is closed if none arrives within
+    /// [`Config::request_timeout`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the SMSC (v5: MC) rejects the
+    /// request or the connection is closed before the response arrives.
+    pub async fn unbind(&self) -> Result<(), Error> {
+        self.unbound.store(true, Ordering::Relaxed);
+
+        match self.inner.request(Pdu::Unbind(Unbind {})).await {
+            Ok((_, Pdu::UnbindResp(_))) => Ok(()),
+            Ok((_, pdu)) => Err(Error::UnexpectedResponse(pdu.command_id())),
+            Err(e) => {
+                if matches!(e, Error::Timeout) {
+                    self.inner.close();
+                }
+
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Drop for EsmeClient {
+    fn drop(&mut self) {
+        if *self.unbound.get_mut() {
+            return;
+        }
+
+        let header = Header::new(Id::Unbind, Status::ESME_ROK, self.inner.sequence.allocate());
+        let sent = self.inner.send(header, Pdu::Unbind(Unbind {}));
+
+        // The read loop closes the session once the unbind_resp arrives; an
+        // SMSC (v5: MC) that never answers must not keep the link open.
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if sent.is_ok() => {
+                let inner = self.inner.clone();
+                handle.spawn(async move {
+                    let mut closed = inner.closed.subscribe();
+                    let unbound = closed.wait_for(|closed| *closed);
+                    if time::timeout(inner.config.request_timeout, unbound).await.is_err() {
+                        inner.close();
+                    }
+                });
+            }
+            _ => self.inner.close(),
+        }
+    }
+}
+
+/// The stream of ***deliver_sm*** PDUs received by an [`EsmeClient`]. It ends
+/// when the session is closed.
+pub struct Deliveries {
+    rx: mpsc::UnboundedReceiver<DeliverSm>,
+}
+
+impl Stream for Deliveries {
+    type Item = DeliverSm;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+/// What happened to the session of an [`EsmeClient`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// An ***enquire_link*** was not answered within
+    /// [`Config::enquire_link_timeout`]; the session has been closed.
+    LinkTimeout,
+    /// The SMSC (v5: MC) unbound the session.
+    Unbound,
+    /// The connection was closed or broken by the SMSC (v5: MC) or the
+    /// network.
+    Disconnected,
+}
+
+/// The stream of [`Event`]s of an [`EsmeClient`]. It ends when the session is
+/// closed.
+pub struct Events {
+    rx: mpsc::UnboundedReceiver<Event>,
+}
+
+impl Stream for Events {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+struct Inner {
+    outbound: mpsc::UnboundedSender<(Header, Pdu)>,
+    /// Dropped once the session is closed, which ends the [`Events`] stream.
+    events: Mutex<Option<mpsc::UnboundedSender<Event>>>,
+    /// Set once the session is closed; stops the background tasks.
+    closed: watch::Sender<bool>,
+    /// The time the last PDU was received.
+    last_activity: Mutex<Instant>,
+    state: Mutex<StateMachine>,
+    sequence: Sequence,
+    pending: Arc<PendingRequests>,
+    window: Window,
+    config: Config,
+}
+
+impl Inner {
+    fn close(&self) {
+        self.events.lock().unwrap().take();
+        self.closed.send_replace(true);
+    }
+
+    fn event(&self, event: Event) {
+        if let Some(events) = &*self.events.lock().unwrap() {
+            let _ = events.send(event);
+        }
+    }
+
+    fn send(&self, header: Header, pdu: Pdu) -> Result<(), Error> {
+        self.state.lock().unwrap().outbound(&header).map_err(Error::Rejected)?;
+        self.outbound.send((header, pdu)).map_err(|_| Error::Closed)
+    }
+
+    async fn request(&self, pdu: Pdu) -> Result<(Header, Pdu), Error> {
+        self.request_within(pdu, self.config.request_timeout).await
+    }
+
+    async fn request_within(&self, pdu: Pdu, timeout: Duration) -> Result<(Header, Pdu), Error> {
+        let header = Header::new(pdu.command_id(), Status::ESME_ROK, self.sequence.allocate());
+        let response = self.pending.register(&header)?;
+
+        // Requests registered once the session is closed would never be
+        // resolved.
+        if *self.closed.borrow() {
+            return Err(Error::Closed);
+        }
+
+        self.send(header, pdu)?;
+
+        Ok(response.wait(timeout).await?)
+    }
+
+    async fn windowed_request(&self, pdu: Pdu) -> Result<(Header, Pdu), Error> {
+        let _slot = match self.config.window_policy {
+            WindowPolicy::Block => self.window.acquire().await,
+            WindowPolicy::Reject => self.window.try_acquire().ok_or(Error::WindowFull)?,
+        };
+
+        let result = self.request(pdu).await;
+        match &result {
+            Ok((header, _)) => self.window.record(&header.command_status),
+            Err(Error::Status(status) | Error::Nack(status)) => self.window.record(status),
+            Err(_) => {}
+        }
+
+        result
+    }
+}
+
+async fn read_loop<S>(mut stream: S, inner: Arc<Inner>, deliveries: mpsc::UnboundedSender<DeliverSm>)
+where
+    S: Stream<Item = Result<(Header, Pdu), codec::Error>> + Unpin,
+{
+    let mut closed = inner.closed.subscribe();
+    // After a decoding error the stream yields `None` once, then resumes.
+    let mut errored = false;
+
+    let event = loop {
+        let item = tokio::select! {
+            item = stream.next() => item,
+            _ = closed.wait_for(|closed| *closed) => break None,
+        };
+
+        let (header, pdu) = match item {
+            Some(Ok(item)) => item,
+            // The PDU has been framed by its command_length, so only its body
+            // is skipped.
+            Some(Err(codec::Error::Body(header, e))) => {
+                errored = true;
+
+                if header.command_id.is_response() {
+                    // Rather than waiting for the request to time out.
+                    let malformed = RequestError::Malformed(header.command_id.clone());
+                    inner.pending.fail(&header, malformed);
+                } else {
+                    let _ = inner.send(
+                        Header::new(Id::GenericNack, e.command_status(), header.sequence_number),
+                        Pdu::GenericNack(GenericNack {}),
+                    );
+                }
+
+                continue;
+            }
+            None if std::mem::take(&mut errored) => continue,
+            _ => break Some(Event::Disconnected),
+        };
+
+        *inner.last_activity.lock().unwrap() = Instant::now();
+        let seq = header.sequence_number;
+
+        let checked = inner.state.lock().unwrap().inbound(&header);
+        if let Err(status) = checked {
+            if let Some((header, pdu)) = StateMachine::reject(&header, status) {
+                let _ = inner.send(header, pdu);
+            }
+
+            continue;
+        }
+
+        if header.command_id.is_response() {
+            let unbound = matches!(pdu, Pdu::UnbindResp(_));
+            // Responses nobody waits for any more, e.g. after a timeout, are
+            // dropped.
+            let _ = inner.pending.resolve(header, pdu);
+
+            if unbound {
+                break None;
+            }
+
+            continue;
+        }
+
+        match pdu {
+            Pdu::DeliverSm(deliver_sm) => {
+                let resp = DeliverSmResp {
+                    message_id: COctet::default(),
+
+                    #[cfg(feature = "v5")]
+                    msg_delivery_resp_tlv: vec![],
+                };
+                let _ = inner.send(
+                    Header::new(Id::DeliverSmResp, Status::ESME_ROK, seq),
+                    Pdu::DeliverSmResp(resp),
+                );
+                let _ = deliveries.send(deliver_sm);
+            }
+            Pdu::EnquireLink(_) => {
+                let _ = inner.send(
+                    Header::new(Id::EnquireLinkResp, Status::ESME_ROK, seq),
+                    Pdu::EnquireLinkResp(EnquireLinkResp {}),
+                );
+            }
+            Pdu::Unbind(_) => {
+                let _ = inner.send(
+                    Header::new(Id::UnbindResp, Status::ESME_ROK, seq),
+                    Pdu::UnbindResp(UnbindResp {}),
+                );
+                break Some(Event::Unbound);
+            }
+            _ => {
+                let _ = inner.send(
+                    Header::new(Id::GenericNack, Status::ESME_RINVCMDID, seq),
+                    Pdu::GenericNack(GenericNack {}),
+                );
+            }
+        }
+    };
+
+    if let Some(event) = event {
+        inner.event(event);
+    }
+
+    // Dropping the waiters wakes them up with `Error::Closed`.
+    inner.close();
+    inner.state.lock().unwrap().close();
+    inner.pending.close();
+}
+
+async fn keepalive(inner: Arc<Inner>, interval: Duration) {
+    let mut closed = inner.closed.subscribe();
+
+    loop {
+        let deadline = *inner.last_activity.lock().unwrap() + interval;
+        if Instant::now() < deadline {
+            tokio::select! {
+                _ = time::sleep_until(deadline) => continue,
+                _ = closed.wait_for(|closed| *closed) => return,
+            }
+        }
+
+        let enquire_link = Pdu::EnquireLink(EnquireLink {});
+        match inner
+            .request_within(enquire_link, inner.config.enquire_link_timeout)
+            .await
+        {
+            Err(Error::Timeout) => {
+                inner.event(Event::LinkTimeout);
+                inner.close();
+                return;
+            }
+            Err(Error::Closed | Error::Rejected(_)) => return,
+            // Any answer, even a negative one, shows that the link is alive.
+            _ => *inner.last_activity.lock().unwrap() = Instant::now(),
+        }
+    }
+}
+
+/// Errors that occur in an ESME session.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Codec(codec::Error),
+    /// The SMSC (v5: MC) responded with a non-zero *command_status*.
+    Status(Status),
+    /// The SMSC (v5: MC) responded with a ***generic_nack***.
+    Nack(Status),
+    /// The SMSC (v5: MC) responded with an unexpected PDU.
+    UnexpectedResponse(Id),
+    /// The SMSC (v5: MC) responded with a PDU whose body cannot be decoded.
+    Malformed(Id),
+    /// The SMSC (v5: MC) did not open the session with a matching
+    /// ***outbind***.
+    InvalidOutbind,
+    /// The request is not allowed in the current state of the session.
+    Rejected(Status),
+    /// The window is full or the SMSC (v5: MC) has asked the client to slow
+    /// down, under [`WindowPolicy::Reject`].
+    WindowFull,
+    /// No response arrived within [`Config::request_timeout`].
+    Timeout,
+    /// The session was closed before the response arrived.
+    Closed,
+    /// A request with the same *sequence_number* is still in flight.
+    InFlight(u32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::Codec(e) => e.fmt(f),
+            Self::Status(status) => write!(f, "request failed with {:?}", status),
+            Self::Nack(status) => write!(f, "request rejected by generic_nack with {:?}", status),
+            Self::UnexpectedResponse(id) => write!(f, "unexpected response {:?}", id),
+            Self::Malformed(id) => write!(f, "malformed response {:?}", id),
+            Self::InvalidOutbind => write!(f, "invalid outbind"),
+            Self::Rejected(status) => write!(f, "request not allowed in this state: {:?}", status),
+            Self::WindowFull => write!(f, "window full"),
+            Self::Timeout => write!(f, "request timed out"),
+            Self::Closed => write!(f, "session closed"),
+            Self::InFlight(seq) => write!(f, "sequence_number {} already in flight", seq),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Codec(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<codec::Error> for Error {
+    fn from(e: codec::Error) -> Self {
+        Self::Codec(e)
+    }
+}
+
+impl From<RequestError> for Error {
+    fn from(e: RequestError) -> Self {
+        match e {
+            RequestError::Status(status) => Self::Status(status),
+            RequestError::Nack(status) => Self::Nack(status),
+            RequestError::UnexpectedResponse(id) => Self::UnexpectedResponse(id),
+            RequestError::Malformed(id) => Self::Malformed(id),
+            RequestError::Timeout => Self::Timeout,
+            RequestError::Closed => Self::Closed,
+            RequestError::InFlight(seq) => Self::InFlight(seq),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::Arc,
+    time::Duration,
+};
+
+use futures_util::StreamExt;
+use tokio::{
+    net::ToSocketAddrs,
+    sync::{mpsc, watch},
+    task::JoinHandle,
+    time,
+};
+
+use super::{BindMode, Config, Deliveries, Error, EsmeClient};
+use crate::smpp::pdu::command::{Bind, Status, SubmitSm, SubmitSmResp};
+
+/// The delay between two attempts to reconnect, growing exponentially with
+/// the number of failed attempts.
+#[derive(Clone, Debug)]
+pub struct Backoff {
+    /// The delay after the first failed attempt.
+    pub initial: Duration,
+    /// The upper bound of the delay.
+    pub max: Duration,
+    /// The fraction, from 0.0 to 1.0, by which each delay is randomly
+    /// shortened, so that many clients do not reconnect at the same time.
+    pub jitter: f64,
+}
+
+impl Backoff {
+    /// Returns the delay after `attempt` consecutive failed attempts, counting
+    /// from zero.
+    #[must_use]
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.initial.saturating_mul(1 << attempt.min(16)).min(self.max);
+
+        // A fresh `RandomState` is randomly seeded, which is good enough for
+        // spreading out reconnects.
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+
+        delay.mul_f64(1.0 - self.jitter.clamp(0.0, 1.0) * random)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+            jitter: 0.5,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum State {
+    Connecting,
+    Bound(Arc<EsmeClient>),
+    /// The SMSC (v5: MC) rejected the credentials.
+    Failed(Status),
+    Stopped,
+}
+
+/// Keeps an [`EsmeClient`] bound to an SMSC (v5: MC), reconnecting and
+/// rebinding whenever the session is lost.
+///
+/// Reconnecting is retried forever with [`Backoff`], unless the bind request is
+/// rejected with `ESME_RINVPASWD` or `ESME_RINVSYSID`, which would fail again.
+///
+/// Messages submitted while no session is bound wait for the next one. A
+/// message whose session is lost before the ***submit_sm_resp*** arrives is
+/// submitted again after the rebind, so it may be delivered twice.
+pub struct Supervisor {
+    state: watch::Receiver<State>,
+    stop: watch::Sender<bool>,
+    deliveries: Option<Deliveries>,
+    task: JoinHandle<()>,
+}
+
+impl Supervisor {
+    /// Starts connecting to the SMSC (v5: MC) at `addr` and binding in the
+    /// given mode.
+    pub fn start<A>(addr: A, mode: BindMode, bind: Bind, config: Config, backoff: Backoff) -> Self
+    where
+        A: ToSocketAddrs + Clone + Send + 'static,
+    {
+        let (state_tx, state) = watch::channel(State::Connecting);
+        let (stop, stop_rx) = watch::channel(false);
+        let (deliveries, deliveries_rx) = mpsc::unbounded_channel();
+
+        let task = tokio::spawn(async move {
+            let mut stop = stop_rx;
+            let mut attempt = 0;
+
+            loop {
+                let connect = EsmeClient::connect(addr.clone(), mode, bind.clone(), config.clone());
+                let result = tokio::select! {
+                    result = connect => result,
+                    _ = stop.wait_for(|stop| *stop) => break,
+                };
+
+                match result {
+                    Ok(mut client) => {
+                        attempt = 0;
+
+                        let mut events = client.events().expect("events of a new client");
+                        if let Some(mut rx) = client.deliveries() {
+                            let deliveries = deliveries.clone();
+                            tokio::spawn(async move {
+                                while let Some(deliver_sm) = rx.next().await {
+                                    let _ = deliveries.send(deliver_sm);
+                                }
+                            });
+                        }
+
+                        let client = Arc::new(client);
+                        state_tx.send_replace(State::Bound(client.clone()));
+
+                        let stopped = tokio::select! {
+                            _ = events.next() => false,
+                            _ = stop.wait_for(|stop| *stop) => true,
+                        };
+
+                        if stopped {
+                            state_tx.send_replace(State::Stopped);
+                            // Messages still waiting for a response fail
+                            // once the session is unbound.
+                            let _ = client.unbind().await;
+                            return;
+                        }
+
+                        state_tx.send_replace(State::Connecting);
+                    }
+                    Err(
+                        Error::Status(status @ (Status::ESME_RINVPASWD | Status::ESME_RINVSYSID))
+                        | Error::Nack(status @ (Status::ESME_RINVPASWD | Status::ESME_RINVSYSID)),
+                    ) => {
+                        state_tx.send_replace(State::Failed(status));
+                        return;
+                    }
+                    Err(_) => {}
+                }
+
+                tokio::select! {
+                    _ = time::sleep(backoff.delay(attempt)) => attempt = attempt.saturating_add(1),
+                    _ = stop.wait_for(|stop| *stop) => break,
+                }
+            }
+
+            state_tx.send_replace(State::Stopped);
+        });
+
+        Self {
+            state,
+            stop,
+            deliveries: Some(Deliveries { rx: deliveries_rx }),
+            task,
+        }
+    }
+
+    /// Submits a short message and waits for the SMSC (v5: MC) to accept it,
+    /// waiting for a session to be bound first if necessary. If the session is
+    /// lost before the SMSC (v5: MC) answers, the message is submitted again
+    /// once rebound.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the SMSC (v5: MC) rejects the
+    /// message or the credentials, or the supervisor is stopped.
+    pub async fn submit(&self, submit_sm: SubmitSm) -> Result<SubmitSmResp, Error> {
+        let mut state = self.state.clone();
+
+        loop {
+            let client = match &*state
+                .wait_for(|state| !matches!(state, State::Connecting))
+                .await
+                .map_err(|_| Error::Closed)?
+            {
+                State::Bound(client) => client.clone(),
+                State::Failed(status) => return Err(Error::Status(status.clone())),
+                State::Connecting | State::Stopped => return Err(Error::Closed),
+            };
+
+            let result = client.submit(submit_sm.clone()).await;
+            match result {
+                // The SMSC (v5: MC) has answered, if only negatively.
+                Ok(_) | Err(Error::Status(_) | Error::Nack(_)) => return result,
+                // The session was lost before the response arrived, or before
+                // the supervisor noticed; submit the message again once
+                // rebound.
+                Err(_) if client.is_closed() => {
+                    let _ = state
+                        .wait_for(|state| !matches!(state, State::Bound(c) if Arc::ptr_eq(c, &client)))
+                        .await;
+                }
+                Err(_) => return result,
+            }
+        }
+    }
+
+    /// Takes the stream of messages delivered over all sessions. Returns
+    /// `None` if it has been taken before.
+    pub fn deliveries(&mut self) -> Option<Deliveries> {
+        self.deliveries.take()
+    }
+
+    /// Returns `true` while a session is bound.
+    #[must_use]
+    pub fn is_bound(&self) -> bool {
+        matches!(*self.state.borrow(), State::Bound(_))
+    }
+
+    /// Stops reconnecting and unbinds the current session, if any.
+    pub async fn shutdown(mut self) {
+        self.stop.send_replace(true);
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        self.stop.send_replace(true);
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+//! A [`tokio_util::codec`] implementation for streaming SMPP PDUs over a byte
+//! stream such as a TCP connection.
+//!
+//! PDUs may arrive split across several reads, or several PDUs may arrive in a
+//! single read; [`SmppCodec`] buffers input until a complete PDU, as given by
+//! its *command_length*, is available.
+
+use std::{fmt, io};
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use super::pdu::{self, command::Status, Frame, Header, Pdu};
+
+/// Decodes a byte stream into [`Header`] and [`Pdu`] pairs, and encodes them
+/// back.
+///
+/// # Note:
+///
+/// When encoding, only the *command_status* and *sequence_number* of the
+/// [`Header`] are used. The *command_id* and *command_length* are always
+/// derived from the [`Pdu`].
+#[derive(Clone, Debug)]
+pub struct SmppCodec {
+    max_command_length: u32,
+}
+
+impl SmppCodec {
+    /// The default upper bound of *command_length*, large enough for a
+    /// ***submit_sm*** carrying a full 64K *message_payload* TLV.
+    pub const DEFAULT_MAX_COMMAND_LENGTH: u32 = 0x20000;
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_command_length: Self::DEFAULT_MAX_COMMAND_LENGTH,
+        }
+    }
+
+    /// Sets the largest *command_length* accepted from the peer. A PDU
+    /// announcing a larger length is rejected before its body is buffered.
+    #[must_use]
+    pub const fn with_max_command_length(self, max_command_length: u32) -> Self {
+        Self { max_command_length }
+    }
+
+    #[must_use]
+    pub const fn max_command_length(&self) -> u32 {
+        self.max_command_length
+    }
+}
+
+impl Default for SmppCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for SmppCodec {
+    type Error = Error;
+    type Item = (Header, Pdu);
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let command_length = match Frame::peek_len(src) {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+
+        if command_length > self.max_command_length {
+            return Err(Error::CommandLengthExceeded(command_length));
+        }
+
+        let len = command_length as usize;
+        if len < Frame::HEADER_LEN {
+            return Err(pdu::Error::InvalidCommandLength(command_length).into());
+        }
+
+        if src.len() < len {
+            src.reserve(len - src.len());
+            return Ok(None);
+        }
+
+        let buf = src.split_to(len);
+        let frame = Frame::from_bytes(&buf)?;
+        let pdu = Pdu::from_frame(&frame).map_err(|e| Error::Body(frame.header.clone(), e))?;
+
+        Ok(Some((frame.header, pdu)))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(item) => Ok(Some(item)),
+            None if src.is_empty() => Ok(None),
+            None => {
+                let remaining = src.remaining();
+                src.clear();
+                Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("{} bytes of an incomplete PDU remaining", remaining),
+                )
+                .into())
+            }
+        }
+    }
+}
+
+impl Encoder<(Header, Pdu)> for SmppCodec {
+    type Error = Error;
+
+    fn encode(&mut self, (header, pdu): (Header, Pdu), dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let buf = pdu.encode_with_status(header.command_status, header.sequence_number)?;
+        dst.extend_from_slice(&buf);
+
+        Ok(())
+    }
+}
+
+/// Errors that occur while reading or writing a PDU stream.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The PDU could not be framed, decoded or encoded.
+    Pdu(pdu::Error),
+    /// The PDU was framed correctly but its body could not be decoded. The
+    /// header is kept so that a response can still be sent to the peer.
+    Body(Header, pdu::Error),
+    /// The peer announced a *command_length* larger than the configured
+    /// maximum.
+    CommandLengthExceeded(u32),
+}
+
+impl Error {
+    /// Returns the *command_status* to report to the peer that sent the
+    /// offending PDU.
+    #[must_use]
+    pub const fn command_status(&self) -> Status {
+        match self {
+            Self::Io(_) => Status::ESME_RSYSERR,
+            Self::Pdu(e) | Self::Body(_, e) => e.command_status(),
+            Self::CommandLengthExceeded(_) => Status::ESME_RINVCMDLEN,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::Pdu(e) | Self::Body(_, e) => e.fmt(f),
+            Self::CommandLengthExceeded(len) => write!(f, "command_length {} exceeds the maximum", len),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Pdu(e) | Self::Body(_, e) => Some(e),
+            Self::CommandLengthExceeded(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<pdu::Error> for Error {
+    fn from(e: pdu::Error) -> Self {
+        Self::Pdu(e)
+    }
+}
@@ -2,7 +2,16 @@
 // SPDX-License-Identifier: MIT
 
 pub mod prelude {
-    pub use super::pdu::{command::*, typedef::*, Header};
+    pub use super::pdu::{command::*, typedef::*, Frame, Header, Pdu};
 }
 
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "tokio")]
+pub mod codec;
 pub mod pdu;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod session;
+#[cfg(feature = "simulator")]
+pub mod simulator;
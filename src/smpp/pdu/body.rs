@@ -0,0 +1,274 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use super::{
+    command::{self, Id, Status},
+    Error, Frame, Header,
+};
+use command::*;
+
+/// A complete SMPP PDU body, typed by its *command_id*.
+///
+/// # Note:
+///
+/// The [`Pdu::Raw`] variant carries the undecoded body of a PDU whose
+/// *command_id* is unknown to this crate, so that it can still be passed
+/// through unchanged. It is also used for negative responses whose body has
+/// been omitted by the sender, as is allowed e.g. for ***bind_*_resp***, while
+/// responses that never have a body, such as ***generic_nack***, are still
+/// typed.
+#[derive(Clone, Debug)]
+pub enum Pdu {
+    GenericNack(GenericNack),
+    BindReceiver(BindReceiver),
+    BindReceiverResp(BindReceiverResp),
+    BindTransmitter(BindTransmitter),
+    BindTransmitterResp(BindTransmitterResp),
+    QuerySm(QuerySm),
+    QuerySmResp(QuerySmResp),
+    SubmitSm(SubmitSm),
+    SubmitSmResp(SubmitSmResp),
+    DeliverSm(DeliverSm),
+    DeliverSmResp(DeliverSmResp),
+    Unbind(Unbind),
+    UnbindResp(UnbindResp),
+    ReplaceSm(ReplaceSm),
+    ReplaceSmResp(ReplaceSmResp),
+    CancelSm(CancelSm),
+    CancelSmResp(CancelSmResp),
+    BindTransceiver(BindTransceiver),
+    BindTransceiverResp(BindTransceiverResp),
+    Outbind(OutBind),
+    EnquireLink(EnquireLink),
+    EnquireLinkResp(EnquireLinkResp),
+    SubmitMulti(SubmitMulti),
+    SubmitMultiResp(SubmitMultiResp),
+    AlertNotification(AlertNotification),
+    DataSm(DataSm),
+    DataSmResp(DataSmResp),
+    #[cfg(feature = "v5")]
+    BroadcastSm(BroadcastSm),
+    #[cfg(feature = "v5")]
+    BroadcastSmResp(BroadcastSmResp),
+    #[cfg(feature = "v5")]
+    QueryBroadcastSm(QueryBroadcastSm),
+    #[cfg(feature = "v5")]
+    QueryBroadcastSmResp(QueryBroadcastSmResp),
+    #[cfg(feature = "v5")]
+    CancelBroadcastSm(CancelBroadcastSm),
+    #[cfg(feature = "v5")]
+    CancelBroadcastSmResp(CancelBroadcastSmResp),
+    Raw {
+        command_id: Id,
+        body: Vec<u8>,
+    },
+}
+
+impl Pdu {
+    /// Decodes a PDU, including its header, from a buffer holding exactly one
+    /// PDU.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the *command_length* does not
+    /// match the length of `buf`, or the header or the body cannot be decoded.
+    pub fn decode(buf: &[u8]) -> Result<(Header, Self), Error> {
+        let frame = Frame::from_bytes(buf)?;
+        let pdu = Self::from_frame(&frame)?;
+
+        Ok((frame.header, pdu))
+    }
+
+    /// Decodes the body of a PDU that has been split off the wire.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the body cannot be decoded, or if
+    /// it does not fill the frame exactly.
+    pub fn from_frame(frame: &Frame) -> Result<Self, Error> {
+        let header = &frame.header;
+
+        match Self::decode_body(frame) {
+            Err(_)
+                if frame.body.is_empty()
+                    && header.command_id.is_response()
+                    && !matches!(header.command_status, Status::ESME_ROK) =>
+            {
+                Ok(Self::Raw {
+                    command_id: header.command_id.clone(),
+                    body: vec![],
+                })
+            }
+            res => res,
+        }
+    }
+
+    fn decode_body(frame: &Frame) -> Result<Self, Error> {
+        Ok(match frame.header.command_id.clone() {
+            Id::GenericNack => Self::GenericNack(frame.decode_body()?),
+            Id::BindReceiver => Self::BindReceiver(frame.decode_body()?),
+            Id::BindReceiverResp => Self::BindReceiverResp(frame.decode_body()?),
+            Id::BindTransmitter => Self::BindTransmitter(frame.decode_body()?),
+            Id::BindTransmitterResp => Self::BindTransmitterResp(frame.decode_body()?),
+            Id::QuerySm => Self::QuerySm(frame.decode_body()?),
+            Id::QuerySmResp => Self::QuerySmResp(frame.decode_body()?),
+            Id::SubmitSm => Self::SubmitSm(frame.decode_body()?),
+            Id::SubmitSmResp => Self::SubmitSmResp(frame.decode_body()?),
+            Id::DeliverSm => Self::DeliverSm(frame.decode_body()?),
+            Id::DeliverSmResp => Self::DeliverSmResp(frame.decode_body()?),
+            Id::Unbind => Self::Unbind(frame.decode_body()?),
+            Id::UnbindResp => Self::UnbindResp(frame.decode_body()?),
+            Id::ReplaceSm => Self::ReplaceSm(frame.decode_body()?),
+            Id::ReplaceSmResp => Self::ReplaceSmResp(frame.decode_body()?),
+            Id::CancelSm => Self::CancelSm(frame.decode_body()?),
+            Id::CancelSmResp => Self::CancelSmResp(frame.decode_body()?),
+            Id::BindTransceiver => Self::BindTransceiver(frame.decode_body()?),
+            Id::BindTransceiverResp => Self::BindTransceiverResp(frame.decode_body()?),
+            Id::Outbind => Self::Outbind(frame.decode_body()?),
+            Id::EnquireLink => Self::EnquireLink(frame.decode_body()?),
+            Id::EnquireLinkResp => Self::EnquireLinkResp(frame.decode_body()?),
+            Id::SubmitMulti => Self::SubmitMulti(frame.decode_body()?),
+            Id::SubmitMultiResp => Self::SubmitMultiResp(frame.decode_body()?),
+            Id::AlertNotification => Self::AlertNotification(frame.decode_body()?),
+            Id::DataSm => Self::DataSm(frame.decode_body()?),
+            Id::DataSmResp => Self::DataSmResp(frame.decode_body()?),
+            #[cfg(feature = "v5")]
+            Id::BroadcastSm => Self::BroadcastSm(frame.decode_body()?),
+            #[cfg(feature = "v5")]
+            Id::BroadcastSmResp => Self::BroadcastSmResp(frame.decode_body()?),
+            #[cfg(feature = "v5")]
+            Id::QueryBroadcastSm => Self::QueryBroadcastSm(frame.decode_body()?),
+            #[cfg(feature = "v5")]
+            Id::QueryBroadcastSmResp => Self::QueryBroadcastSmResp(frame.decode_body()?),
+            #[cfg(feature = "v5")]
+            Id::CancelBroadcastSm => Self::CancelBroadcastSm(frame.decode_body()?),
+            #[cfg(feature = "v5")]
+            Id::CancelBroadcastSmResp => Self::CancelBroadcastSmResp(frame.decode_body()?),
+            command_id @ Id::Reserved(_) => Self::Raw {
+                command_id,
+                body: frame.body.to_vec(),
+            },
+        })
+    }
+
+    /// Encodes the PDU with a *command_status* of `ESME_ROK`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the body cannot be encoded.
+    pub fn encode(&self, seq_num: u32) -> Result<Vec<u8>, Error> {
+        self.encode_with_status(Status::ESME_ROK, seq_num)
+    }
+
+    /// Encodes the PDU with the given *command_status*, which is usually only
+    /// meaningful for response PDUs.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the body cannot be encoded.
+    pub fn encode_with_status(&self, status: Status, seq_num: u32) -> Result<Vec<u8>, Error> {
+        let config = bincode::config::standard().with_big_endian().with_fixed_int_encoding();
+        let body = self.encode_body()?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let header = Header::new(self.command_id(), status, seq_num).set_len(16 + body.len() as u32);
+
+        let mut res = bincode::encode_to_vec(header, config)?;
+        res.extend_from_slice(&body);
+        Ok(res)
+    }
+
+    /// Returns the *command_id* matching this PDU.
+    #[must_use]
+    pub fn command_id(&self) -> Id {
+        match self {
+            Self::GenericNack(_) => Id::GenericNack,
+            Self::BindReceiver(_) => Id::BindReceiver,
+            Self::BindReceiverResp(_) => Id::BindReceiverResp,
+            Self::BindTransmitter(_) => Id::BindTransmitter,
+            Self::BindTransmitterResp(_) => Id::BindTransmitterResp,
+            Self::QuerySm(_) => Id::QuerySm,
+            Self::QuerySmResp(_) => Id::QuerySmResp,
+            Self::SubmitSm(_) => Id::SubmitSm,
+            Self::SubmitSmResp(_) => Id::SubmitSmResp,
+            Self::DeliverSm(_) => Id::DeliverSm,
+            Self::DeliverSmResp(_) => Id::DeliverSmResp,
+            Self::Unbind(_) => Id::Unbind,
+            Self::UnbindResp(_) => Id::UnbindResp,
+            Self::ReplaceSm(_) => Id::ReplaceSm,
+            Self::ReplaceSmResp(_) => Id::ReplaceSmResp,
+            Self::CancelSm(_) => Id::CancelSm,
+            Self::CancelSmResp(_) => Id::CancelSmResp,
+            Self::BindTransceiver(_) => Id::BindTransceiver,
+            Self::BindTransceiverResp(_) => Id::BindTransceiverResp,
+            Self::Outbind(_) => Id::Outbind,
+            Self::EnquireLink(_) => Id::EnquireLink,
+            Self::EnquireLinkResp(_) => Id::EnquireLinkResp,
+            Self::SubmitMulti(_) => Id::SubmitMulti,
+            Self::SubmitMultiResp(_) => Id::SubmitMultiResp,
+            Self::AlertNotification(_) => Id::AlertNotification,
+            Self::DataSm(_) => Id::DataSm,
+            Self::DataSmResp(_) => Id::DataSmResp,
+            #[cfg(feature = "v5")]
+            Self::BroadcastSm(_) => Id::BroadcastSm,
+            #[cfg(feature = "v5")]
+            Self::BroadcastSmResp(_) => Id::BroadcastSmResp,
+            #[cfg(feature = "v5")]
+            Self::QueryBroadcastSm(_) => Id::QueryBroadcastSm,
+            #[cfg(feature = "v5")]
+            Self::QueryBroadcastSmResp(_) => Id::QueryBroadcastSmResp,
+            #[cfg(feature = "v5")]
+            Self::CancelBroadcastSm(_) => Id::CancelBroadcastSm,
+            #[cfg(feature = "v5")]
+            Self::CancelBroadcastSmResp(_) => Id::CancelBroadcastSmResp,
+            Self::Raw { command_id, .. } => command_id.clone(),
+        }
+    }
+
+    fn encode_body(&self) -> Result<Vec<u8>, bincode::error::EncodeError> {
+        let config = bincode::config::standard().with_big_endian().with_fixed_int_encoding();
+
+        match self {
+            Self::GenericNack(b) => bincode::encode_to_vec(b, config),
+            Self::BindReceiver(b) => bincode::encode_to_vec(b, config),
+            Self::BindReceiverResp(b) => bincode::encode_to_vec(b, config),
+            Self::BindTransmitter(b) => bincode::encode_to_vec(b, config),
+            Self::BindTransmitterResp(b) => bincode::encode_to_vec(b, config),
+            Self::QuerySm(b) => bincode::encode_to_vec(b, config),
+            Self::QuerySmResp(b) => bincode::encode_to_vec(b, config),
+            Self::SubmitSm(b) => bincode::encode_to_vec(b, config),
+            Self::SubmitSmResp(b) => bincode::encode_to_vec(b, config),
+            Self::DeliverSm(b) => bincode::encode_to_vec(b, config),
+            Self::DeliverSmResp(b) => bincode::encode_to_vec(b, config),
+            Self::Unbind(b) => bincode::encode_to_vec(b, config),
+            Self::UnbindResp(b) => bincode::encode_to_vec(b, config),
+            Self::ReplaceSm(b) => bincode::encode_to_vec(b, config),
+            Self::ReplaceSmResp(b) => bincode::encode_to_vec(b, config),
+            Self::CancelSm(b) => bincode::encode_to_vec(b, config),
+            Self::CancelSmResp(b) => bincode::encode_to_vec(b, config),
+            Self::BindTransceiver(b) => bincode::encode_to_vec(b, config),
+            Self::BindTransceiverResp(b) => bincode::encode_to_vec(b, config),
+            Self::Outbind(b) => bincode::encode_to_vec(b, config),
+            Self::EnquireLink(b) => bincode::encode_to_vec(b, config),
+            Self::EnquireLinkResp(b) => bincode::encode_to_vec(b, config),
+            Self::SubmitMulti(b) => bincode::encode_to_vec(b, config),
+            Self::SubmitMultiResp(b) => bincode::encode_to_vec(b, config),
+            Self::AlertNotification(b) => bincode::encode_to_vec(b, config),
+            Self::DataSm(b) => bincode::encode_to_vec(b, config),
+            Self::DataSmResp(b) => bincode::encode_to_vec(b, config),
+            #[cfg(feature = "v5")]
+            Self::BroadcastSm(b) => bincode::encode_to_vec(b, config),
+            #[cfg(feature = "v5")]
+            Self::BroadcastSmResp(b) => bincode::encode_to_vec(b, config),
+            #[cfg(feature = "v5")]
+            Self::QueryBroadcastSm(b) => bincode::encode_to_vec(b, config),
+            #[cfg(feature = "v5")]
+            Self::QueryBroadcastSmResp(b) => bincode::encode_to_vec(b, config),
+            #[cfg(feature = "v5")]
+            Self::CancelBroadcastSm(b) => bincode::encode_to_vec(b, config),
+            #[cfg(feature = "v5")]
+            Self::CancelBroadcastSmResp(b) => bincode::encode_to_vec(b, config),
+            Self::Raw { body, .. } => Ok(body.clone()),
+        }
+    }
+}
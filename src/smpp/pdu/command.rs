@@ -4,9 +4,10 @@
 //! Defines the various Operation PDUs that make up the SMPP protocol. The
 //! Operations are described in 6 categories: Session Management ([`session`]),
 //! Message Submission ([`submit`]), Message Delivery ([`delivery`]), Message
-//! Broadcast, Anciliary Submission and Anciliary Broadcast operations.
+//! Broadcast, Anciliary Submission ([`ancillary`]) and Anciliary Broadcast
+//! operations.
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Id {
     GenericNack,         /* = 0x80000000 */
     BindReceiver,        /* = 0x00000001 */
@@ -69,11 +70,9 @@ pub enum Id {
     Reserved(u32),
 }
 
-impl bincode::Decode for Id {
-    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
-        let u = u32::decode(decoder)?;
-
-        Ok(match u {
+impl From<u32> for Id {
+    fn from(u: u32) -> Self {
+        match u {
             0x80000000 => Self::GenericNack,
             0x00000001 => Self::BindReceiver,
             0x80000001 => Self::BindReceiverResp,
@@ -114,56 +113,81 @@ impl bincode::Decode for Id {
             #[cfg(feature = "v5")]
             0x80000113 => Self::CancelBroadcastSmResp,
             x => Self::Reserved(x),
-        })
+        }
+    }
+}
+
+impl From<&Id> for u32 {
+    fn from(x: &Id) -> Self {
+        match x {
+            Id::GenericNack => 0x80000000,
+            Id::BindReceiver => 0x00000001,
+            Id::BindReceiverResp => 0x80000001,
+            Id::BindTransmitter => 0x00000002,
+            Id::BindTransmitterResp => 0x80000002,
+            Id::QuerySm => 0x00000003,
+            Id::QuerySmResp => 0x80000003,
+            Id::SubmitSm => 0x00000004,
+            Id::SubmitSmResp => 0x80000004,
+            Id::DeliverSm => 0x00000005,
+            Id::DeliverSmResp => 0x80000005,
+            Id::Unbind => 0x00000006,
+            Id::UnbindResp => 0x80000006,
+            Id::ReplaceSm => 0x00000007,
+            Id::ReplaceSmResp => 0x80000007,
+            Id::CancelSm => 0x00000008,
+            Id::CancelSmResp => 0x80000008,
+            Id::BindTransceiver => 0x00000009,
+            Id::BindTransceiverResp => 0x80000009,
+            Id::Outbind => 0x0000000B,
+            Id::EnquireLink => 0x00000015,
+            Id::EnquireLinkResp => 0x80000015,
+            Id::SubmitMulti => 0x00000021,
+            Id::SubmitMultiResp => 0x80000021,
+            Id::AlertNotification => 0x00000102,
+            Id::DataSm => 0x00000103,
+            Id::DataSmResp => 0x80000103,
+            #[cfg(feature = "v5")]
+            Id::BroadcastSm => 0x00000111,
+            #[cfg(feature = "v5")]
+            Id::BroadcastSmResp => 0x80000111,
+            #[cfg(feature = "v5")]
+            Id::QueryBroadcastSm => 0x00000112,
+            #[cfg(feature = "v5")]
+            Id::QueryBroadcastSmResp => 0x80000112,
+            #[cfg(feature = "v5")]
+            Id::CancelBroadcastSm => 0x00000113,
+            #[cfg(feature = "v5")]
+            Id::CancelBroadcastSmResp => 0x80000113,
+            Id::Reserved(x) => *x,
+        }
+    }
+}
+
+impl bincode::Decode for Id {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
+        Ok(u32::decode(decoder)?.into())
     }
 }
 
 impl bincode::Encode for Id {
     fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
-        let u = match self {
-            Self::GenericNack => 0x80000000u32,
-            Self::BindReceiver => 0x00000001,
-            Self::BindReceiverResp => 0x80000001,
-            Self::BindTransmitter => 0x00000002,
-            Self::BindTransmitterResp => 0x80000002,
-            Self::QuerySm => 0x00000003,
-            Self::QuerySmResp => 0x80000003,
-            Self::SubmitSm => 0x00000004,
-            Self::SubmitSmResp => 0x80000004,
-            Self::DeliverSm => 0x00000005,
-            Self::DeliverSmResp => 0x80000005,
-            Self::Unbind => 0x00000006,
-            Self::UnbindResp => 0x80000006,
-            Self::ReplaceSm => 0x00000007,
-            Self::ReplaceSmResp => 0x80000007,
-            Self::CancelSm => 0x00000008,
-            Self::CancelSmResp => 0x80000008,
-            Self::BindTransceiver => 0x00000009,
-            Self::BindTransceiverResp => 0x80000009,
-            Self::Outbind => 0x0000000B,
-            Self::EnquireLink => 0x00000015,
-            Self::EnquireLinkResp => 0x80000015,
-            Self::SubmitMulti => 0x00000021,
-            Self::SubmitMultiResp => 0x80000021,
-            Self::AlertNotification => 0x00000102,
-            Self::DataSm => 0x00000103,
-            Self::DataSmResp => 0x80000103,
-            #[cfg(feature = "v5")]
-            Self::BroadcastSm => 0x00000111,
-            #[cfg(feature = "v5")]
-            Self::BroadcastSmResp => 0x80000111,
-            #[cfg(feature = "v5")]
-            Self::QueryBroadcastSm => 0x00000112,
-            #[cfg(feature = "v5")]
-            Self::QueryBroadcastSmResp => 0x80000112,
-            #[cfg(feature = "v5")]
-            Self::CancelBroadcastSm => 0x00000113,
-            #[cfg(feature = "v5")]
-            Self::CancelBroadcastSmResp => 0x80000113,
-            Self::Reserved(x) => *x,
-        };
+        u32::from(self).encode(encoder)
+    }
+}
 
-        u.encode(encoder)
+impl Id {
+    /// Returns `true` if the *command_id* denotes a response PDU, i.e. the
+    /// most significant bit is set.
+    #[must_use]
+    pub fn is_response(&self) -> bool {
+        u32::from(self) & 0x80000000 != 0
+    }
+
+    /// Returns the *command_id* of the response to this request.
+    #[must_use]
+    pub fn response(&self) -> Self {
+        (u32::from(self) | 0x80000000).into()
     }
 }
 
@@ -171,7 +195,7 @@ impl bincode::Encode for Id {
 /// *command_status* field of the SMPP message header and in the
 /// *error_status_code* field of a ***submit_multi_resp*** message.
 #[allow(non_camel_case_types)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum Status {
     /// No Error.
@@ -719,10 +743,16 @@ impl bincode::Encode for Status {
     }
 }
 
+pub use ancillary::*;
+#[cfg(feature = "v5")]
+pub use broadcast::*;
 pub use delivery::*;
 pub use session::*;
 pub use submit::*;
 
+pub mod ancillary;
+#[cfg(feature = "v5")]
+pub mod broadcast;
 pub mod delivery;
 pub mod session;
 pub mod submit;
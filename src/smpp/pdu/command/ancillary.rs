@@ -0,0 +1,264 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+//! Ancillary submission operations provide an ESME with the ability to query,
+//! cancel or replace short messages previously submitted to the SMSC (v5: MC).
+
+use crate::smpp::pdu::typedef::{COctet, MessageState, TLV};
+
+/// This command is issued by the ESME to query the status of a previously
+/// submitted short message.
+///
+/// The matching mechanism is based on the SMSC (v5: MC) assigned *message_id*
+/// and source address. Where the original ***submit_sm***, ***data_sm*** or
+/// ***submit_multi*** *source_addr* was defaulted to NULL, then the
+/// *source_addr* in the ***query_sm*** command should also be set to NULL.
+#[derive(Clone, Debug, bincode::Decode, bincode::Encode)]
+pub struct QuerySm {
+    /// Message ID of the message whose state is to be queried. This must be
+    /// the SMSC (v5: MC) assigned Message ID allocated to the original short
+    /// message when submitted to the SMSC (v5: MC).
+    pub message_id: COctet,
+    /// Type of Number of message originator. This is used for verification
+    /// purposes, and must match that supplied in the original request PDU.
+    pub source_addr_ton: u8,
+    /// Numbering Plan Identity of message originator. This is used for
+    /// verification purposes, and must match that supplied in the original
+    /// request PDU.
+    pub source_addr_npi: u8,
+    /// Address of message originator. This is used for verification purposes,
+    /// and must match that supplied in the original request PDU.
+    pub source_addr: COctet,
+}
+
+/// The ***query_sm_resp*** PDU is returned in response to a ***query_sm***
+/// request.
+#[derive(Clone, Debug)]
+pub struct QuerySmResp {
+    /// SMSC (v5: MC) Message ID of the message whose state is being queried.
+    pub message_id: COctet,
+    /// Date and time when the queried message reached its final state. For
+    /// messages, which have not yet reached a final state, this field will
+    /// contain a single NULL octet.
+    pub final_date: COctet,
+    /// Specifies the status of the queried short message.
+    pub message_state: MessageState,
+    /// Where appropriate this holds a network error code defining the reason
+    /// for failure of message delivery.
+    pub error_code: u8,
+
+    #[cfg(feature = "v5")]
+    pub query_sm_resp_tlv: Vec<TLV>,
+}
+
+impl bincode::Decode for QuerySmResp {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
+        let message_id = COctet::decode(decoder)?;
+        let final_date = COctet::decode(decoder)?;
+        let message_state = MessageState::decode(decoder)?;
+        let error_code = u8::decode(decoder)?;
+
+        #[cfg_attr(not(feature = "v5"), allow(unused_variables))]
+        let query_sm_resp_tlv = TLV::decode_all(decoder)?;
+
+        Ok(Self {
+            message_id,
+            final_date,
+            message_state,
+            error_code,
+
+            #[cfg(feature = "v5")]
+            query_sm_resp_tlv,
+        })
+    }
+}
+
+impl bincode::Encode for QuerySmResp {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
+        self.message_id.encode(encoder)?;
+        self.final_date.encode(encoder)?;
+        self.message_state.encode(encoder)?;
+        self.error_code.encode(encoder)?;
+
+        #[cfg(feature = "v5")]
+        for t in &self.query_sm_resp_tlv {
+            t.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// This command is issued by the ESME to cancel one or more previously
+/// submitted short messages that are pending delivery. The command may specify
+/// a particular message to cancel, or all messages matching a particular
+/// source, destination and *service_type*.
+///
+/// If the *message_id* is set to the ID of a previously submitted message, then
+/// provided the source address supplied in the ***cancel_sm*** operation
+/// matches that of the previously submitted message, that message will be
+/// cancelled.
+///
+/// If the *message_id* is NULL, all outstanding undelivered messages with the
+/// source and destination addresses (and *service_type* if specified) given in
+/// the PDU are cancelled.
+#[derive(Clone, Debug, bincode::Decode, bincode::Encode)]
+pub struct CancelSm {
+    /// Set to indicate SMS Application service, if cancellation of a group of
+    /// application service messages is desired. Otherwise set to NULL.
+    pub service_type: COctet,
+    /// Message ID of the message to be cancelled. This must be the SMSC (v5:
+    /// MC) assigned Message ID of the original message.
+    ///
+    /// Set to NULL if cancelling a group of messages.
+    pub message_id: COctet,
+    /// Type of Number of message originator. This is used for verification
+    /// purposes, and must match that supplied in the original message
+    /// submission request PDU.
+    pub source_addr_ton: u8,
+    /// Numbering Plan Identity of message originator. This is used for
+    /// verification purposes, and must match that supplied in the original
+    /// message submission request PDU.
+    pub source_addr_npi: u8,
+    /// Source address of message(s) to be cancelled. This is used for
+    /// verification purposes, and must match that supplied in the original
+    /// message submission request PDU(s).
+    pub source_addr: COctet,
+    /// Type of number of destination SME address of the message(s) to be
+    /// cancelled.
+    pub dest_addr_ton: u8,
+    /// Numbering Plan Indicator of destination SME address of the message(s)
+    /// to be cancelled.
+    pub dest_addr_npi: u8,
+    /// Destination address of message(s) to be cancelled.
+    pub destination_addr: COctet,
+}
+
+/// The ***cancel_sm_resp*** PDU is used to reply to a ***cancel_sm*** request.
+/// It comprises the SMPP message header only.
+#[derive(Clone, Debug, bincode::Decode, bincode::Encode)]
+pub struct CancelSmResp {}
+
+/// This command is issued by the ESME to replace a previously submitted short
+/// message that is pending delivery. The matching mechanism is based on the
+/// *message_id* and source address of the original message.
+///
+/// Where the original ***submit_sm*** *source_addr* was defaulted to NULL, then
+/// the *source_addr* in the ***replace_sm*** command should also be NULL.
+#[derive(Clone, Debug)]
+pub struct ReplaceSm {
+    /// Message ID of the message to be replaced. This must be the SMSC (v5:
+    /// MC) assigned Message ID allocated to the original short message when
+    /// submitted to the SMSC (v5: MC) by the ***submit_sm***, ***data_sm***
+    /// or ***submit_multi*** command.
+    pub message_id: COctet,
+    /// Type of Number of message originator. This is used for verification
+    /// purposes, and must match that supplied in the original message
+    /// submission request PDU.
+    pub source_addr_ton: u8,
+    /// Numbering Plan Indicator for source address of original message. This
+    /// is used for verification purposes, and must match that supplied in the
+    /// original message submission request PDU.
+    pub source_addr_npi: u8,
+    /// Originating address of the short message to be replaced. This is used
+    /// for verification purposes, and must match that supplied in the original
+    /// message submission request PDU.
+    pub source_addr: COctet,
+    /// New scheduled delivery time for the short message.
+    ///
+    /// Set to NULL to preserve the original scheduled delivery time.
+    pub schedule_delivery_time: COctet,
+    /// New expiry time for the short message.
+    ///
+    /// Set to NULL to preserve the original validity period setting.
+    pub validity_period: COctet,
+    /// Indicator to signify if a SMSC (v5: MC) delivery receipt, user/manual
+    /// or delivery ACK or intermediate notification is required.
+    pub registered_delivery: u8,
+    /// Indicates the short message to send from a list of predefined
+    /// (‘canned’) short messages stored on the SMSC (v5: MC).
+    ///
+    /// If not using a SMSC (v5: MC) canned message, set to NULL.
+    pub sm_default_msg_id: u8,
+    /// Length in octets of the *short_message* user data.
+    pub sm_length: u8,
+    /// Up to 255 octets of short message user data.
+    pub short_message: Vec<u8>,
+
+    /// Contains the extended short message user data. Up to 64K octets can be
+    /// transmitted.
+    ///
+    /// # Note:
+    ///
+    /// The *short_message* field must be set to NULL (zero length) if this TLV
+    /// is included.
+    #[cfg(feature = "v5")]
+    pub message_payload: Option<TLV>,
+}
+
+impl bincode::Decode for ReplaceSm {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
+        use bincode::de::read::Reader;
+
+        let message_id = COctet::decode(decoder)?;
+        let source_addr_ton = u8::decode(decoder)?;
+        let source_addr_npi = u8::decode(decoder)?;
+        let source_addr = COctet::decode(decoder)?;
+        let schedule_delivery_time = COctet::decode(decoder)?;
+        let validity_period = COctet::decode(decoder)?;
+        let registered_delivery = u8::decode(decoder)?;
+        let sm_default_msg_id = u8::decode(decoder)?;
+        let sm_length = u8::decode(decoder)?;
+
+        let mut short_message = vec![Default::default(); sm_length.into()];
+        decoder.reader().read(&mut short_message)?;
+
+        #[cfg(feature = "v5")]
+        let message_payload = TLV::decode_optional(decoder)?;
+
+        Ok(Self {
+            message_id,
+            source_addr_ton,
+            source_addr_npi,
+            source_addr,
+            schedule_delivery_time,
+            validity_period,
+            registered_delivery,
+            sm_default_msg_id,
+            sm_length,
+            short_message,
+
+            #[cfg(feature = "v5")]
+            message_payload,
+        })
+    }
+}
+
+impl bincode::Encode for ReplaceSm {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
+        use bincode::enc::write::Writer;
+
+        self.message_id.encode(encoder)?;
+        self.source_addr_ton.encode(encoder)?;
+        self.source_addr_npi.encode(encoder)?;
+        self.source_addr.encode(encoder)?;
+        self.schedule_delivery_time.encode(encoder)?;
+        self.validity_period.encode(encoder)?;
+        self.registered_delivery.encode(encoder)?;
+        self.sm_default_msg_id.encode(encoder)?;
+        self.sm_length.encode(encoder)?;
+        encoder.writer().write(&self.short_message)?;
+
+        #[cfg(feature = "v5")]
+        if let Some(t) = &self.message_payload {
+            t.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The ***replace_sm_resp*** PDU is used to reply to a ***replace_sm***
+/// request. It comprises the SMPP message header only.
+#[derive(Clone, Debug, bincode::Decode, bincode::Encode)]
+pub struct ReplaceSmResp {}
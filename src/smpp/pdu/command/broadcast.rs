@@ -0,0 +1,397 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+//! Message broadcast operations provide cell broadcast services to ESMEs.
+//! Ancillary broadcast operations allow an ESME to query the state of, or
+//! cancel, a previously submitted broadcast message.
+//!
+//! These operations are only defined by SMPP v5.
+
+use bincode::error::{DecodeError, EncodeError};
+
+use crate::smpp::pdu::typedef::{COctet, Tag, TLV};
+
+/// This operation is issued by the ESME to submit a message to the MC for
+/// broadcast to a specified geographical area or set of geographical areas.
+#[derive(Clone, Debug)]
+pub struct BroadcastSm {
+    pub service_type: COctet,
+    pub source_addr_ton: u8,
+    pub source_addr_npi: u8,
+    pub source_addr: COctet,
+    /// If using ***broadcast_sm*** to replace a message, previously submitted
+    /// for broadcast, then set *message_id* to the MC assigned message ID
+    /// allocated to the original message and returned in the
+    /// ***broadcast_sm_resp*** PDU.
+    ///
+    /// Otherwise set to NULL.
+    pub message_id: COctet,
+    pub priority_flag: u8,
+    pub schedule_delivery_time: COctet,
+    pub validity_period: COctet,
+    pub replace_if_present_flag: u8,
+    pub data_coding: u8,
+    pub sm_default_msg_id: u8,
+    /// Identifies the target Broadcast Area(s) for the requested message
+    /// broadcast. At least one instance is mandatory.
+    pub broadcast_area_identifier: Vec<TLV>,
+    /// Specifies the content type of the message.
+    pub broadcast_content_type: TLV,
+    /// Indicates the number of repeated broadcasts of a message requested by
+    /// the submitter.
+    pub broadcast_rep_num: TLV,
+    /// Indicates the frequency interval at which the broadcasts of a message
+    /// should be repeated.
+    pub broadcast_frequency_interval: TLV,
+    pub broadcast_request_optional_tlv: Vec<TLV>,
+}
+
+impl bincode::Decode for BroadcastSm {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let service_type = COctet::decode(decoder)?;
+        let source_addr_ton = u8::decode(decoder)?;
+        let source_addr_npi = u8::decode(decoder)?;
+        let source_addr = COctet::decode(decoder)?;
+        let message_id = COctet::decode(decoder)?;
+        let priority_flag = u8::decode(decoder)?;
+        let schedule_delivery_time = COctet::decode(decoder)?;
+        let validity_period = COctet::decode(decoder)?;
+        let replace_if_present_flag = u8::decode(decoder)?;
+        let data_coding = u8::decode(decoder)?;
+        let sm_default_msg_id = u8::decode(decoder)?;
+
+        let mut tlv = TLV::decode_all(decoder)?;
+
+        let broadcast_area_identifier = take_all(&mut tlv, |t| matches!(t, Tag::BroadcastAreaIdentifier));
+        if broadcast_area_identifier.is_empty() {
+            return Err(DecodeError::Other("missing mandatory TLV broadcast_area_identifier"));
+        }
+        let broadcast_content_type = take_one(&mut tlv, |t| matches!(t, Tag::BroadcastContentType))
+            .ok_or(DecodeError::Other("missing mandatory TLV broadcast_content_type"))?;
+        let broadcast_rep_num = take_one(&mut tlv, |t| matches!(t, Tag::BroadcastRepNum))
+            .ok_or(DecodeError::Other("missing mandatory TLV broadcast_rep_num"))?;
+        let broadcast_frequency_interval = take_one(&mut tlv, |t| matches!(t, Tag::BroadcastFrequencyInterval))
+            .ok_or(DecodeError::Other("missing mandatory TLV broadcast_frequency_interval"))?;
+
+        Ok(Self {
+            service_type,
+            source_addr_ton,
+            source_addr_npi,
+            source_addr,
+            message_id,
+            priority_flag,
+            schedule_delivery_time,
+            validity_period,
+            replace_if_present_flag,
+            data_coding,
+            sm_default_msg_id,
+            broadcast_area_identifier,
+            broadcast_content_type,
+            broadcast_rep_num,
+            broadcast_frequency_interval,
+            broadcast_request_optional_tlv: tlv,
+        })
+    }
+}
+
+impl bincode::Encode for BroadcastSm {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        if self.broadcast_area_identifier.is_empty() {
+            return Err(EncodeError::Other("missing mandatory TLV broadcast_area_identifier"));
+        }
+        check_tag(
+            &self.broadcast_area_identifier,
+            Tag::BroadcastAreaIdentifier,
+            "broadcast_area_identifier",
+        )?;
+        check_tag(
+            [&self.broadcast_content_type],
+            Tag::BroadcastContentType,
+            "broadcast_content_type",
+        )?;
+        check_tag([&self.broadcast_rep_num], Tag::BroadcastRepNum, "broadcast_rep_num")?;
+        check_tag(
+            [&self.broadcast_frequency_interval],
+            Tag::BroadcastFrequencyInterval,
+            "broadcast_frequency_interval",
+        )?;
+
+        self.service_type.encode(encoder)?;
+        self.source_addr_ton.encode(encoder)?;
+        self.source_addr_npi.encode(encoder)?;
+        self.source_addr.encode(encoder)?;
+        self.message_id.encode(encoder)?;
+        self.priority_flag.encode(encoder)?;
+        self.schedule_delivery_time.encode(encoder)?;
+        self.validity_period.encode(encoder)?;
+        self.replace_if_present_flag.encode(encoder)?;
+        self.data_coding.encode(encoder)?;
+        self.sm_default_msg_id.encode(encoder)?;
+
+        for t in &self.broadcast_area_identifier {
+            t.encode(encoder)?;
+        }
+        self.broadcast_content_type.encode(encoder)?;
+        self.broadcast_rep_num.encode(encoder)?;
+        self.broadcast_frequency_interval.encode(encoder)?;
+
+        for t in &self.broadcast_request_optional_tlv {
+            t.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The ***broadcast_sm_resp*** PDU is used to reply to a ***broadcast_sm***
+/// request.
+#[derive(Clone, Debug)]
+pub struct BroadcastSmResp {
+    /// This field contains the MC message ID of the submitted message.
+    pub message_id: COctet,
+    pub broadcast_response_optional_tlv: Vec<TLV>,
+}
+
+impl bincode::Decode for BroadcastSmResp {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let message_id = COctet::decode(decoder)?;
+
+        let broadcast_response_optional_tlv = TLV::decode_all(decoder)?;
+
+        Ok(Self {
+            message_id,
+            broadcast_response_optional_tlv,
+        })
+    }
+}
+
+impl bincode::Encode for BroadcastSmResp {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.message_id.encode(encoder)?;
+
+        for t in &self.broadcast_response_optional_tlv {
+            t.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// This command is issued by the ESME to query the status of a previously
+/// submitted Broadcast Message. The message can be queried on the basis of the
+/// MC assigned reference *message_id* returned in the ***broadcast_sm_resp***.
+#[derive(Clone, Debug)]
+pub struct QueryBroadcastSm {
+    /// Message ID of the message to be queried. This must be the MC assigned
+    /// Message ID allocated to the original short message when submitted to
+    /// the MC by the ***broadcast_sm*** command.
+    pub message_id: COctet,
+    pub source_addr_ton: u8,
+    pub source_addr_npi: u8,
+    pub source_addr: COctet,
+    pub broadcast_query_optional_tlv: Vec<TLV>,
+}
+
+impl bincode::Decode for QueryBroadcastSm {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let message_id = COctet::decode(decoder)?;
+        let source_addr_ton = u8::decode(decoder)?;
+        let source_addr_npi = u8::decode(decoder)?;
+        let source_addr = COctet::decode(decoder)?;
+
+        let broadcast_query_optional_tlv = TLV::decode_all(decoder)?;
+
+        Ok(Self {
+            message_id,
+            source_addr_ton,
+            source_addr_npi,
+            source_addr,
+            broadcast_query_optional_tlv,
+        })
+    }
+}
+
+impl bincode::Encode for QueryBroadcastSm {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.message_id.encode(encoder)?;
+        self.source_addr_ton.encode(encoder)?;
+        self.source_addr_npi.encode(encoder)?;
+        self.source_addr.encode(encoder)?;
+
+        for t in &self.broadcast_query_optional_tlv {
+            t.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The ***query_broadcast_sm_resp*** PDU is used to reply to a
+/// ***query_broadcast_sm*** request.
+#[derive(Clone, Debug)]
+pub struct QueryBroadcastSmResp {
+    /// MC Message ID of the message whose state is being queried.
+    pub message_id: COctet,
+    /// Specifies the status of the queried short message.
+    pub message_state: TLV,
+    /// Identifies one or more target Broadcast Area(s) for which the status
+    /// information applies. At least one instance is mandatory.
+    pub broadcast_area_identifier: Vec<TLV>,
+    /// The success rate indicator, per Broadcast Area. At least one instance
+    /// is mandatory.
+    pub broadcast_area_success: Vec<TLV>,
+    pub broadcast_query_response_optional_tlv: Vec<TLV>,
+}
+
+impl bincode::Decode for QueryBroadcastSmResp {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let message_id = COctet::decode(decoder)?;
+
+        let mut tlv = TLV::decode_all(decoder)?;
+
+        let message_state = take_one(&mut tlv, |t| matches!(t, Tag::MessageState))
+            .ok_or(DecodeError::Other("missing mandatory TLV message_state"))?;
+        let broadcast_area_identifier = take_all(&mut tlv, |t| matches!(t, Tag::BroadcastAreaIdentifier));
+        if broadcast_area_identifier.is_empty() {
+            return Err(DecodeError::Other("missing mandatory TLV broadcast_area_identifier"));
+        }
+        let broadcast_area_success = take_all(&mut tlv, |t| matches!(t, Tag::BroadcastAreaSuccess));
+        if broadcast_area_success.is_empty() {
+            return Err(DecodeError::Other("missing mandatory TLV broadcast_area_success"));
+        }
+
+        Ok(Self {
+            message_id,
+            message_state,
+            broadcast_area_identifier,
+            broadcast_area_success,
+            broadcast_query_response_optional_tlv: tlv,
+        })
+    }
+}
+
+impl bincode::Encode for QueryBroadcastSmResp {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        if self.broadcast_area_identifier.is_empty() {
+            return Err(EncodeError::Other("missing mandatory TLV broadcast_area_identifier"));
+        }
+        if self.broadcast_area_success.is_empty() {
+            return Err(EncodeError::Other("missing mandatory TLV broadcast_area_success"));
+        }
+        check_tag([&self.message_state], Tag::MessageState, "message_state")?;
+        check_tag(
+            &self.broadcast_area_identifier,
+            Tag::BroadcastAreaIdentifier,
+            "broadcast_area_identifier",
+        )?;
+        check_tag(
+            &self.broadcast_area_success,
+            Tag::BroadcastAreaSuccess,
+            "broadcast_area_success",
+        )?;
+
+        self.message_id.encode(encoder)?;
+        self.message_state.encode(encoder)?;
+
+        for t in &self.broadcast_area_identifier {
+            t.encode(encoder)?;
+        }
+        for t in &self.broadcast_area_success {
+            t.encode(encoder)?;
+        }
+        for t in &self.broadcast_query_response_optional_tlv {
+            t.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// This command is issued by the ESME to cancel a broadcast message which has
+/// been previously submitted to the Message Centre for broadcast via
+/// ***broadcast_sm*** and which is still pending delivery.
+///
+/// If the *message_id* is set to the ID of a previously submitted message, then
+/// provided the source address supplied in the ***cancel_broadcast_sm***
+/// operation matches that of the previously submitted message, that message
+/// will be cancelled.
+///
+/// If the *message_id* is NULL, all outstanding undelivered messages with the
+/// source address (and *service_type* if specified) given in the PDU are
+/// cancelled.
+#[derive(Clone, Debug)]
+pub struct CancelBroadcastSm {
+    pub service_type: COctet,
+    pub message_id: COctet,
+    pub source_addr_ton: u8,
+    pub source_addr_npi: u8,
+    pub source_addr: COctet,
+    pub broadcast_cancel_optional_tlv: Vec<TLV>,
+}
+
+impl bincode::Decode for CancelBroadcastSm {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let service_type = COctet::decode(decoder)?;
+        let message_id = COctet::decode(decoder)?;
+        let source_addr_ton = u8::decode(decoder)?;
+        let source_addr_npi = u8::decode(decoder)?;
+        let source_addr = COctet::decode(decoder)?;
+
+        let broadcast_cancel_optional_tlv = TLV::decode_all(decoder)?;
+
+        Ok(Self {
+            service_type,
+            message_id,
+            source_addr_ton,
+            source_addr_npi,
+            source_addr,
+            broadcast_cancel_optional_tlv,
+        })
+    }
+}
+
+impl bincode::Encode for CancelBroadcastSm {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.service_type.encode(encoder)?;
+        self.message_id.encode(encoder)?;
+        self.source_addr_ton.encode(encoder)?;
+        self.source_addr_npi.encode(encoder)?;
+        self.source_addr.encode(encoder)?;
+
+        for t in &self.broadcast_cancel_optional_tlv {
+            t.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The ***cancel_broadcast_sm_resp*** PDU is used to reply to a
+/// ***cancel_broadcast_sm*** request. It comprises the SMPP message header
+/// only.
+#[derive(Clone, Debug, bincode::Decode, bincode::Encode)]
+pub struct CancelBroadcastSmResp {}
+
+/// Removes the first TLV whose tag satisfies `f` from `tlv`.
+fn take_one(tlv: &mut Vec<TLV>, f: fn(&Tag) -> bool) -> Option<TLV> {
+    tlv.iter().position(|t| f(&t.tag)).map(|i| tlv.remove(i))
+}
+
+/// Removes all TLVs whose tag satisfies `f` from `tlv`, keeping their order.
+fn take_all(tlv: &mut Vec<TLV>, f: fn(&Tag) -> bool) -> Vec<TLV> {
+    let (taken, rest) = tlv.drain(..).partition(|t| f(&t.tag));
+    *tlv = rest;
+    taken
+}
+
+/// Checks that the TLVs given for a mandatory field all carry its tag, as
+/// decoding would find them by.
+fn check_tag<'a>(tlv: impl IntoIterator<Item = &'a TLV>, tag: Tag, field: &str) -> Result<(), EncodeError> {
+    match tlv.into_iter().find(|t| t.tag != tag) {
+        Some(t) => Err(EncodeError::OtherString(format!(
+            "mandatory TLV {} has tag {:?}",
+            field, t.tag
+        ))),
+        None => Ok(()),
+    }
+}
@@ -58,10 +58,7 @@ impl bincode::Decode for DeliverSm {
         let mut short_message = vec![Default::default(); sm_length.into()];
         decoder.reader().read(&mut short_message)?;
 
-        let mut msg_delivery_tlv = vec![];
-        while let Ok(t) = TLV::decode(decoder) {
-            msg_delivery_tlv.push(t);
-        }
+        let msg_delivery_tlv = TLV::decode_all(decoder)?;
 
         Ok(Self {
             service_type,
@@ -130,10 +127,8 @@ impl bincode::Decode for DeliverSmResp {
     fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
         let message_id = COctet::decode(decoder)?;
 
-        let mut msg_delivery_resp_tlv = vec![];
-        while let Ok(t) = TLV::decode(decoder) {
-            msg_delivery_resp_tlv.push(t);
-        }
+        #[cfg_attr(not(feature = "v5"), allow(unused_variables))]
+        let msg_delivery_resp_tlv = TLV::decode_all(decoder)?;
 
         Ok(Self {
             message_id,
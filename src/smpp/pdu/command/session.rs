@@ -73,7 +73,7 @@ pub mod bind {
     impl bincode::Decode for BindResp {
         fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
             let system_id = COctet::decode(decoder)?;
-            let sc_interface_version = TLV::decode(decoder).ok();
+            let sc_interface_version = TLV::decode_optional(decoder)?;
 
             Ok(Self {
                 system_id,
@@ -203,10 +203,7 @@ impl bincode::Decode for AlertNotification {
         let esme_addr_npi = u8::decode(decoder)?;
         let esme_addr = COctet::decode(decoder)?;
 
-        let mut ms_availability_status = vec![];
-        while let Ok(t) = TLV::decode(decoder) {
-            ms_availability_status.push(t);
-        }
+        let ms_availability_status = TLV::decode_all(decoder)?;
 
         Ok(Self {
             source_addr_ton,
@@ -4,7 +4,14 @@
 //! Message submission operations provide an ESME with the ability to submit
 //! messages for onward delivery to mobile stations.
 
-use crate::smpp::pdu::typedef::{COctet, TLV};
+pub use segment::*;
+
+use crate::smpp::pdu::{
+    command::Status,
+    typedef::{COctet, TLV},
+};
+
+mod segment;
 
 /// This operation is used by an ESME to submit a short message to the SMSC (v5:
 /// MC) for onward transmission to a specified short message entity (SME).
@@ -56,10 +63,7 @@ impl bincode::Decode for SubmitSm {
         let mut short_message = vec![Default::default(); sm_length.into()];
         decoder.reader().read(&mut short_message)?;
 
-        let mut msg_submission_tlv = vec![];
-        while let Ok(t) = TLV::decode(decoder) {
-            msg_submission_tlv.push(t);
-        }
+        let msg_submission_tlv = TLV::decode_all(decoder)?;
 
         Ok(Self {
             service_type,
@@ -128,10 +132,8 @@ impl bincode::Decode for SubmitSmResp {
     fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
         let message_id = COctet::decode(decoder)?;
 
-        let mut msg_submission_resp_tlv = vec![];
-        while let Ok(t) = TLV::decode(decoder) {
-            msg_submission_resp_tlv.push(t);
-        }
+        #[cfg_attr(not(feature = "v5"), allow(unused_variables))]
+        let msg_submission_resp_tlv = TLV::decode_all(decoder)?;
 
         Ok(Self {
             message_id,
@@ -155,6 +157,281 @@ impl bincode::Encode for SubmitSmResp {
     }
 }
 
+/// The ***submit_multi*** operation is an enhanced variation of ***submit_sm***
+/// designed to support up to 254 different destinations per request. This can
+/// include a mixture of SME addresses and distribution list names.
+#[derive(Clone, Debug)]
+pub struct SubmitMulti {
+    pub service_type: COctet,
+    pub source_addr_ton: u8,
+    pub source_addr_npi: u8,
+    pub source_addr: COctet,
+    /// Contains one or more SME addresses or/and Distribution List names. The
+    /// *number_of_dests* field is derived from its length.
+    pub dest_address: Vec<DestAddress>,
+    pub esm_class: u8,
+    pub protocol_id: u8,
+    pub priority_flag: u8,
+    pub schedule_delivery_time: COctet,
+    pub validity_period: COctet,
+    pub registered_delivery: u8,
+    pub replace_if_present_flag: u8,
+    pub data_coding: u8,
+    pub sm_default_msg_id: u8,
+    pub sm_length: u8,
+    pub short_message: Vec<u8>,
+    pub msg_submission_tlv: Vec<TLV>,
+}
+
+impl SubmitMulti {
+    /// The maximum number of destinations allowed in a single
+    /// ***submit_multi***.
+    pub const MAX_DESTS: usize = 254;
+}
+
+impl bincode::Decode for SubmitMulti {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
+        use bincode::de::read::Reader;
+
+        let service_type = COctet::decode(decoder)?;
+        let source_addr_ton = u8::decode(decoder)?;
+        let source_addr_npi = u8::decode(decoder)?;
+        let source_addr = COctet::decode(decoder)?;
+
+        let number_of_dests = u8::decode(decoder)?;
+        if number_of_dests == 0 {
+            return Err(bincode::error::DecodeError::Other("no destination in submit_multi"));
+        }
+        if usize::from(number_of_dests) > Self::MAX_DESTS {
+            return Err(bincode::error::DecodeError::Other(
+                "too many destinations in submit_multi",
+            ));
+        }
+
+        let mut dest_address = Vec::with_capacity(number_of_dests.into());
+        for _ in 0..number_of_dests {
+            dest_address.push(DestAddress::decode(decoder)?);
+        }
+
+        let esm_class = u8::decode(decoder)?;
+        let protocol_id = u8::decode(decoder)?;
+        let priority_flag = u8::decode(decoder)?;
+        let schedule_delivery_time = COctet::decode(decoder)?;
+        let validity_period = COctet::decode(decoder)?;
+        let registered_delivery = u8::decode(decoder)?;
+        let replace_if_present_flag = u8::decode(decoder)?;
+        let data_coding = u8::decode(decoder)?;
+        let sm_default_msg_id = u8::decode(decoder)?;
+        let sm_length = u8::decode(decoder)?;
+
+        let mut short_message = vec![Default::default(); sm_length.into()];
+        decoder.reader().read(&mut short_message)?;
+
+        let msg_submission_tlv = TLV::decode_all(decoder)?;
+
+        Ok(Self {
+            service_type,
+            source_addr_ton,
+            source_addr_npi,
+            source_addr,
+            dest_address,
+            esm_class,
+            protocol_id,
+            priority_flag,
+            schedule_delivery_time,
+            validity_period,
+            registered_delivery,
+            replace_if_present_flag,
+            data_coding,
+            sm_default_msg_id,
+            sm_length,
+            short_message,
+            msg_submission_tlv,
+        })
+    }
+}
+
+impl bincode::Encode for SubmitMulti {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
+        use bincode::enc::write::Writer;
+
+        if self.dest_address.is_empty() {
+            return Err(bincode::error::EncodeError::Other("no destination in submit_multi"));
+        }
+        if self.dest_address.len() > Self::MAX_DESTS {
+            return Err(bincode::error::EncodeError::Other(
+                "too many destinations in submit_multi",
+            ));
+        }
+
+        self.service_type.encode(encoder)?;
+        self.source_addr_ton.encode(encoder)?;
+        self.source_addr_npi.encode(encoder)?;
+        self.source_addr.encode(encoder)?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        (self.dest_address.len() as u8).encode(encoder)?;
+        for d in &self.dest_address {
+            d.encode(encoder)?;
+        }
+
+        self.esm_class.encode(encoder)?;
+        self.protocol_id.encode(encoder)?;
+        self.priority_flag.encode(encoder)?;
+        self.schedule_delivery_time.encode(encoder)?;
+        self.validity_period.encode(encoder)?;
+        self.registered_delivery.encode(encoder)?;
+        self.replace_if_present_flag.encode(encoder)?;
+        self.data_coding.encode(encoder)?;
+        self.sm_default_msg_id.encode(encoder)?;
+        self.sm_length.encode(encoder)?;
+        encoder.writer().write(&self.short_message)?;
+
+        for t in &self.msg_submission_tlv {
+            t.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A destination of a ***submit_multi***, which is either an SME address or
+/// the name of a distribution list provisioned on the SMSC (v5: MC).
+#[derive(Clone, Debug)]
+pub enum DestAddress {
+    /// SME Address (*dest_flag* = `0x01`)
+    SmeAddress {
+        dest_addr_ton: u8,
+        dest_addr_npi: u8,
+        destination_addr: COctet,
+    },
+    /// Distribution List Name (*dest_flag* = `0x02`)
+    DistributionList(COctet),
+}
+
+impl bincode::Decode for DestAddress {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
+        let dest_flag = u8::decode(decoder)?;
+
+        match dest_flag {
+            0x01 => {
+                let dest_addr_ton = u8::decode(decoder)?;
+                let dest_addr_npi = u8::decode(decoder)?;
+                let destination_addr = COctet::decode(decoder)?;
+
+                Ok(Self::SmeAddress {
+                    dest_addr_ton,
+                    dest_addr_npi,
+                    destination_addr,
+                })
+            }
+            0x02 => Ok(Self::DistributionList(COctet::decode(decoder)?)),
+            x => Err(bincode::error::DecodeError::OtherString(format!(
+                "invalid dest_flag {:#04x} in submit_multi",
+                x
+            ))),
+        }
+    }
+}
+
+impl bincode::Encode for DestAddress {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
+        match self {
+            Self::SmeAddress {
+                dest_addr_ton,
+                dest_addr_npi,
+                destination_addr,
+            } => {
+                0x01u8.encode(encoder)?;
+                dest_addr_ton.encode(encoder)?;
+                dest_addr_npi.encode(encoder)?;
+                destination_addr.encode(encoder)
+            }
+            Self::DistributionList(dl_name) => {
+                0x02u8.encode(encoder)?;
+                dl_name.encode(encoder)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SubmitMultiResp {
+    pub message_id: COctet,
+    /// The SME addresses to which submission was unsuccessful. The
+    /// *no_unsuccess* field is derived from its length.
+    pub unsuccess_sme: Vec<UnsuccessSme>,
+
+    #[cfg(feature = "v5")]
+    pub msg_submission_resp_tlv: Vec<TLV>,
+}
+
+impl bincode::Decode for SubmitMultiResp {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
+        let message_id = COctet::decode(decoder)?;
+
+        let no_unsuccess = u8::decode(decoder)?;
+        if usize::from(no_unsuccess) > SubmitMulti::MAX_DESTS {
+            return Err(bincode::error::DecodeError::Other(
+                "too many unsuccessful SMEs in submit_multi_resp",
+            ));
+        }
+
+        let mut unsuccess_sme = Vec::with_capacity(no_unsuccess.into());
+        for _ in 0..no_unsuccess {
+            unsuccess_sme.push(UnsuccessSme::decode(decoder)?);
+        }
+
+        #[cfg_attr(not(feature = "v5"), allow(unused_variables))]
+        let msg_submission_resp_tlv = TLV::decode_all(decoder)?;
+
+        Ok(Self {
+            message_id,
+            unsuccess_sme,
+
+            #[cfg(feature = "v5")]
+            msg_submission_resp_tlv,
+        })
+    }
+}
+
+impl bincode::Encode for SubmitMultiResp {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
+        if self.unsuccess_sme.len() > SubmitMulti::MAX_DESTS {
+            return Err(bincode::error::EncodeError::Other(
+                "too many unsuccessful SMEs in submit_multi_resp",
+            ));
+        }
+
+        self.message_id.encode(encoder)?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        (self.unsuccess_sme.len() as u8).encode(encoder)?;
+        for u in &self.unsuccess_sme {
+            u.encode(encoder)?;
+        }
+
+        #[cfg(feature = "v5")]
+        for t in &self.msg_submission_resp_tlv {
+            t.encode(encoder)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An SME address to which a ***submit_multi*** could not be delivered,
+/// together with the reason.
+#[derive(Clone, Debug, bincode::Decode, bincode::Encode)]
+pub struct UnsuccessSme {
+    pub dest_addr_ton: u8,
+    pub dest_addr_npi: u8,
+    pub destination_addr: COctet,
+    /// Indicates the success or failure of the ***submit_multi*** request to
+    /// this SME address.
+    pub error_status_code: Status,
+}
+
 /// The ***data_sm*** operation is similar to the submit_sm in that it provides
 /// a means to submit a mobile-terminated message. However, data_sm is intended
 /// for packet-based applications such as WAP in that it features a reduced PDU
@@ -187,10 +464,7 @@ impl bincode::Decode for DataSm {
         let registered_delivery = u8::decode(decoder)?;
         let data_coding = u8::decode(decoder)?;
 
-        let mut msg_submission_tlv = vec![];
-        while let Ok(t) = TLV::decode(decoder) {
-            msg_submission_tlv.push(t);
-        }
+        let msg_submission_tlv = TLV::decode_all(decoder)?;
 
         Ok(Self {
             service_type,
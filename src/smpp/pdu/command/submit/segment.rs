@@ -0,0 +1,187 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::fmt;
+
+use super::SubmitSm;
+use crate::smpp::pdu::typedef::{
+    encode_text, encode_text_as, split_text, DataCoding, EncodedText, EsmClassGsm, InformationElement, Tag, TextError,
+    UdhError, UserDataHeader, TLV,
+};
+
+/// The capacity of a short message, in octets.
+const OCTETS: usize = 140;
+
+/// How a text too long for a single short message is carried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segmentation {
+    /// Concatenated short messages, each starting with a UDH that holds an
+    /// 8-bit *reference*.
+    Udh { reference: u8 },
+    /// Concatenated short messages, each starting with a UDH that holds a
+    /// 16-bit *reference*.
+    Udh16 { reference: u16 },
+    /// Concatenated short messages, each carrying the *sar_msg_ref_num*,
+    /// *sar_total_segments* and *sar_segment_seqnum* TLVs.
+    Sar { reference: u16 },
+    /// A single ***submit_sm*** carrying the whole text in the
+    /// *message_payload* TLV, leaving the *short_message* empty.
+    Payload,
+}
+
+impl SubmitSm {
+    /// Encodes `text` into one or more ***submit_sm*** PDUs, each a copy of
+    /// `self` with the *data_coding*, *short_message* and *sm_length* set.
+    ///
+    /// The text is encoded with [`encode_text`]. If it fits in a single short
+    /// message, only one PDU is returned, without UDH or SAR TLVs, unless
+    /// [`Segmentation::Payload`] is used. Otherwise the text is split without
+    /// splitting a GSM escape sequence or a UTF-16 surrogate pair, and
+    /// [`EsmClassGsm::UDHI`] or the SAR TLVs are added to each part.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the text needs more than 255
+    /// parts, or more than 65535 octets in the *message_payload*.
+    pub fn segment(&self, text: &str, segmentation: Segmentation) -> Result<Vec<Self>, SegmentError> {
+        let EncodedText {
+            data_coding,
+            bytes,
+            segments,
+        } = encode_text(text);
+
+        if segmentation == Segmentation::Payload {
+            let len = u16::try_from(bytes.len()).map_err(|_| SegmentError::TooLong)?;
+
+            let mut pdu = self.with_short_message(data_coding, vec![]);
+            pdu.msg_submission_tlv.push(TLV {
+                tag: Tag::MessagePayload,
+                len,
+                val: bytes,
+            });
+
+            return Ok(vec![pdu]);
+        }
+
+        if segments == 1 {
+            return Ok(vec![self.with_short_message(data_coding, bytes)]);
+        }
+
+        let udh = |total, sequence| {
+            let element = match segmentation {
+                Segmentation::Udh { reference } => InformationElement::Concatenated8 {
+                    reference,
+                    total,
+                    sequence,
+                },
+                Segmentation::Udh16 { reference } => InformationElement::Concatenated16 {
+                    reference,
+                    total,
+                    sequence,
+                },
+                _ => return None,
+            };
+
+            Some(UserDataHeader {
+                elements: vec![element],
+            })
+        };
+
+        let octets = OCTETS - udh(0, 0).map_or(0, |udh| udh.encoded_len());
+        let capacity = match data_coding {
+            DataCoding::GSM => octets * 8 / 7,
+            _ => octets,
+        };
+
+        let parts = split_text(data_coding, text, capacity);
+        let total = u8::try_from(parts.len()).map_err(|_| SegmentError::TooLong)?;
+
+        let mut pdus = vec![];
+        for (sequence, part) in (1..=total).zip(parts) {
+            let mut pdu = if let Some(udh) = udh(total, sequence) {
+                let short_message = match data_coding {
+                    DataCoding::GSM => udh.join_gsm(part)?,
+                    _ => udh.join(&encode_text_as(data_coding, part)?)?,
+                };
+
+                let mut pdu = self.with_short_message(data_coding, short_message);
+                pdu.esm_class |= EsmClassGsm::UDHI as u8;
+                pdu
+            } else {
+                self.with_short_message(data_coding, encode_text_as(data_coding, part)?)
+            };
+
+            if let Segmentation::Sar { reference } = segmentation {
+                pdu.msg_submission_tlv.extend([
+                    TLV {
+                        tag: Tag::SarMsgRefNum,
+                        len: 2,
+                        val: reference.to_be_bytes().to_vec(),
+                    },
+                    TLV {
+                        tag: Tag::SarTotalSegments,
+                        len: 1,
+                        val: vec![total],
+                    },
+                    TLV {
+                        tag: Tag::SarSegmentSeqnum,
+                        len: 1,
+                        val: vec![sequence],
+                    },
+                ]);
+            }
+
+            pdus.push(pdu);
+        }
+
+        Ok(pdus)
+    }
+
+    fn with_short_message(&self, data_coding: DataCoding, short_message: Vec<u8>) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        let sm_length = short_message.len() as u8;
+
+        Self {
+            data_coding: data_coding.into(),
+            sm_length,
+            short_message,
+            ..self.clone()
+        }
+    }
+}
+
+/// Errors that occur while segmenting a text with [`SubmitSm::segment`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SegmentError {
+    /// The text needs more than 255 parts, or more than 65535 octets in the
+    /// *message_payload*.
+    TooLong,
+    /// The text could not be encoded.
+    Text(TextError),
+}
+
+impl fmt::Display for SegmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLong => write!(f, "text too long to segment"),
+            Self::Text(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for SegmentError {}
+
+impl From<TextError> for SegmentError {
+    fn from(e: TextError) -> Self {
+        Self::Text(e)
+    }
+}
+
+impl From<UdhError> for SegmentError {
+    fn from(e: UdhError) -> Self {
+        match e {
+            UdhError::Unrepresentable(e) => Self::Text(e.into()),
+            UdhError::Truncated | UdhError::TooLong => Self::TooLong,
+        }
+    }
+}
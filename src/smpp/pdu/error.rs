@@ -0,0 +1,63 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::fmt;
+
+use bincode::error::{DecodeError, EncodeError};
+
+use super::command::Status;
+
+/// Errors that occur while converting between [`Pdu`](super::Pdu) and bytes.
+#[derive(Debug)]
+pub enum Error {
+    /// The PDU could not be decoded.
+    Decode(DecodeError),
+    /// The PDU could not be encoded.
+    Encode(EncodeError),
+    /// The *command_length* is either too short or too long for the PDU.
+    InvalidCommandLength(u32),
+}
+
+impl Error {
+    /// Returns the *command_status* to report to the peer that sent the
+    /// offending PDU.
+    #[must_use]
+    pub const fn command_status(&self) -> Status {
+        match self {
+            Self::InvalidCommandLength(_) => Status::ESME_RINVCMDLEN,
+            Self::Decode(_) | Self::Encode(_) => Status::ESME_RSYSERR,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(e) => write!(f, "failed to decode PDU: {}", e),
+            Self::Encode(e) => write!(f, "failed to encode PDU: {}", e),
+            Self::InvalidCommandLength(len) => write!(f, "invalid command_length {}", len),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode(e) => Some(e),
+            Self::Encode(e) => Some(e),
+            Self::InvalidCommandLength(_) => None,
+        }
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(e: DecodeError) -> Self {
+        Self::Decode(e)
+    }
+}
+
+impl From<EncodeError> for Error {
+    fn from(e: EncodeError) -> Self {
+        Self::Encode(e)
+    }
+}
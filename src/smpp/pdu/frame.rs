@@ -0,0 +1,100 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use bincode::error::DecodeError;
+
+use super::{Error, Header};
+
+/// A single PDU as it appears on the wire: a [`Header`] followed by exactly
+/// *command_length* - 16 octets of body.
+///
+/// Bodies are always decoded from [`Frame::body`] only, so that a decoder never
+/// reads into the next PDU and trailing TLVs can be told apart from the end of
+/// the PDU.
+#[derive(Clone, Debug)]
+pub struct Frame<'a> {
+    pub header: Header,
+    pub body: &'a [u8],
+}
+
+impl<'a> Frame<'a> {
+    /// The length of the PDU header in octets, which is also the minimum value
+    /// of *command_length*.
+    pub const HEADER_LEN: usize = 16;
+
+    /// Returns the *command_length* of the PDU at the start of `buf`, or `None`
+    /// if fewer than four octets are available.
+    #[must_use]
+    pub fn peek_len(buf: &[u8]) -> Option<u32> {
+        buf.get(..4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Splits the first PDU off `buf`, returning the frame together with the
+    /// number of octets it occupies. Returns `Ok(None)` if `buf` does not yet
+    /// hold a complete PDU.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidCommandLength`] if the
+    /// *command_length* is shorter than the header, or an error if the header
+    /// cannot be decoded.
+    pub fn parse(buf: &'a [u8]) -> Result<Option<(Self, usize)>, Error> {
+        let command_length = match Self::peek_len(buf) {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+
+        let len = usize::try_from(command_length).map_err(|_| Error::InvalidCommandLength(command_length))?;
+        if len < Self::HEADER_LEN {
+            return Err(Error::InvalidCommandLength(command_length));
+        }
+
+        if buf.len() < len {
+            return Ok(None);
+        }
+
+        let config = bincode::config::standard().with_big_endian().with_fixed_int_encoding();
+        let (header, _) = bincode::decode_from_slice::<Header, _>(&buf[..Self::HEADER_LEN], config)?;
+
+        Ok(Some((
+            Self {
+                header,
+                body: &buf[Self::HEADER_LEN..len],
+            },
+            len,
+        )))
+    }
+
+    /// Parses a buffer holding exactly one PDU.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidCommandLength`] if the
+    /// *command_length* does not match the length of `buf`, or an error if the
+    /// header cannot be decoded.
+    pub fn from_bytes(buf: &'a [u8]) -> Result<Self, Error> {
+        match Self::parse(buf)? {
+            Some((frame, len)) if len == buf.len() => Ok(frame),
+            _ => Err(Error::InvalidCommandLength(Self::peek_len(buf).unwrap_or_default())),
+        }
+    }
+
+    /// Decodes the body of the PDU.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidCommandLength`] if the body is
+    /// too short for the PDU or leaves octets undecoded, or an error if the
+    /// body is malformed otherwise.
+    pub fn decode_body<T: bincode::Decode>(&self) -> Result<T, Error> {
+        let config = bincode::config::standard().with_big_endian().with_fixed_int_encoding();
+
+        match bincode::decode_from_slice(self.body, config) {
+            Ok((t, len)) if len == self.body.len() => Ok(t),
+            Ok(_) | Err(DecodeError::UnexpectedEnd { .. }) => {
+                Err(Error::InvalidCommandLength(self.header.command_length))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
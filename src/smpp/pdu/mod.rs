@@ -1,6 +1,10 @@
 // Copyright (c) 2022 GreenYun Organization
 // SPDX-License-Identifier: MIT
 
+pub use body::Pdu;
+pub use error::Error;
+pub use frame::Frame;
+
 #[derive(Clone, Debug, bincode::Decode, bincode::Encode)]
 pub struct Header {
     pub command_length: u32,
@@ -50,5 +54,8 @@ impl Header {
     }
 }
 
+mod body;
 pub mod command;
+mod error;
+mod frame;
 pub mod typedef;
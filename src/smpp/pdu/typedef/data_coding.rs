@@ -1,9 +1,17 @@
 // Copyright (c) 2022 GreenYun Organization
 // SPDX-License-Identifier: MIT
 
-pub use gsm::{decode as gsm_decode, encode as gsm_encode};
+pub use gsm::{
+    decode as gsm_decode, decode_with as gsm_decode_with, encode as gsm_encode, encode_lossy as gsm_encode_lossy,
+    encode_national as gsm_encode_national, encode_with as gsm_encode_with, GsmEncodeError, NationalLanguage,
+    ShiftTables,
+};
+pub(super) use gsm::{decode_with_fill, encode_with_fill};
+pub use scheme::*;
+pub(crate) use text::split_text;
+pub use text::{decode_text, encode_text, encode_text_as, EncodedText, TextError};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum DataCoding {
     /// SMSC Default Alphabet (GSM 03.38 default 7-bit)
@@ -34,11 +42,18 @@ pub enum DataCoding {
     Reserved(u8),
 }
 
-impl bincode::Decode for DataCoding {
-    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
-        let u = u8::decode(decoder)?;
+impl DataCoding {
+    /// The GSM 03.38 default alphabet, which is the SMSC (v5: MC) default.
+    #[cfg(not(feature = "v5"))]
+    pub const GSM: Self = Self::SmscDefault;
+    /// The GSM 03.38 default alphabet, which is the SMSC (v5: MC) default.
+    #[cfg(feature = "v5")]
+    pub const GSM: Self = Self::McSpecific;
+}
 
-        Ok(match u {
+impl From<u8> for DataCoding {
+    fn from(u: u8) -> Self {
+        match u {
             #[cfg(not(feature = "v5"))]
             0b00000000 => Self::SmscDefault,
             #[cfg(feature = "v5")]
@@ -53,31 +68,47 @@ impl bincode::Decode for DataCoding {
             0b00001101 => Self::EucJp,
             0b00001110 => Self::EucKr,
             x => Self::Reserved(x),
-        })
+        }
     }
 }
 
-impl bincode::Encode for DataCoding {
-    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
-        let u = match self {
+impl From<DataCoding> for u8 {
+    fn from(x: DataCoding) -> Self {
+        match x {
             #[cfg(not(feature = "v5"))]
-            Self::SmscDefault => 0b00000000,
+            DataCoding::SmscDefault => 0b00000000,
             #[cfg(feature = "v5")]
-            Self::McSpecific => 0b00000000,
-            Self::Ascii => 0b00000001,
-            Self::Latin1 => 0b00000011,
-            Self::ShiftJis => 0b00000101,
-            Self::Cyrillic => 0b00000110,
-            Self::Hebrew => 0b00000111,
-            Self::Ucs2 => 0b00001000,
-            Self::Iso2022Jp => 0b00001010,
-            Self::EucJp => 0b00001101,
-            Self::EucKr => 0b00001110,
-            Self::Reserved(x) => *x,
-        };
+            DataCoding::McSpecific => 0b00000000,
+            DataCoding::Ascii => 0b00000001,
+            DataCoding::Latin1 => 0b00000011,
+            DataCoding::ShiftJis => 0b00000101,
+            DataCoding::Cyrillic => 0b00000110,
+            DataCoding::Hebrew => 0b00000111,
+            DataCoding::Ucs2 => 0b00001000,
+            DataCoding::Iso2022Jp => 0b00001010,
+            DataCoding::EucJp => 0b00001101,
+            DataCoding::EucKr => 0b00001110,
+            DataCoding::Reserved(x) => x,
+        }
+    }
+}
+
+impl bincode::Decode for DataCoding {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
+        let u = u8::decode(decoder)?;
+
+        Ok(u.into())
+    }
+}
+
+impl bincode::Encode for DataCoding {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
+        let u: u8 = (*self).into();
 
         u.encode(encoder)
     }
 }
 
 mod gsm;
+mod scheme;
+mod text;
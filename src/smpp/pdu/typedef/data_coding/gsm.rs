@@ -1,10 +1,28 @@
 // Copyright (c) 2022 GreenYun Organization
 // SPDX-License-Identifier: MIT
 
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 use bitstream_io::{BitRead, BitReader, BitWrite, BitWriter, LittleEndian};
 
+pub use national::NationalLanguage;
+
+use crate::smpp::pdu::typedef::{InformationElement, UserDataHeader};
+
+/// The escape to the extension table. Its place in the basic table holds a
+/// non-breaking space, which is what a receiver displays for a lone escape.
+const ESC: u8 = 0x1B;
+
+/// Carriage return, used to pad a message whose last octet would otherwise
+/// hold seven fill bits.
+const CR: u8 = 0x0D;
+
+/// Substituted for characters that cannot be represented.
+const REPLACEMENT: u8 = 0x3F;
+
+/// Marks the positions of a table that hold no character.
+const UNDEFINED: u32 = 0xFFFF;
+
 const REV_LOOKUP: [u32; 128] = [
     0x40, 0xA3, 0x24, 0xA5, 0xE8, 0xE9, 0xF9, 0xEC, 0xF2, 0xC7, 0x0A, 0xD8, 0xF8, 0x0D, 0xC5, 0xE5, 0x0394, 0x005F,
     0x03A6, 0x0393, 0x039B, 0x03A9, 0x03A0, 0x03A8, 0x03A3, 0x0398, 0x039E, 0x00A0, 0xC6, 0xE6, 0xDF, 0xC9, 0x20, 0x21,
@@ -15,54 +33,337 @@ const REV_LOOKUP: [u32; 128] = [
     0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0xE4, 0xF6, 0xF1, 0xFC, 0xE0,
 ];
 
+/// The default extension table, as pairs of the code following an escape and
+/// the character it stands for.
+const EXT_LOOKUP: [(u8, char); 10] = [
+    (0x0A, '\u{000C}'),
+    (0x14, '^'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2F, '\\'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x65, '€'),
+];
+
 lazy_static::lazy_static! {
-    static ref FORWARD_LOOKUP: HashMap<char, u8> = {
-        let mut forward_lookup: HashMap<char, u8> = HashMap::new();
-        REV_LOOKUP.into_iter().enumerate().for_each(|(i, u)| {
-            #[allow(clippy::cast_possible_truncation)]
-            forward_lookup.insert(char::from_u32(u).unwrap_or_default(), i as u8);
-        });
-
-        forward_lookup
-    };
+    static ref FORWARD_LOOKUP: HashMap<Option<NationalLanguage>, HashMap<char, u8>> = tables()
+        .map(|lang| {
+            let mut forward_lookup: HashMap<char, u8> = HashMap::new();
+            locking_shift_table(lang)
+                .iter()
+                .enumerate()
+                .filter(|(i, u)| *i != ESC as usize && **u != UNDEFINED)
+                .for_each(|(i, u)| {
+                    #[allow(clippy::cast_possible_truncation)]
+                    forward_lookup.entry(char::from_u32(*u).unwrap_or_default()).or_insert(i as u8);
+                });
+
+            (lang, forward_lookup)
+        })
+        .collect();
+
+    static ref EXT_FORWARD_LOOKUP: HashMap<Option<NationalLanguage>, HashMap<char, u8>> = tables()
+        .map(|lang| {
+            let mut forward_lookup: HashMap<char, u8> = HashMap::new();
+            single_shift_table(lang).iter().for_each(|(u, c)| {
+                forward_lookup.entry(*c).or_insert(*u);
+            });
+
+            (lang, forward_lookup)
+        })
+        .collect();
+}
+
+/// The locking shift and single shift tables a message is encoded with.
+/// `None` stands for the GSM 03.38 default alphabet and its extension table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ShiftTables {
+    pub locking: Option<NationalLanguage>,
+    pub single: Option<NationalLanguage>,
+}
+
+impl ShiftTables {
+    /// Reads the tables from the National Language Single Shift and Locking
+    /// Shift information elements among `elements`. Other information
+    /// elements are skipped.
+    #[must_use]
+    pub fn from_information_elements(elements: &[InformationElement]) -> Self {
+        let mut tables = Self::default();
+        for element in elements {
+            match *element {
+                InformationElement::NationalSingleShift(lang) => tables.single = Some(lang),
+                InformationElement::NationalLockingShift(lang) => tables.locking = Some(lang),
+                _ => {}
+            }
+        }
+
+        tables
+    }
+
+    /// Returns the information elements announcing the national tables in
+    /// use, to be included in the UDH. It is empty for the default alphabet.
+    #[must_use]
+    pub fn information_elements(&self) -> Vec<InformationElement> {
+        let single = self.single.map(InformationElement::NationalSingleShift);
+        let locking = self.locking.map(InformationElement::NationalLockingShift);
+
+        single.into_iter().chain(locking).collect()
+    }
+
+    fn septets(self, s: &str) -> Result<Vec<u8>, GsmEncodeError> {
+        let mut septets = Vec::with_capacity(s.len());
+        for (index, c) in s.chars().enumerate() {
+            if !push_septets(&mut septets, c, self) {
+                return Err(GsmEncodeError { index, char: c });
+            }
+        }
+
+        Ok(septets)
+    }
 }
 
+/// Decodes packed GSM 03.38 default alphabet septets, including characters of
+/// the extension table.
+///
+/// An escape followed by a code the extension table does not define is
+/// displayed as the character of that code in the basic table, as the
+/// specification requires.
 #[must_use]
 pub fn decode(v: &[u8]) -> String {
+    decode_with(v, ShiftTables::default())
+}
+
+/// Decodes packed septets like [`decode`], using the national language tables
+/// given, e.g. by the UDH of the message.
+#[must_use]
+pub fn decode_with(v: &[u8], tables: ShiftTables) -> String {
+    decode_with_fill(v, tables, 0)
+}
+
+/// Decodes packed septets that start after `fill_bits` fill bits, as they do
+/// when following a UDH.
+pub(in crate::smpp::pdu::typedef) fn decode_with_fill(v: &[u8], tables: ShiftTables, fill_bits: u32) -> String {
     let mut dec = BitReader::endian(v, LittleEndian);
     let mut parsed = vec![];
-    loop {
-        let b = 7;
-        let c = dec.read::<u8>(b);
-        match c {
-            Ok(c) => {
-                parsed.push(c);
-            }
-            Err(_) => {
-                break;
+    if dec.skip(fill_bits).is_ok() {
+        while let Ok(c) = dec.read::<u8>(7) {
+            parsed.push(c);
+        }
+    }
+
+    // Seven fill bits at the end hold a CR, which is not part of the message.
+    let bits = (v.len() * 8).saturating_sub(fill_bits as usize);
+    if bits % 7 == 0 && parsed.last() == Some(&CR) {
+        parsed.pop();
+    }
+
+    let locking = locking_shift_table(tables.locking);
+    let single = single_shift_table(tables.single);
+    let basic = |u: u8| {
+        Some(locking[u as usize])
+            .filter(|u| *u != UNDEFINED)
+            .and_then(char::from_u32)
+    };
+
+    let mut s = String::with_capacity(parsed.len());
+    let mut septets = parsed.into_iter();
+    while let Some(u) = septets.next() {
+        let c = match u {
+            ESC => match septets.next() {
+                Some(u) => single
+                    .iter()
+                    .find_map(|(ext, c)| (*ext == u).then_some(*c))
+                    .or_else(|| basic(u)),
+                None => None,
+            },
+            u => basic(u),
+        };
+        s.extend(c);
+    }
+
+    s
+}
+
+/// Encodes a string into packed GSM 03.38 default alphabet septets, using the
+/// extension table for characters such as `{`, `[` and `€`.
+///
+/// # Errors
+///
+/// This function will return an error for the first character that neither
+/// the basic nor the extension table can represent.
+pub fn encode(s: &str) -> Result<Vec<u8>, GsmEncodeError> {
+    encode_with(s, ShiftTables::default())
+}
+
+/// Encodes a string like [`encode`], using the national language tables
+/// given. The receiver learns of them from the
+/// [information elements](ShiftTables::information_elements) in the UDH.
+///
+/// # Errors
+///
+/// This function will return an error for the first character that neither
+/// table can represent.
+pub fn encode_with(s: &str, tables: ShiftTables) -> Result<Vec<u8>, GsmEncodeError> {
+    encode_with_fill(s, tables, 0)
+}
+
+/// Encodes a string into packed septets preceded by `fill_bits` fill bits, as
+/// they are when following a UDH.
+pub(in crate::smpp::pdu::typedef) fn encode_with_fill(
+    s: &str,
+    tables: ShiftTables,
+    fill_bits: u32,
+) -> Result<Vec<u8>, GsmEncodeError> {
+    tables.septets(s).map(|septets| pack(&septets, fill_bits))
+}
+
+/// Encodes a string with the combination of the default alphabet and the
+/// tables of `languages` that takes the fewest septets, counting the UDH
+/// needed to announce the tables and the fill bits following it.
+///
+/// Returns the whole *short_message* together with its UDH. For the default
+/// alphabet, the UDH is empty and the *short_message* is the packed text
+/// alone; otherwise the *short_message* starts with the UDH, and the
+/// [`UDHI`](crate::smpp::pdu::typedef::EsmClassGsm::UDHI) has to be set.
+///
+/// # Errors
+///
+/// This function will return an error if no combination can represent the
+/// string. The error names the first character that the combination getting
+/// furthest fails on.
+pub fn encode_national(s: &str, languages: &[NationalLanguage]) -> Result<(Vec<u8>, UserDataHeader), GsmEncodeError> {
+    let lockings = languages.iter().filter(|lang| lang.has_locking_shift());
+    let lockings = std::iter::once(None).chain(lockings.copied().map(Some));
+    let singles = std::iter::once(None).chain(languages.iter().copied().map(Some));
+
+    let mut best: Option<(usize, UserDataHeader, Vec<u8>)> = None;
+    let mut furthest: Option<GsmEncodeError> = None;
+
+    for locking in lockings {
+        for single in singles.clone() {
+            let tables = ShiftTables { locking, single };
+            match tables.septets(s) {
+                Ok(septets) => {
+                    let udh = UserDataHeader::from(tables);
+                    // The text following a UDH starts on a septet boundary.
+                    let udh_septets = if udh.is_empty() {
+                        0
+                    } else {
+                        (udh.encoded_len() * 8).div_ceil(7)
+                    };
+                    let len = udh_septets + septets.len();
+                    if best.as_ref().map_or(true, |(min, ..)| len < *min) {
+                        best = Some((len, udh, septets));
+                    }
+                }
+                Err(e) => {
+                    if furthest.as_ref().map_or(true, |f| e.index > f.index) {
+                        furthest = Some(e);
+                    }
+                }
             }
         }
     }
 
-    let s = parsed
-        .into_iter()
-        .filter_map(|u| char::from_u32(REV_LOOKUP[u as usize]))
-        .collect::<String>();
+    match (best, furthest) {
+        (Some((_, udh, septets)), _) if udh.is_empty() => Ok((pack(&septets, 0), udh)),
+        (Some((_, udh, septets)), _) => {
+            let mut v = udh.to_bytes().expect("a UDH of two information elements at most");
+            v.extend(pack(&septets, udh.fill_bits()));
 
-    s.replace('\u{00A0}', "1)")
+            Ok((v, udh))
+        }
+        (None, Some(e)) => Err(e),
+        (None, None) => unreachable!("the default tables are always tried"),
+    }
 }
 
+/// Encodes a string like [`encode`], substituting `?` for every character
+/// that cannot be represented. The substituted characters are returned
+/// together with their index in the string.
 #[must_use]
-pub fn encode(s: &str) -> Vec<u8> {
+pub fn encode_lossy(s: &str) -> (Vec<u8>, Vec<(usize, char)>) {
+    let mut septets = Vec::with_capacity(s.len());
+    let mut lost = vec![];
+    for (index, c) in s.chars().enumerate() {
+        if !push_septets(&mut septets, c, ShiftTables::default()) {
+            septets.push(REPLACEMENT);
+            lost.push((index, c));
+        }
+    }
+
+    (pack(&septets, 0), lost)
+}
+
+/// The default alphabet followed by every national language.
+fn tables() -> impl Iterator<Item = Option<NationalLanguage>> {
+    std::iter::once(None).chain(NationalLanguage::ALL.into_iter().map(Some))
+}
+
+fn locking_shift_table(lang: Option<NationalLanguage>) -> &'static [u32; 128] {
+    lang.and_then(NationalLanguage::locking_shift_table)
+        .unwrap_or(&REV_LOOKUP)
+}
+
+fn single_shift_table(lang: Option<NationalLanguage>) -> &'static [(u8, char)] {
+    lang.map_or(&EXT_LOOKUP, NationalLanguage::single_shift_table)
+}
+
+/// Returns the number of septets representing `c`, i.e. 2 for a character
+/// of the single shift table, or `None` if it cannot be represented.
+pub(super) fn septet_len(c: char, tables: ShiftTables) -> Option<usize> {
+    let locking = &FORWARD_LOOKUP[&tables.locking.filter(|lang| lang.has_locking_shift())];
+    let single = &EXT_FORWARD_LOOKUP[&tables.single];
+
+    if locking.contains_key(&c) {
+        Some(1)
+    } else if single.contains_key(&c) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Pushes the septets representing `c`, or returns `false` if there are none.
+fn push_septets(septets: &mut Vec<u8>, c: char, tables: ShiftTables) -> bool {
+    let locking = &FORWARD_LOOKUP[&tables.locking.filter(|lang| lang.has_locking_shift())];
+    let single = &EXT_FORWARD_LOOKUP[&tables.single];
+
+    if let Some(u) = locking.get(&c) {
+        septets.push(*u);
+    } else if let Some(u) = single.get(&c) {
+        septets.extend([ESC, *u]);
+    } else {
+        return false;
+    }
+
+    true
+}
+
+fn pack(septets: &[u8], fill_bits: u32) -> Vec<u8> {
     let buf = vec![];
     let mut enc = BitWriter::endian(buf, LittleEndian);
 
-    s.replace("1)", "\u{00A0}")
-        .chars()
-        .map(|c| FORWARD_LOOKUP.get(&c).copied().unwrap_or(0x20))
-        .for_each(|u| {
-            enc.write(7, u).unwrap_or_default();
-        });
+    enc.write(fill_bits, 0u8).unwrap_or_default();
+    septets.iter().for_each(|u| {
+        enc.write(7, *u).unwrap_or_default();
+    });
+
+    let bits = fill_bits as usize + septets.len() * 7;
+
+    // A CR meant as the last character would be taken for padding if it ended
+    // on an octet boundary, so another one is added.
+    if bits % 8 == 0 && septets.last() == Some(&CR) {
+        enc.write(7, CR).unwrap_or_default();
+    }
+
+    // Seven fill bits would be read as another '@', so they are a CR instead.
+    if bits % 8 == 1 {
+        enc.write(7, CR).unwrap_or_default();
+    }
 
     while !enc.byte_aligned() {
         enc.write_bit(false).unwrap_or_default();
@@ -70,3 +371,25 @@ pub fn encode(s: &str) -> Vec<u8> {
 
     enc.into_writer()
 }
+
+/// A character that the GSM 03.38 tables in use cannot represent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GsmEncodeError {
+    /// The index of the character in the string, counted in characters.
+    pub index: usize,
+    pub char: char,
+}
+
+impl fmt::Display for GsmEncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "character {:?} at index {} cannot be represented in GSM 03.38 septets",
+            self.char, self.index
+        )
+    }
+}
+
+impl std::error::Error for GsmEncodeError {}
+
+mod national;
@@ -0,0 +1,1163 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+//! The national language tables of 3GPP TS 23.038, Annex A.
+//!
+//! Positions a table leaves undefined hold [`UNDEFINED`]. The escape to the
+//! single shift table holds a non-breaking space, as in the default alphabet.
+
+use super::UNDEFINED;
+
+/// A national language with its own single shift table and, for most, a
+/// locking shift table, identified in the National Language Single Shift
+/// (0x24) and Locking Shift (0x25) information elements of the UDH.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, num_derive::FromPrimitive, num_derive::ToPrimitive)]
+#[repr(u8)]
+pub enum NationalLanguage {
+    Turkish    = 0x01,
+    /// Spanish has a single shift table only.
+    Spanish    = 0x02,
+    Portuguese = 0x03,
+    Bengali    = 0x04,
+    Gujarati   = 0x05,
+    Hindi      = 0x06,
+    Kannada    = 0x07,
+    Malayalam  = 0x08,
+    Oriya      = 0x09,
+    Punjabi    = 0x0A,
+    Tamil      = 0x0B,
+    Telugu     = 0x0C,
+    Urdu       = 0x0D,
+}
+
+impl NationalLanguage {
+    /// All national languages, in the order of their identifiers.
+    pub const ALL: [Self; 13] = [
+        Self::Turkish,
+        Self::Spanish,
+        Self::Portuguese,
+        Self::Bengali,
+        Self::Gujarati,
+        Self::Hindi,
+        Self::Kannada,
+        Self::Malayalam,
+        Self::Oriya,
+        Self::Punjabi,
+        Self::Tamil,
+        Self::Telugu,
+        Self::Urdu,
+    ];
+
+    /// Returns `true` if the language has a locking shift table.
+    #[must_use]
+    pub const fn has_locking_shift(self) -> bool {
+        !matches!(self, Self::Spanish)
+    }
+
+    pub(super) const fn locking_shift_table(self) -> Option<&'static [u32; 128]> {
+        Some(match self {
+            Self::Turkish => &TURKISH_LOCKING_SHIFT,
+            Self::Spanish => return None,
+            Self::Portuguese => &PORTUGUESE_LOCKING_SHIFT,
+            Self::Bengali => &BENGALI_LOCKING_SHIFT,
+            Self::Gujarati => &GUJARATI_LOCKING_SHIFT,
+            Self::Hindi => &HINDI_LOCKING_SHIFT,
+            Self::Kannada => &KANNADA_LOCKING_SHIFT,
+            Self::Malayalam => &MALAYALAM_LOCKING_SHIFT,
+            Self::Oriya => &ORIYA_LOCKING_SHIFT,
+            Self::Punjabi => &PUNJABI_LOCKING_SHIFT,
+            Self::Tamil => &TAMIL_LOCKING_SHIFT,
+            Self::Telugu => &TELUGU_LOCKING_SHIFT,
+            Self::Urdu => &URDU_LOCKING_SHIFT,
+        })
+    }
+
+    pub(super) const fn single_shift_table(self) -> &'static [(u8, char)] {
+        match self {
+            Self::Turkish => &TURKISH_SINGLE_SHIFT,
+            Self::Spanish => &SPANISH_SINGLE_SHIFT,
+            Self::Portuguese => &PORTUGUESE_SINGLE_SHIFT,
+            Self::Bengali => &BENGALI_SINGLE_SHIFT,
+            Self::Gujarati => &GUJARATI_SINGLE_SHIFT,
+            Self::Hindi => &HINDI_SINGLE_SHIFT,
+            Self::Kannada => &KANNADA_SINGLE_SHIFT,
+            Self::Malayalam => &MALAYALAM_SINGLE_SHIFT,
+            Self::Oriya => &ORIYA_SINGLE_SHIFT,
+            Self::Punjabi => &PUNJABI_SINGLE_SHIFT,
+            Self::Tamil => &TAMIL_SINGLE_SHIFT,
+            Self::Telugu => &TELUGU_SINGLE_SHIFT,
+            Self::Urdu => &URDU_SINGLE_SHIFT,
+        }
+    }
+}
+
+/// A.3.1 Turkish National Language Locking Shift Table
+const TURKISH_LOCKING_SHIFT: [u32; 128] = [
+    0x40, 0xA3, 0x24, 0xA5, 0x20AC, 0xE9, 0xF9, 0x0131, 0xF2, 0xC7, 0x0A, 0x011E, 0x011F, 0x0D, 0xC5, 0xE5, 0x0394,
+    0x5F, 0x03A6, 0x0393, 0x039B, 0x03A9, 0x03A0, 0x03A8, 0x03A3, 0x0398, 0x039E, 0x00A0, 0x015E, 0x015F, 0xDF, 0xC9,
+    0x20, 0x21, 0x22, 0x23, 0xA4, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2C, 0x2D, 0x2E, 0x2F, 0x30, 0x31, 0x32,
+    0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F, 0x0130, 0x41, 0x42, 0x43, 0x44, 0x45,
+    0x46, 0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58,
+    0x59, 0x5A, 0xC4, 0xD6, 0xD1, 0xDC, 0xA7, 0xE7, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B,
+    0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0xE4, 0xF6, 0xF1, 0xFC,
+    0xE0,
+];
+
+/// A.3.3 Portuguese National Language Locking Shift Table
+const PORTUGUESE_LOCKING_SHIFT: [u32; 128] = [
+    0x40, 0xA3, 0x24, 0xA5, 0xEA, 0xE9, 0xFA, 0xED, 0xF3, 0xE7, 0x0A, 0xD4, 0xF4, 0x0D, 0xC1, 0xE1, 0x0394, 0x5F, 0xAA,
+    0xC7, 0xC0, 0x221E, 0x5E, 0x5C, 0x20AC, 0xD3, 0x7C, 0x00A0, 0xC2, 0xE2, 0xCA, 0xC9, 0x20, 0x21, 0x22, 0x23, 0xBA,
+    0x25, 0x26, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2C, 0x2D, 0x2E, 0x2F, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+    0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D, 0x3E, 0x3F, 0xCD, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4A,
+    0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0xC3, 0xD5, 0xDA,
+    0xDC, 0xA7, 0x7E, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70,
+    0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0xE3, 0xF5, 0x60, 0xFC, 0xE0,
+];
+
+/// A.3.4 Bengali National Language Locking Shift Table
+const BENGALI_LOCKING_SHIFT: [u32; 128] = [
+    0x0981, 0x0982, 0x0983, 0x0985, 0x0986, 0x0987, 0x0988, 0x0989, 0x098A, 0x098B, 0x0A, 0x098C, UNDEFINED, 0x0D,
+    UNDEFINED, 0x098F, 0x0990, UNDEFINED, UNDEFINED, 0x0993, 0x0994, 0x0995, 0x0996, 0x0997, 0x0998, 0x0999, 0x099A,
+    0x00A0, 0x099B, 0x099C, 0x099D, 0x099E, 0x20, 0x21, 0x099F, 0x09A0, 0x09A1, 0x09A2, 0x09A3, 0x09A4, 0x29, 0x28,
+    0x09A5, 0x09A6, 0x2C, 0x09A7, 0x2E, 0x09A8, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B,
+    UNDEFINED, 0x09AA, 0x09AB, 0x3F, 0x09AC, 0x09AD, 0x09AE, 0x09AF, 0x09B0, UNDEFINED, 0x09B2, UNDEFINED, UNDEFINED,
+    UNDEFINED, 0x09B6, 0x09B7, 0x09B8, 0x09B9, 0x09BC, 0x09BD, 0x09BE, 0x09BF, 0x09C0, 0x09C1, 0x09C2, 0x09C3, 0x09C4,
+    UNDEFINED, UNDEFINED, 0x09C7, 0x09C8, UNDEFINED, UNDEFINED, 0x09CB, 0x09CC, 0x09CD, 0x09CE, 0x61, 0x62, 0x63, 0x64,
+    0x65, 0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
+    0x78, 0x79, 0x7A, 0x09D7, 0x09DC, 0x09DD, 0x09F0, 0x09F1,
+];
+
+/// A.3.5 Gujarati National Language Locking Shift Table
+const GUJARATI_LOCKING_SHIFT: [u32; 128] = [
+    0x0A81, 0x0A82, 0x0A83, 0x0A85, 0x0A86, 0x0A87, 0x0A88, 0x0A89, 0x0A8A, 0x0A8B, 0x0A, 0x0A8C, 0x0A8D, 0x0D,
+    UNDEFINED, 0x0A8F, 0x0A90, 0x0A91, UNDEFINED, 0x0A93, 0x0A94, 0x0A95, 0x0A96, 0x0A97, 0x0A98, 0x0A99, 0x0A9A,
+    0x00A0, 0x0A9B, 0x0A9C, 0x0A9D, 0x0A9E, 0x20, 0x21, 0x0A9F, 0x0AA0, 0x0AA1, 0x0AA2, 0x0AA3, 0x0AA4, 0x29, 0x28,
+    0x0AA5, 0x0AA6, 0x2C, 0x0AA7, 0x2E, 0x0AA8, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B,
+    UNDEFINED, 0x0AAA, 0x0AAB, 0x3F, 0x0AAC, 0x0AAD, 0x0AAE, 0x0AAF, 0x0AB0, UNDEFINED, 0x0AB2, 0x0AB3, UNDEFINED,
+    0x0AB5, 0x0AB6, 0x0AB7, 0x0AB8, 0x0AB9, 0x0ABC, 0x0ABD, 0x0ABE, 0x0ABF, 0x0AC0, 0x0AC1, 0x0AC2, 0x0AC3, 0x0AC4,
+    0x0AC5, UNDEFINED, 0x0AC7, 0x0AC8, 0x0AC9, UNDEFINED, 0x0ACB, 0x0ACC, 0x0ACD, 0x0AD0, 0x61, 0x62, 0x63, 0x64, 0x65,
+    0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78,
+    0x79, 0x7A, 0x0AE0, 0x0AE1, 0x0AE2, 0x0AE3, 0x0AF1,
+];
+
+/// A.3.6 Hindi National Language Locking Shift Table
+const HINDI_LOCKING_SHIFT: [u32; 128] = [
+    0x0901, 0x0902, 0x0903, 0x0905, 0x0906, 0x0907, 0x0908, 0x0909, 0x090A, 0x090B, 0x0A, 0x090C, 0x090D, 0x0D, 0x090E,
+    0x090F, 0x0910, 0x0911, 0x0912, 0x0913, 0x0914, 0x0915, 0x0916, 0x0917, 0x0918, 0x0919, 0x091A, 0x00A0, 0x091B,
+    0x091C, 0x091D, 0x091E, 0x20, 0x21, 0x091F, 0x0920, 0x0921, 0x0922, 0x0923, 0x0924, 0x29, 0x28, 0x0925, 0x0926,
+    0x2C, 0x0927, 0x2E, 0x0928, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B, 0x0929, 0x092A,
+    0x092B, 0x3F, 0x092C, 0x092D, 0x092E, 0x092F, 0x0930, 0x0931, 0x0932, 0x0933, 0x0934, 0x0935, 0x0936, 0x0937,
+    0x0938, 0x0939, 0x093C, 0x093D, 0x093E, 0x093F, 0x0940, 0x0941, 0x0942, 0x0943, 0x0944, 0x0945, 0x0946, 0x0947,
+    0x0948, 0x0949, 0x094A, 0x094B, 0x094C, 0x094D, 0x0950, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6A,
+    0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0x0972, 0x097B,
+    0x097C, 0x097E, 0x097F,
+];
+
+/// A.3.7 Kannada National Language Locking Shift Table
+const KANNADA_LOCKING_SHIFT: [u32; 128] = [
+    UNDEFINED, 0x0C82, 0x0C83, 0x0C85, 0x0C86, 0x0C87, 0x0C88, 0x0C89, 0x0C8A, 0x0C8B, 0x0A, 0x0C8C, UNDEFINED, 0x0D,
+    0x0C8E, 0x0C8F, 0x0C90, UNDEFINED, 0x0C92, 0x0C93, 0x0C94, 0x0C95, 0x0C96, 0x0C97, 0x0C98, 0x0C99, 0x0C9A, 0x00A0,
+    0x0C9B, 0x0C9C, 0x0C9D, 0x0C9E, 0x20, 0x21, 0x0C9F, 0x0CA0, 0x0CA1, 0x0CA2, 0x0CA3, 0x0CA4, 0x29, 0x28, 0x0CA5,
+    0x0CA6, 0x2C, 0x0CA7, 0x2E, 0x0CA8, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B,
+    UNDEFINED, 0x0CAA, 0x0CAB, 0x3F, 0x0CAC, 0x0CAD, 0x0CAE, 0x0CAF, 0x0CB0, 0x0CB1, 0x0CB2, 0x0CB3, UNDEFINED, 0x0CB5,
+    0x0CB6, 0x0CB7, 0x0CB8, 0x0CB9, 0x0CBC, 0x0CBD, 0x0CBE, 0x0CBF, 0x0CC0, 0x0CC1, 0x0CC2, 0x0CC3, 0x0CC4, UNDEFINED,
+    0x0CC6, 0x0CC7, 0x0CC8, UNDEFINED, 0x0CCA, 0x0CCB, 0x0CCC, 0x0CCD, 0x0CD5, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66,
+    0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79,
+    0x7A, 0x0CD6, 0x0CE0, 0x0CE1, 0x0CE2, 0x0CE3,
+];
+
+/// A.3.8 Malayalam National Language Locking Shift Table
+const MALAYALAM_LOCKING_SHIFT: [u32; 128] = [
+    UNDEFINED, 0x0D02, 0x0D03, 0x0D05, 0x0D06, 0x0D07, 0x0D08, 0x0D09, 0x0D0A, 0x0D0B, 0x0A, 0x0D0C, UNDEFINED, 0x0D,
+    0x0D0E, 0x0D0F, 0x0D10, UNDEFINED, 0x0D12, 0x0D13, 0x0D14, 0x0D15, 0x0D16, 0x0D17, 0x0D18, 0x0D19, 0x0D1A, 0x00A0,
+    0x0D1B, 0x0D1C, 0x0D1D, 0x0D1E, 0x20, 0x21, 0x0D1F, 0x0D20, 0x0D21, 0x0D22, 0x0D23, 0x0D24, 0x29, 0x28, 0x0D25,
+    0x0D26, 0x2C, 0x0D27, 0x2E, 0x0D28, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B,
+    UNDEFINED, 0x0D2A, 0x0D2B, 0x3F, 0x0D2C, 0x0D2D, 0x0D2E, 0x0D2F, 0x0D30, 0x0D31, 0x0D32, 0x0D33, 0x0D34, 0x0D35,
+    0x0D36, 0x0D37, 0x0D38, 0x0D39, UNDEFINED, 0x0D3D, 0x0D3E, 0x0D3F, 0x0D40, 0x0D41, 0x0D42, 0x0D43, 0x0D44,
+    UNDEFINED, 0x0D46, 0x0D47, 0x0D48, UNDEFINED, 0x0D4A, 0x0D4B, 0x0D4C, 0x0D4D, 0x0D57, 0x61, 0x62, 0x63, 0x64, 0x65,
+    0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78,
+    0x79, 0x7A, 0x0D60, 0x0D61, 0x0D62, 0x0D63, 0x0D79,
+];
+
+/// A.3.9 Oriya National Language Locking Shift Table
+const ORIYA_LOCKING_SHIFT: [u32; 128] = [
+    0x0B01, 0x0B02, 0x0B03, 0x0B05, 0x0B06, 0x0B07, 0x0B08, 0x0B09, 0x0B0A, 0x0B0B, 0x0A, 0x0B0C, UNDEFINED, 0x0D,
+    UNDEFINED, 0x0B0F, 0x0B10, UNDEFINED, UNDEFINED, 0x0B13, 0x0B14, 0x0B15, 0x0B16, 0x0B17, 0x0B18, 0x0B19, 0x0B1A,
+    0x00A0, 0x0B1B, 0x0B1C, 0x0B1D, 0x0B1E, 0x20, 0x21, 0x0B1F, 0x0B20, 0x0B21, 0x0B22, 0x0B23, 0x0B24, 0x29, 0x28,
+    0x0B25, 0x0B26, 0x2C, 0x0B27, 0x2E, 0x0B28, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B,
+    UNDEFINED, 0x0B2A, 0x0B2B, 0x3F, 0x0B2C, 0x0B2D, 0x0B2E, 0x0B2F, 0x0B30, UNDEFINED, 0x0B32, 0x0B33, UNDEFINED,
+    0x0B35, 0x0B36, 0x0B37, 0x0B38, 0x0B39, 0x0B3C, 0x0B3D, 0x0B3E, 0x0B3F, 0x0B40, 0x0B41, 0x0B42, 0x0B43, 0x0B44,
+    UNDEFINED, UNDEFINED, 0x0B47, 0x0B48, UNDEFINED, UNDEFINED, 0x0B4B, 0x0B4C, 0x0B4D, 0x0B56, 0x61, 0x62, 0x63, 0x64,
+    0x65, 0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
+    0x78, 0x79, 0x7A, 0x0B57, 0x0B60, 0x0B61, 0x0B62, 0x0B63,
+];
+
+/// A.3.10 Punjabi National Language Locking Shift Table
+const PUNJABI_LOCKING_SHIFT: [u32; 128] = [
+    0x0A01, 0x0A02, 0x0A03, 0x0A05, 0x0A06, 0x0A07, 0x0A08, 0x0A09, 0x0A0A, UNDEFINED, 0x0A, UNDEFINED, UNDEFINED,
+    0x0D, UNDEFINED, 0x0A0F, 0x0A10, UNDEFINED, UNDEFINED, 0x0A13, 0x0A14, 0x0A15, 0x0A16, 0x0A17, 0x0A18, 0x0A19,
+    0x0A1A, 0x00A0, 0x0A1B, 0x0A1C, 0x0A1D, 0x0A1E, 0x20, 0x21, 0x0A1F, 0x0A20, 0x0A21, 0x0A22, 0x0A23, 0x0A24, 0x29,
+    0x28, 0x0A25, 0x0A26, 0x2C, 0x0A27, 0x2E, 0x0A28, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A,
+    0x3B, UNDEFINED, 0x0A2A, 0x0A2B, 0x3F, 0x0A2C, 0x0A2D, 0x0A2E, 0x0A2F, 0x0A30, UNDEFINED, 0x0A32, 0x0A33,
+    UNDEFINED, 0x0A35, 0x0A36, UNDEFINED, 0x0A38, 0x0A39, 0x0A3C, UNDEFINED, 0x0A3E, 0x0A3F, 0x0A40, 0x0A41, 0x0A42,
+    UNDEFINED, UNDEFINED, UNDEFINED, UNDEFINED, 0x0A47, 0x0A48, UNDEFINED, UNDEFINED, 0x0A4B, 0x0A4C, 0x0A4D, 0x0A51,
+    0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73,
+    0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0x0A70, 0x0A71, 0x0A72, 0x0A73, 0x0A74,
+];
+
+/// A.3.11 Tamil National Language Locking Shift Table
+const TAMIL_LOCKING_SHIFT: [u32; 128] = [
+    UNDEFINED, 0x0B82, 0x0B83, 0x0B85, 0x0B86, 0x0B87, 0x0B88, 0x0B89, 0x0B8A, UNDEFINED, 0x0A, UNDEFINED, UNDEFINED,
+    0x0D, 0x0B8E, 0x0B8F, 0x0B90, UNDEFINED, 0x0B92, 0x0B93, 0x0B94, 0x0B95, UNDEFINED, UNDEFINED, UNDEFINED, 0x0B99,
+    0x0B9A, 0x00A0, UNDEFINED, 0x0B9C, UNDEFINED, 0x0B9E, 0x20, 0x21, 0x0B9F, UNDEFINED, UNDEFINED, UNDEFINED, 0x0BA3,
+    0x0BA4, 0x29, 0x28, UNDEFINED, UNDEFINED, 0x2C, UNDEFINED, 0x2E, 0x0BA8, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36,
+    0x37, 0x38, 0x39, 0x3A, 0x3B, 0x0BA9, 0x0BAA, UNDEFINED, 0x3F, UNDEFINED, UNDEFINED, 0x0BAE, 0x0BAF, 0x0BB0,
+    0x0BB1, 0x0BB2, 0x0BB3, 0x0BB4, 0x0BB5, 0x0BB6, 0x0BB7, 0x0BB8, 0x0BB9, UNDEFINED, UNDEFINED, 0x0BBE, 0x0BBF,
+    0x0BC0, 0x0BC1, 0x0BC2, UNDEFINED, UNDEFINED, UNDEFINED, 0x0BC6, 0x0BC7, 0x0BC8, UNDEFINED, 0x0BCA, 0x0BCB, 0x0BCC,
+    0x0BCD, 0x0BD0, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70,
+    0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0x0BD7, 0x0BF0, 0x0BF1, 0x0BF2, 0x0BF9,
+];
+
+/// A.3.12 Telugu National Language Locking Shift Table
+const TELUGU_LOCKING_SHIFT: [u32; 128] = [
+    0x0C01, 0x0C02, 0x0C03, 0x0C05, 0x0C06, 0x0C07, 0x0C08, 0x0C09, 0x0C0A, 0x0C0B, 0x0A, 0x0C0C, UNDEFINED, 0x0D,
+    0x0C0E, 0x0C0F, 0x0C10, UNDEFINED, 0x0C12, 0x0C13, 0x0C14, 0x0C15, 0x0C16, 0x0C17, 0x0C18, 0x0C19, 0x0C1A, 0x00A0,
+    0x0C1B, 0x0C1C, 0x0C1D, 0x0C1E, 0x20, 0x21, 0x0C1F, 0x0C20, 0x0C21, 0x0C22, 0x0C23, 0x0C24, 0x29, 0x28, 0x0C25,
+    0x0C26, 0x2C, 0x0C27, 0x2E, 0x0C28, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B,
+    UNDEFINED, 0x0C2A, 0x0C2B, 0x3F, 0x0C2C, 0x0C2D, 0x0C2E, 0x0C2F, 0x0C30, 0x0C31, 0x0C32, 0x0C33, UNDEFINED, 0x0C35,
+    0x0C36, 0x0C37, 0x0C38, 0x0C39, UNDEFINED, 0x0C3D, 0x0C3E, 0x0C3F, 0x0C40, 0x0C41, 0x0C42, 0x0C43, 0x0C44,
+    UNDEFINED, 0x0C46, 0x0C47, 0x0C48, UNDEFINED, 0x0C4A, 0x0C4B, 0x0C4C, 0x0C4D, 0x0C55, 0x61, 0x62, 0x63, 0x64, 0x65,
+    0x66, 0x67, 0x68, 0x69, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78,
+    0x79, 0x7A, 0x0C56, 0x0C60, 0x0C61, 0x0C62, 0x0C63,
+];
+
+/// A.3.13 Urdu National Language Locking Shift Table
+const URDU_LOCKING_SHIFT: [u32; 128] = [
+    0x0627, 0x0622, 0x0628, 0x067B, 0x0680, 0x067E, 0x06A6, 0x062A, 0x06C2, 0x067F, 0x0A, 0x0679, 0x067D, 0x0D, 0x067A,
+    0x067C, 0x062B, 0x062C, 0x0681, 0x0684, 0x0683, 0x0685, 0x0686, 0x0687, 0x062D, 0x062E, 0x062F, 0x00A0, 0x068C,
+    0x0688, 0x0689, 0x068A, 0x20, 0x21, 0x068F, 0x068D, 0x0630, 0x0631, 0x0691, 0x0693, 0x29, 0x28, 0x0699, 0x0632,
+    0x2C, 0x0696, 0x2E, 0x0698, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B, 0x069A, 0x0633,
+    0x0634, 0x3F, 0x0635, 0x0636, 0x0637, 0x0638, 0x0639, 0x0641, 0x0642, 0x06A9, 0x06AA, 0x06AB, 0x06AF, 0x06B3,
+    0x06B1, 0x0644, 0x0645, 0x0646, 0x06BA, 0x06BB, 0x06BC, 0x0648, 0x06C4, 0x06D5, 0x06C1, 0x06BE, 0x0621, 0x06CC,
+    0x06D0, 0x06D2, 0x064D, 0x0650, 0x064F, 0x0657, 0x0654, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6A,
+    0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0x0655, 0x0651,
+    0x0653, 0x0656, 0x0670,
+];
+
+/// A.2.1 Turkish National Language Single Shift Table
+const TURKISH_SINGLE_SHIFT: [(u8, char); 17] = [
+    (0x0A, '\u{000C}'),
+    (0x14, '^'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2F, '\\'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x47, 'Ğ'),
+    (0x49, 'İ'),
+    (0x53, 'Ş'),
+    (0x63, 'ç'),
+    (0x65, '€'),
+    (0x67, 'ğ'),
+    (0x69, 'ı'),
+    (0x73, 'ş'),
+];
+
+/// A.2.2 Spanish National Language Single Shift Table
+const SPANISH_SINGLE_SHIFT: [(u8, char); 19] = [
+    (0x09, 'ç'),
+    (0x0A, '\u{000C}'),
+    (0x14, '^'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2F, '\\'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'Á'),
+    (0x49, 'Í'),
+    (0x4F, 'Ó'),
+    (0x55, 'Ú'),
+    (0x61, 'á'),
+    (0x65, '€'),
+    (0x69, 'í'),
+    (0x6F, 'ó'),
+    (0x75, 'ú'),
+];
+
+/// A.2.3 Portuguese National Language Single Shift Table
+const PORTUGUESE_SINGLE_SHIFT: [(u8, char); 37] = [
+    (0x05, 'ê'),
+    (0x09, 'ç'),
+    (0x0A, '\u{000C}'),
+    (0x0B, 'Ô'),
+    (0x0C, 'ô'),
+    (0x0E, 'Á'),
+    (0x0F, 'á'),
+    (0x12, 'Φ'),
+    (0x13, 'Γ'),
+    (0x14, '^'),
+    (0x15, 'Ω'),
+    (0x16, 'Π'),
+    (0x17, 'Ψ'),
+    (0x18, 'Σ'),
+    (0x19, 'Θ'),
+    (0x1F, 'Ê'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2F, '\\'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'À'),
+    (0x49, 'Í'),
+    (0x4F, 'Ó'),
+    (0x55, 'Ú'),
+    (0x5B, 'Ã'),
+    (0x5C, 'Õ'),
+    (0x61, 'Â'),
+    (0x65, '€'),
+    (0x69, 'í'),
+    (0x6F, 'ó'),
+    (0x75, 'ú'),
+    (0x7B, 'ã'),
+    (0x7C, 'õ'),
+    (0x7F, 'â'),
+];
+
+/// A.2.4 Bengali National Language Single Shift Table
+const BENGALI_SINGLE_SHIFT: [(u8, char); 84] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '\u{0964}'),
+    (0x1A, '\u{0965}'),
+    (0x1C, '\u{09E6}'),
+    (0x1D, '\u{09E7}'),
+    (0x1E, '\u{09E8}'),
+    (0x1F, '\u{09E9}'),
+    (0x20, '\u{09EA}'),
+    (0x21, '\u{09EB}'),
+    (0x22, '\u{09EC}'),
+    (0x23, '\u{09ED}'),
+    (0x24, '\u{09EE}'),
+    (0x25, '\u{09EF}'),
+    (0x26, '\u{09DF}'),
+    (0x27, '\u{09E0}'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2A, '\u{09E1}'),
+    (0x2B, '\u{09E2}'),
+    (0x2C, '\u{09E3}'),
+    (0x2D, '\u{09F2}'),
+    (0x2E, '\u{09F3}'),
+    (0x2F, '\\'),
+    (0x30, '\u{09F4}'),
+    (0x31, '\u{09F5}'),
+    (0x32, '\u{09F6}'),
+    (0x33, '\u{09F7}'),
+    (0x34, '\u{09F8}'),
+    (0x35, '\u{09F9}'),
+    (0x36, '\u{09FA}'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
+
+/// A.2.5 Gujarati National Language Single Shift Table
+const GUJARATI_SINGLE_SHIFT: [(u8, char); 70] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '\u{0964}'),
+    (0x1A, '\u{0965}'),
+    (0x1C, '\u{0AE6}'),
+    (0x1D, '\u{0AE7}'),
+    (0x1E, '\u{0AE8}'),
+    (0x1F, '\u{0AE9}'),
+    (0x20, '\u{0AEA}'),
+    (0x21, '\u{0AEB}'),
+    (0x22, '\u{0AEC}'),
+    (0x23, '\u{0AED}'),
+    (0x24, '\u{0AEE}'),
+    (0x25, '\u{0AEF}'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2F, '\\'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
+
+/// A.2.6 Hindi National Language Single Shift Table
+const HINDI_SINGLE_SHIFT: [(u8, char); 88] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '\u{0964}'),
+    (0x1A, '\u{0965}'),
+    (0x1C, '\u{0966}'),
+    (0x1D, '\u{0967}'),
+    (0x1E, '\u{0968}'),
+    (0x1F, '\u{0969}'),
+    (0x20, '\u{096A}'),
+    (0x21, '\u{096B}'),
+    (0x22, '\u{096C}'),
+    (0x23, '\u{096D}'),
+    (0x24, '\u{096E}'),
+    (0x25, '\u{096F}'),
+    (0x26, '\u{0951}'),
+    (0x27, '\u{0952}'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2A, '\u{0953}'),
+    (0x2B, '\u{0954}'),
+    (0x2C, '\u{0958}'),
+    (0x2D, '\u{0959}'),
+    (0x2E, '\u{095A}'),
+    (0x2F, '\\'),
+    (0x30, '\u{095B}'),
+    (0x31, '\u{095C}'),
+    (0x32, '\u{095D}'),
+    (0x33, '\u{095E}'),
+    (0x34, '\u{095F}'),
+    (0x35, '\u{0960}'),
+    (0x36, '\u{0961}'),
+    (0x37, '\u{0962}'),
+    (0x38, '\u{0963}'),
+    (0x39, '\u{0970}'),
+    (0x3A, '\u{0971}'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
+
+/// A.2.7 Kannada National Language Single Shift Table
+const KANNADA_SINGLE_SHIFT: [(u8, char); 73] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '\u{0964}'),
+    (0x1A, '\u{0965}'),
+    (0x1C, '\u{0CE6}'),
+    (0x1D, '\u{0CE7}'),
+    (0x1E, '\u{0CE8}'),
+    (0x1F, '\u{0CE9}'),
+    (0x20, '\u{0CEA}'),
+    (0x21, '\u{0CEB}'),
+    (0x22, '\u{0CEC}'),
+    (0x23, '\u{0CED}'),
+    (0x24, '\u{0CEE}'),
+    (0x25, '\u{0CEF}'),
+    (0x26, '\u{0CDE}'),
+    (0x27, '\u{0CF1}'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2A, '\u{0CF2}'),
+    (0x2F, '\\'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
+
+/// A.2.8 Malayalam National Language Single Shift Table
+const MALAYALAM_SINGLE_SHIFT: [(u8, char); 82] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '\u{0964}'),
+    (0x1A, '\u{0965}'),
+    (0x1C, '\u{0D66}'),
+    (0x1D, '\u{0D67}'),
+    (0x1E, '\u{0D68}'),
+    (0x1F, '\u{0D69}'),
+    (0x20, '\u{0D6A}'),
+    (0x21, '\u{0D6B}'),
+    (0x22, '\u{0D6C}'),
+    (0x23, '\u{0D6D}'),
+    (0x24, '\u{0D6E}'),
+    (0x25, '\u{0D6F}'),
+    (0x26, '\u{0D70}'),
+    (0x27, '\u{0D71}'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2A, '\u{0D72}'),
+    (0x2B, '\u{0D73}'),
+    (0x2C, '\u{0D74}'),
+    (0x2D, '\u{0D75}'),
+    (0x2E, '\u{0D7A}'),
+    (0x2F, '\\'),
+    (0x30, '\u{0D7B}'),
+    (0x31, '\u{0D7C}'),
+    (0x32, '\u{0D7D}'),
+    (0x33, '\u{0D7E}'),
+    (0x34, '\u{0D7F}'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
+
+/// A.2.9 Oriya National Language Single Shift Table
+const ORIYA_SINGLE_SHIFT: [(u8, char); 75] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '\u{0964}'),
+    (0x1A, '\u{0965}'),
+    (0x1C, '\u{0B66}'),
+    (0x1D, '\u{0B67}'),
+    (0x1E, '\u{0B68}'),
+    (0x1F, '\u{0B69}'),
+    (0x20, '\u{0B6A}'),
+    (0x21, '\u{0B6B}'),
+    (0x22, '\u{0B6C}'),
+    (0x23, '\u{0B6D}'),
+    (0x24, '\u{0B6E}'),
+    (0x25, '\u{0B6F}'),
+    (0x26, '\u{0B5C}'),
+    (0x27, '\u{0B5D}'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2A, '\u{0B5F}'),
+    (0x2B, '\u{0B70}'),
+    (0x2C, '\u{0B71}'),
+    (0x2F, '\\'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
+
+/// A.2.10 Punjabi National Language Single Shift Table
+const PUNJABI_SINGLE_SHIFT: [(u8, char); 76] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '\u{0964}'),
+    (0x1A, '\u{0965}'),
+    (0x1C, '\u{0A66}'),
+    (0x1D, '\u{0A67}'),
+    (0x1E, '\u{0A68}'),
+    (0x1F, '\u{0A69}'),
+    (0x20, '\u{0A6A}'),
+    (0x21, '\u{0A6B}'),
+    (0x22, '\u{0A6C}'),
+    (0x23, '\u{0A6D}'),
+    (0x24, '\u{0A6E}'),
+    (0x25, '\u{0A6F}'),
+    (0x26, '\u{0A59}'),
+    (0x27, '\u{0A5A}'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2A, '\u{0A5B}'),
+    (0x2B, '\u{0A5C}'),
+    (0x2C, '\u{0A5E}'),
+    (0x2D, '\u{0A75}'),
+    (0x2F, '\\'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
+
+/// A.2.11 Tamil National Language Single Shift Table
+const TAMIL_SINGLE_SHIFT: [(u8, char); 77] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '\u{0964}'),
+    (0x1A, '\u{0965}'),
+    (0x1C, '\u{0BE6}'),
+    (0x1D, '\u{0BE7}'),
+    (0x1E, '\u{0BE8}'),
+    (0x1F, '\u{0BE9}'),
+    (0x20, '\u{0BEA}'),
+    (0x21, '\u{0BEB}'),
+    (0x22, '\u{0BEC}'),
+    (0x23, '\u{0BED}'),
+    (0x24, '\u{0BEE}'),
+    (0x25, '\u{0BEF}'),
+    (0x26, '\u{0BF3}'),
+    (0x27, '\u{0BF4}'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2A, '\u{0BF5}'),
+    (0x2B, '\u{0BF6}'),
+    (0x2C, '\u{0BF7}'),
+    (0x2D, '\u{0BF8}'),
+    (0x2E, '\u{0BFA}'),
+    (0x2F, '\\'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
+
+/// A.2.12 Telugu National Language Single Shift Table
+const TELUGU_SINGLE_SHIFT: [(u8, char); 78] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x1C, '\u{0C66}'),
+    (0x1D, '\u{0C67}'),
+    (0x1E, '\u{0C68}'),
+    (0x1F, '\u{0C69}'),
+    (0x20, '\u{0C6A}'),
+    (0x21, '\u{0C6B}'),
+    (0x22, '\u{0C6C}'),
+    (0x23, '\u{0C6D}'),
+    (0x24, '\u{0C6E}'),
+    (0x25, '\u{0C6F}'),
+    (0x26, '\u{0C58}'),
+    (0x27, '\u{0C59}'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2A, '\u{0C78}'),
+    (0x2B, '\u{0C79}'),
+    (0x2C, '\u{0C7A}'),
+    (0x2D, '\u{0C7B}'),
+    (0x2E, '\u{0C7C}'),
+    (0x2F, '\\'),
+    (0x30, '\u{0C7D}'),
+    (0x31, '\u{0C7E}'),
+    (0x32, '\u{0C7F}'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
+
+/// A.2.13 Urdu National Language Single Shift Table
+const URDU_SINGLE_SHIFT: [(u8, char); 90] = [
+    (0x00, '@'),
+    (0x01, '£'),
+    (0x02, '$'),
+    (0x03, '¥'),
+    (0x04, '¿'),
+    (0x05, '"'),
+    (0x06, '¤'),
+    (0x07, '%'),
+    (0x08, '&'),
+    (0x09, '\''),
+    (0x0A, '\u{000C}'),
+    (0x0B, '*'),
+    (0x0C, '+'),
+    (0x0E, '-'),
+    (0x0F, '/'),
+    (0x10, '<'),
+    (0x11, '='),
+    (0x12, '>'),
+    (0x13, '¡'),
+    (0x14, '^'),
+    (0x15, '¡'),
+    (0x16, '_'),
+    (0x17, '#'),
+    (0x18, '*'),
+    (0x19, '؀'),
+    (0x1A, '؁'),
+    (0x1C, '۰'),
+    (0x1D, '۱'),
+    (0x1E, '۲'),
+    (0x1F, '۳'),
+    (0x20, '۴'),
+    (0x21, '۵'),
+    (0x22, '۶'),
+    (0x23, '۷'),
+    (0x24, '۸'),
+    (0x25, '۹'),
+    (0x26, '،'),
+    (0x27, '؍'),
+    (0x28, '{'),
+    (0x29, '}'),
+    (0x2A, '؎'),
+    (0x2B, '؏'),
+    (0x2C, 'ؐ'),
+    (0x2D, 'ؑ'),
+    (0x2E, 'ؒ'),
+    (0x2F, '\\'),
+    (0x30, 'ؓ'),
+    (0x31, 'ؔ'),
+    (0x32, '؛'),
+    (0x33, '؟'),
+    (0x34, 'ـ'),
+    (0x35, 'ْ'),
+    (0x36, '٘'),
+    (0x37, '٫'),
+    (0x38, '٬'),
+    (0x39, 'ٲ'),
+    (0x3A, 'ٳ'),
+    (0x3B, 'ۍ'),
+    (0x3C, '['),
+    (0x3D, '~'),
+    (0x3E, ']'),
+    (0x3F, '۔'),
+    (0x40, '|'),
+    (0x41, 'A'),
+    (0x42, 'B'),
+    (0x43, 'C'),
+    (0x44, 'D'),
+    (0x45, 'E'),
+    (0x46, 'F'),
+    (0x47, 'G'),
+    (0x48, 'H'),
+    (0x49, 'I'),
+    (0x4A, 'J'),
+    (0x4B, 'K'),
+    (0x4C, 'L'),
+    (0x4D, 'M'),
+    (0x4E, 'N'),
+    (0x4F, 'O'),
+    (0x50, 'P'),
+    (0x51, 'Q'),
+    (0x52, 'R'),
+    (0x53, 'S'),
+    (0x54, 'T'),
+    (0x55, 'U'),
+    (0x56, 'V'),
+    (0x57, 'W'),
+    (0x58, 'X'),
+    (0x59, 'Y'),
+    (0x5A, 'Z'),
+    (0x65, '€'),
+];
@@ -0,0 +1,252 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use super::DataCoding;
+
+/// The *data_coding* octet interpreted as a GSM 03.38 (3GPP TS 23.038) Data
+/// Coding Scheme.
+///
+/// The values 0x00 to 0x0F are read as the SMPP character sets of
+/// [`DataCoding`], which SMPP defines in place of the GSM meaning of that
+/// range. Every other value is read by its coding group.
+///
+/// # Note:
+///
+/// Converting to [`u8`] and back yields the same octet, but not necessarily
+/// the same variant: a [`DataCodingScheme::General`] without message class,
+/// compression or automatic deletion is read back as
+/// [`DataCodingScheme::Alphabet`]. Octets with reserved bits set are kept as
+/// [`DataCodingScheme::Reserved`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataCodingScheme {
+    /// 0000xxxx: An SMPP character set.
+    Alphabet(DataCoding),
+    /// 00xxxxxx: General Data Coding indication, or 01xxxxxx: Message Marked
+    /// for Automatic Deletion group.
+    General {
+        /// The message is deleted by the receiving entity once read.
+        auto_delete: bool,
+        /// The text is compressed as specified in 3GPP TS 23.042.
+        compressed: bool,
+        alphabet: DcsAlphabet,
+        class: Option<DcsMessageClass>,
+    },
+    /// 1100xxxx to 1110xxxx: Message Waiting Indication groups.
+    MessageWaiting {
+        group: DcsWaitingGroup,
+        /// Whether the indication is set active or inactive.
+        active: bool,
+        indication: DcsIndicationType,
+    },
+    /// 1111xxxx: Data coding/message class.
+    MessageClass {
+        /// 8-bit data if set, else the GSM 7-bit default alphabet.
+        octet: bool,
+        class: DcsMessageClass,
+    },
+    /// 1000xxxx to 1011xxxx, or an octet with reserved bits set.
+    Reserved(u8),
+}
+
+impl DataCodingScheme {
+    /// Returns the [`DataCoding`] to decode the text with, or `None` if the
+    /// message carries 8-bit data, compressed text or a reserved coding.
+    #[must_use]
+    pub fn text_coding(&self) -> Option<DataCoding> {
+        match *self {
+            Self::Alphabet(DataCoding::Reserved(_)) | Self::Reserved(_) => None,
+            Self::Alphabet(data_coding) => Some(data_coding),
+            Self::General {
+                compressed: false,
+                alphabet,
+                ..
+            } => alphabet.text_coding(),
+            Self::General { .. } => None,
+            Self::MessageWaiting {
+                group: DcsWaitingGroup::StoreUcs2,
+                ..
+            } => Some(DataCoding::Ucs2),
+            Self::MessageWaiting { .. } | Self::MessageClass { octet: false, .. } => Some(DataCoding::GSM),
+            Self::MessageClass { octet: true, .. } => None,
+        }
+    }
+
+    /// Returns the message class, if one is given.
+    #[must_use]
+    pub const fn message_class(&self) -> Option<DcsMessageClass> {
+        match *self {
+            Self::General { class, .. } => class,
+            Self::MessageClass { class, .. } => Some(class),
+            _ => None,
+        }
+    }
+}
+
+impl From<DataCoding> for DataCodingScheme {
+    fn from(data_coding: DataCoding) -> Self {
+        Self::Alphabet(data_coding)
+    }
+}
+
+impl From<u8> for DataCodingScheme {
+    fn from(u: u8) -> Self {
+        use num_traits::FromPrimitive;
+
+        let class = FromPrimitive::from_u8(u & 0b00000011).unwrap_or_default();
+
+        let dcs = match u >> 4 {
+            0b0000 => Self::Alphabet(u.into()),
+            0b0001..=0b0111 => match FromPrimitive::from_u8((u >> 2) & 0b11) {
+                Some(alphabet) => Self::General {
+                    auto_delete: u & 0b01000000 != 0,
+                    compressed: u & 0b00100000 != 0,
+                    alphabet,
+                    class: (u & 0b00010000 != 0).then_some(class),
+                },
+                None => Self::Reserved(u),
+            },
+            0b1000..=0b1011 => Self::Reserved(u),
+            0b1100..=0b1110 => Self::MessageWaiting {
+                group: FromPrimitive::from_u8(u >> 4).unwrap_or_default(),
+                active: u & 0b00001000 != 0,
+                indication: FromPrimitive::from_u8(u & 0b00000011).unwrap_or_default(),
+            },
+            _ => Self::MessageClass {
+                octet: u & 0b00000100 != 0,
+                class,
+            },
+        };
+
+        // Reserved bits are not kept by the variants, so such octets are kept
+        // whole.
+        if u8::from(dcs) == u {
+            dcs
+        } else {
+            Self::Reserved(u)
+        }
+    }
+}
+
+impl From<DataCodingScheme> for u8 {
+    fn from(x: DataCodingScheme) -> Self {
+        use num_traits::ToPrimitive;
+
+        match x {
+            DataCodingScheme::Alphabet(data_coding) => data_coding.into(),
+            DataCodingScheme::General {
+                auto_delete,
+                compressed,
+                alphabet,
+                class,
+            } => {
+                let mut u = alphabet.to_u8().unwrap_or_default() << 2;
+                if auto_delete {
+                    u |= 0b01000000;
+                }
+                if compressed {
+                    u |= 0b00100000;
+                }
+                if let Some(class) = class {
+                    u |= 0b00010000 | class.to_u8().unwrap_or_default();
+                }
+
+                u
+            }
+            DataCodingScheme::MessageWaiting {
+                group,
+                active,
+                indication,
+            } => {
+                let active = if active { 0b00001000 } else { 0 };
+
+                (group.to_u8().unwrap_or_default() << 4) | active | indication.to_u8().unwrap_or_default()
+            }
+            DataCodingScheme::MessageClass { octet, class } => {
+                let octet = if octet { 0b00000100 } else { 0 };
+
+                0b11110000 | octet | class.to_u8().unwrap_or_default()
+            }
+            DataCodingScheme::Reserved(u) => u,
+        }
+    }
+}
+
+impl bincode::Decode for DataCodingScheme {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
+        let u = u8::decode(decoder)?;
+
+        Ok(u.into())
+    }
+}
+
+impl bincode::Encode for DataCodingScheme {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
+        let u: u8 = (*self).into();
+
+        u.encode(encoder)
+    }
+}
+
+/// The character set of the General Data Coding groups.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, num_derive::FromPrimitive, num_derive::ToPrimitive)]
+#[repr(u8)]
+pub enum DcsAlphabet {
+    /// GSM 7 bit default alphabet
+    #[default]
+    Gsm7  = 0b00,
+    /// 8 bit data
+    Octet = 0b01,
+    /// UCS2 (16 bit)
+    Ucs2  = 0b10,
+}
+
+impl DcsAlphabet {
+    const fn text_coding(self) -> Option<DataCoding> {
+        match self {
+            Self::Gsm7 => Some(DataCoding::GSM),
+            Self::Octet => None,
+            Self::Ucs2 => Some(DataCoding::Ucs2),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, num_derive::FromPrimitive, num_derive::ToPrimitive)]
+#[repr(u8)]
+pub enum DcsMessageClass {
+    /// Class 0, displayed immediately (flash message)
+    #[default]
+    Class0 = 0b00,
+    /// Class 1, Default meaning: ME-specific
+    Class1 = 0b01,
+    /// Class 2, (U)SIM-specific message
+    Class2 = 0b10,
+    /// Class 3, Default meaning: TE specific
+    Class3 = 0b11,
+}
+
+/// The coding group of a Message Waiting Indication.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, num_derive::FromPrimitive, num_derive::ToPrimitive)]
+#[repr(u8)]
+pub enum DcsWaitingGroup {
+    /// Discard Message, the text is in the GSM 7 bit default alphabet
+    #[default]
+    Discard   = 0b1100,
+    /// Store Message, the text is in the GSM 7 bit default alphabet
+    StoreGsm7 = 0b1101,
+    /// Store Message, the text is in UCS2
+    StoreUcs2 = 0b1110,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, num_derive::FromPrimitive, num_derive::ToPrimitive)]
+#[repr(u8)]
+pub enum DcsIndicationType {
+    /// Voicemail Message Waiting
+    #[default]
+    Voicemail = 0b00,
+    /// Fax Message Waiting
+    Fax       = 0b01,
+    /// Electronic Mail Message Waiting
+    Email     = 0b10,
+    /// Other Message Waiting
+    Other     = 0b11,
+}
@@ -0,0 +1,244 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::fmt;
+
+use encoding_rs::{EncoderResult, Encoding, EUC_JP, EUC_KR, ISO_2022_JP, ISO_8859_5, ISO_8859_8, SHIFT_JIS};
+
+use super::{gsm, DataCoding, GsmEncodeError, ShiftTables};
+
+/// The capacity of a short message, in octets.
+const OCTETS: usize = 140;
+
+/// The capacity of each part of a concatenated short message, in octets, after
+/// a UDH with an 8-bit reference concatenation information element.
+const CONCATENATED_OCTETS: usize = OCTETS - 6;
+
+/// A text encoded for the *short_message* of a PDU, together with the
+/// *data_coding* it is encoded with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncodedText {
+    pub data_coding: DataCoding,
+    pub bytes: Vec<u8>,
+    /// The number of short messages needed to carry the text. Texts longer
+    /// than a single short message are counted as concatenated parts, none of
+    /// which splits a GSM escape sequence or a UTF-16 surrogate pair.
+    pub segments: usize,
+}
+
+/// Encodes a text in the most compact alphabet that can represent it: the GSM
+/// 03.38 default alphabet with its extension table, else ISO-8859-1, else
+/// UCS-2.
+///
+/// # Note:
+///
+/// UCS-2 is written as UTF-16BE, so characters outside the Basic
+/// Multilingual Plane are carried as surrogate pairs.
+#[must_use]
+pub fn encode_text(s: &str) -> EncodedText {
+    let data_coding = if gsm::encode(s).is_ok() {
+        DataCoding::GSM
+    } else if s.chars().all(|c| u32::from(c) <= 0xFF) {
+        DataCoding::Latin1
+    } else {
+        DataCoding::Ucs2
+    };
+
+    let (single, concatenated) = match data_coding {
+        DataCoding::GSM => (OCTETS * 8 / 7, CONCATENATED_OCTETS * 8 / 7),
+        _ => (OCTETS, CONCATENATED_OCTETS),
+    };
+
+    let segments = if split_text(data_coding, s, single).len() == 1 {
+        1
+    } else {
+        split_text(data_coding, s, concatenated).len()
+    };
+
+    EncodedText {
+        data_coding,
+        bytes: encode_text_as(data_coding, s).unwrap_or_default(),
+        segments,
+    }
+}
+
+/// Decodes a *short_message* encoded with `data_coding`.
+///
+/// The GSM 03.38 default alphabet is expected in packed septets, and UCS-2 as
+/// UTF-16BE, so that surrogate pairs are decoded too.
+///
+/// # Errors
+///
+/// This function will return an error if `data_coding` is reserved or the
+/// bytes are not valid in its character set.
+pub fn decode_text(data_coding: DataCoding, bytes: &[u8]) -> Result<String, TextError> {
+    let malformed = || TextError::Malformed(data_coding);
+
+    match data_coding {
+        DataCoding::GSM => Ok(gsm::decode(bytes)),
+        DataCoding::Ascii if bytes.is_ascii() => Ok(bytes.iter().map(|b| char::from(*b)).collect()),
+        DataCoding::Ascii => Err(malformed()),
+        DataCoding::Latin1 => Ok(bytes.iter().map(|b| char::from(*b)).collect()),
+        DataCoding::Ucs2 => {
+            if bytes.len() % 2 != 0 {
+                return Err(malformed());
+            }
+
+            let units = bytes.chunks_exact(2).map(|b| u16::from_be_bytes([b[0], b[1]]));
+            char::decode_utf16(units)
+                .collect::<Result<String, _>>()
+                .map_err(|_| malformed())
+        }
+        DataCoding::Reserved(_) => Err(TextError::Unsupported(data_coding)),
+        _ => charset(data_coding)
+            .and_then(|encoding| encoding.decode_without_bom_handling_and_without_replacement(bytes))
+            .map(String::from)
+            .ok_or_else(malformed),
+    }
+}
+
+/// Encodes a text with `data_coding`, the reverse of [`decode_text`].
+///
+/// # Note:
+///
+/// ISO-2022-JP has no half-width katakana, which are written as their
+/// full-width forms instead.
+///
+/// # Errors
+///
+/// This function will return an error if `data_coding` is reserved or a
+/// character of the text is not in its character set.
+pub fn encode_text_as(data_coding: DataCoding, s: &str) -> Result<Vec<u8>, TextError> {
+    let unrepresentable = |limit: u32| {
+        s.chars()
+            .enumerate()
+            .find(|(_, c)| u32::from(*c) > limit)
+            .map(|(index, char)| TextError::Unrepresentable { index, char })
+    };
+
+    match data_coding {
+        DataCoding::GSM => Ok(gsm::encode(s)?),
+        DataCoding::Ascii => unrepresentable(0x7F).map_or_else(|| Ok(s.as_bytes().to_vec()), Err),
+        #[allow(clippy::cast_possible_truncation)]
+        DataCoding::Latin1 => unrepresentable(0xFF).map_or_else(|| Ok(s.chars().map(|c| c as u8).collect()), Err),
+        DataCoding::Ucs2 => Ok(s.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+        DataCoding::Reserved(_) => Err(TextError::Unsupported(data_coding)),
+        _ => charset(data_coding)
+            .ok_or(TextError::Unsupported(data_coding))
+            .and_then(|encoding| encode_charset(encoding, s)),
+    }
+}
+
+/// The character set of the multi-byte and ISO-8859 codings.
+fn charset(data_coding: DataCoding) -> Option<&'static Encoding> {
+    match data_coding {
+        DataCoding::ShiftJis => Some(SHIFT_JIS),
+        DataCoding::Cyrillic => Some(ISO_8859_5),
+        DataCoding::Hebrew => Some(ISO_8859_8),
+        DataCoding::Iso2022Jp => Some(ISO_2022_JP),
+        DataCoding::EucJp => Some(EUC_JP),
+        DataCoding::EucKr => Some(EUC_KR),
+        _ => None,
+    }
+}
+
+fn encode_charset(encoding: &'static Encoding, s: &str) -> Result<Vec<u8>, TextError> {
+    let mut encoder = encoding.new_encoder();
+    let mut buf = Vec::with_capacity(s.len() + 8);
+    let mut rest = s;
+
+    loop {
+        match encoder.encode_from_utf8_to_vec_without_replacement(rest, &mut buf, true) {
+            (EncoderResult::InputEmpty, _) => return Ok(buf),
+            (EncoderResult::OutputFull, read) => {
+                rest = &rest[read..];
+                buf.reserve(rest.len() + 8);
+            }
+            (EncoderResult::Unmappable(char), read) => {
+                let consumed = s.len() - rest.len() + read;
+                return Err(TextError::Unrepresentable {
+                    index: s[..consumed].chars().count() - 1,
+                    char,
+                });
+            }
+        }
+    }
+}
+
+/// Splits a text encoded by [`encode_text`] into parts of at most `capacity`
+/// septets for the GSM 03.38 default alphabet, or else octets, none of which
+/// splits a GSM escape sequence or a UTF-16 surrogate pair.
+pub(crate) fn split_text(data_coding: DataCoding, s: &str, capacity: usize) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut used = 0;
+    for (i, c) in s.char_indices() {
+        let size = char_size(data_coding, c);
+
+        // A CR in the last septet would be taken for padding, so it is
+        // doubled when packed and needs room for one more septet.
+        let room = if data_coding == DataCoding::GSM && c == '\r' {
+            size + 1
+        } else {
+            size
+        };
+
+        if used + room > capacity && i > start {
+            parts.push(&s[start..i]);
+            start = i;
+            used = 0;
+        }
+        used += size;
+    }
+
+    if start < s.len() || parts.is_empty() {
+        parts.push(&s[start..]);
+    }
+
+    parts
+}
+
+/// The size of `c` in septets for the GSM 03.38 default alphabet, or else in
+/// octets, for the alphabets chosen by [`encode_text`].
+fn char_size(data_coding: DataCoding, c: char) -> usize {
+    match data_coding {
+        DataCoding::GSM => gsm::septet_len(c, ShiftTables::default()).unwrap_or(1),
+        DataCoding::Ucs2 => c.len_utf16() * 2,
+        _ => 1,
+    }
+}
+
+/// Errors that occur while converting between text and a *short_message*.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextError {
+    /// The *data_coding* does not name a supported character set.
+    Unsupported(DataCoding),
+    /// The bytes are not valid in the character set of the *data_coding*.
+    Malformed(DataCoding),
+    /// The character set cannot represent the character at the given index of
+    /// the text, counted in characters.
+    Unrepresentable { index: usize, char: char },
+}
+
+impl fmt::Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported(data_coding) => write!(f, "unsupported data_coding {:#04x}", u8::from(*data_coding)),
+            Self::Malformed(data_coding) => write!(f, "malformed text for data_coding {:?}", data_coding),
+            Self::Unrepresentable { index, char } => {
+                write!(f, "character {:?} at index {} cannot be represented", char, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TextError {}
+
+impl From<GsmEncodeError> for TextError {
+    fn from(e: GsmEncodeError) -> Self {
+        Self::Unrepresentable {
+            index: e.index,
+            char: e.char,
+        }
+    }
+}
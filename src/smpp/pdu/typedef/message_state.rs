@@ -0,0 +1,133 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use super::{Tag, TLV};
+
+/// The state of a short message, as returned in a ***query_sm_resp***, carried
+/// in the *message_state* TLV and abbreviated in the *stat* field of a
+/// delivery receipt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, num_derive::FromPrimitive, num_derive::ToPrimitive)]
+#[repr(u8)]
+pub enum MessageState {
+    /// The message is scheduled. Delivery has not yet been initiated.
+    Scheduled     = 0,
+    /// The message is in enroute state.
+    Enroute       = 1,
+    /// Message is delivered to destination.
+    Delivered     = 2,
+    /// Message validity period has expired.
+    Expired       = 3,
+    /// Message has been deleted.
+    Deleted       = 4,
+    /// Message is undeliverable.
+    Undeliverable = 5,
+    /// Message is in accepted state (i.e. has been manually read on behalf of
+    /// the subscriber by customer service).
+    Accepted      = 6,
+    /// Message is in invalid state. The message state is unknown.
+    Unknown       = 7,
+    /// Message is in a rejected state.
+    Rejected      = 8,
+    /// The message was accepted but not transmitted or broadcast on the
+    /// network.
+    Skipped       = 9,
+}
+
+impl MessageState {
+    /// The abbreviation used in the *stat* field of a delivery receipt.
+    #[must_use]
+    pub const fn stat(self) -> &'static str {
+        match self {
+            Self::Scheduled => "SCHEDLD",
+            Self::Enroute => "ENROUTE",
+            Self::Delivered => "DELIVRD",
+            Self::Expired => "EXPIRED",
+            Self::Deleted => "DELETED",
+            Self::Undeliverable => "UNDELIV",
+            Self::Accepted => "ACCEPTD",
+            Self::Unknown => "UNKNOWN",
+            Self::Rejected => "REJECTD",
+            Self::Skipped => "SKIPPED",
+        }
+    }
+
+    /// Parses the *stat* field of a delivery receipt, accepting the
+    /// abbreviations as well as the full names some SMSCs (v5: MCs) use, in
+    /// any case.
+    #[must_use]
+    pub fn from_stat(s: &str) -> Option<Self> {
+        Some(match s.trim().to_ascii_uppercase().as_str() {
+            "SCHEDLD" | "SCHEDULED" => Self::Scheduled,
+            "ENROUTE" => Self::Enroute,
+            "DELIVRD" | "DELIVERED" => Self::Delivered,
+            "EXPIRED" => Self::Expired,
+            "DELETED" => Self::Deleted,
+            "UNDELIV" | "UNDELIVERABLE" => Self::Undeliverable,
+            "ACCEPTD" | "ACCEPTED" => Self::Accepted,
+            "UNKNOWN" => Self::Unknown,
+            "REJECTD" | "REJECTED" => Self::Rejected,
+            "SKIPPED" => Self::Skipped,
+            _ => return None,
+        })
+    }
+
+    /// Reads the value of a *message_state* TLV. Returns `None` if the TLV has
+    /// another tag or is not one octet long. Like in a decoded PDU, a value
+    /// outside the defined range is read as [`MessageState::Unknown`].
+    #[must_use]
+    pub fn from_tlv(tlv: &TLV) -> Option<Self> {
+        match (&tlv.tag, &tlv.val[..]) {
+            (Tag::MessageState, [u]) => Some((*u).try_into().unwrap_or(Self::Unknown)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the message has reached a final state and will not
+    /// change state any more.
+    #[must_use]
+    pub const fn is_final(self) -> bool {
+        !matches!(self, Self::Scheduled | Self::Enroute | Self::Unknown)
+    }
+}
+
+impl TryFrom<u8> for MessageState {
+    type Error = u8;
+
+    fn try_from(u: u8) -> Result<Self, Self::Error> {
+        use num_traits::FromPrimitive;
+
+        FromPrimitive::from_u8(u).ok_or(u)
+    }
+}
+
+impl From<MessageState> for u8 {
+    fn from(x: MessageState) -> Self {
+        x as Self
+    }
+}
+
+impl From<MessageState> for TLV {
+    fn from(x: MessageState) -> Self {
+        Self {
+            tag: Tag::MessageState,
+            len: 1,
+            val: vec![x.into()],
+        }
+    }
+}
+
+/// A value outside the defined range, as sent by some SMSCs (v5: MCs), is
+/// decoded as [`MessageState::Unknown`] instead of failing the whole PDU.
+impl bincode::Decode for MessageState {
+    fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
+        let u = u8::decode(decoder)?;
+
+        Ok(u.try_into().unwrap_or(Self::Unknown))
+    }
+}
+
+impl bincode::Encode for MessageState {
+    fn encode<E: bincode::enc::Encoder>(&self, encoder: &mut E) -> Result<(), bincode::error::EncodeError> {
+        u8::from(*self).encode(encoder)
+    }
+}
@@ -5,12 +5,18 @@ pub use addr::*;
 pub use c_octet::*;
 pub use data_coding::*;
 pub use esm_class::*;
+pub use message_state::*;
+pub use receipt::*;
 pub use time::*;
 pub use tlv::*;
+pub use udh::*;
 
 mod addr;
 mod c_octet;
 mod data_coding;
 mod esm_class;
+mod message_state;
+mod receipt;
 mod time;
 mod tlv;
+mod udh;
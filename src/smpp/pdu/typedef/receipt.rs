@@ -0,0 +1,303 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::{fmt, str::FromStr};
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use super::{EsmClass, EsmClassMessageType, MessageState, Tag, TLV};
+use crate::smpp::pdu::command::DeliverSm;
+
+/// A delivery receipt, as carried in the *short_message* of a ***deliver_sm***
+/// whose *esm_class* is marked as an SMSC (v5: MC) Delivery Receipt.
+///
+/// The text format is not mandated by the specification but follows the
+/// example given in its appendix:
+///
+/// ```text
+/// id:IIIIIIIIII sub:SSS dlvrd:DDD submit date:YYMMDDhhmm done date:YYMMDDhhmm stat:DDDDDDD err:E text: . . . . .
+/// ```
+///
+/// Parsing accepts the variants found in the wild: fields in any order and
+/// case, `_` in place of the space in the date field names, dates with or
+/// without seconds, and any of the fields but *id* missing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeliveryReceipt {
+    /// The *message_id* allocated to the message by the SMSC (v5: MC).
+    pub id: String,
+    /// The number of short messages originally submitted.
+    pub sub: Option<u32>,
+    /// The number of short messages delivered.
+    pub dlvrd: Option<u32>,
+    pub submit_date: Option<NaiveDateTime>,
+    pub done_date: Option<NaiveDateTime>,
+    pub stat: MessageState,
+    /// The network or SMSC (v5: MC) error code, if any.
+    pub err: Option<String>,
+    /// The first characters of the original message.
+    pub text: String,
+}
+
+impl DeliveryReceipt {
+    /// Parses the receipt from a ***deliver_sm***, taking its text from the
+    /// *short_message* or, if that is empty, the *message_payload* TLV.
+    ///
+    /// Fields missing from the text are filled in from the
+    /// *receipted_message_id* and *message_state* TLVs. When both are
+    /// present, the text must agree with those TLVs and with the
+    /// *network_error_code* TLV.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the ***deliver_sm*** is not a
+    /// delivery receipt, its text is malformed, or the text contradicts a
+    /// TLV.
+    pub fn from_deliver_sm(deliver_sm: &DeliverSm) -> Result<Self, ReceiptError> {
+        if !matches!(
+            EsmClass::from(deliver_sm.esm_class).message_type,
+            EsmClassMessageType::DeliveryReceipt
+        ) {
+            return Err(ReceiptError::NotAReceipt);
+        }
+
+        let tlv = |tag: fn(&Tag) -> bool| deliver_sm.msg_delivery_tlv.iter().find(|t| tag(&t.tag));
+
+        let text = match tlv(|t| matches!(t, Tag::MessagePayload)) {
+            Some(payload) if deliver_sm.short_message.is_empty() => &payload.val,
+            _ => &deliver_sm.short_message,
+        };
+        let mut fields = Fields::parse(&String::from_utf8_lossy(text));
+
+        let receipted_message_id = tlv(|t| matches!(t, Tag::ReceiptedMessageId)).map(|t| {
+            let id = t.val.split(|b| *b == 0).next().unwrap_or_default();
+            String::from_utf8_lossy(id).into_owned()
+        });
+        let message_state = tlv(|t| matches!(t, Tag::MessageState))
+            .map(|t| MessageState::from_tlv(t).ok_or(ReceiptError::Mismatch(Tag::MessageState)))
+            .transpose()?;
+        let network_error_code = tlv(|t| matches!(t, Tag::NetworkErrorCode)).and_then(network_error_code);
+
+        let id = match (fields.id.take(), receipted_message_id) {
+            (Some(id), Some(tlv)) if !same_id(&id, &tlv) => {
+                return Err(ReceiptError::Mismatch(Tag::ReceiptedMessageId))
+            }
+            (Some(id), _) | (None, Some(id)) => id,
+            (None, None) => return Err(ReceiptError::MissingField("id")),
+        };
+
+        let stat = match (fields.stat.take(), message_state) {
+            // An unknown state, as an out-of-range value is also read, does
+            // not contradict the text.
+            (Some(stat), Some(tlv)) if stat != tlv && tlv != MessageState::Unknown => {
+                return Err(ReceiptError::Mismatch(Tag::MessageState))
+            }
+            (Some(stat), _) | (None, Some(stat)) => stat,
+            (None, None) => return Err(ReceiptError::MissingField("stat")),
+        };
+
+        if let (Some(err), Some(code)) = (&fields.err, network_error_code) {
+            if err.parse::<u16>().is_ok_and(|err| err != code) {
+                return Err(ReceiptError::Mismatch(Tag::NetworkErrorCode));
+            }
+        }
+
+        Ok(Self {
+            id,
+            stat,
+            ..fields.into_receipt()
+        })
+    }
+
+    /// Returns the final state of the message, or `None` if the receipt is an
+    /// intermediate notification.
+    #[must_use]
+    pub fn final_state(&self) -> Option<MessageState> {
+        Some(self.stat).filter(|stat| stat.is_final())
+    }
+}
+
+impl FromStr for DeliveryReceipt {
+    type Err = ReceiptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = Fields::parse(s);
+
+        Ok(Self {
+            id: fields.id.take().ok_or(ReceiptError::MissingField("id"))?,
+            stat: fields.stat.take().ok_or(ReceiptError::MissingField("stat"))?,
+            ..fields.into_receipt()
+        })
+    }
+}
+
+impl fmt::Display for DeliveryReceipt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "id:{}", self.id)?;
+        write!(f, " sub:{:03}", self.sub.unwrap_or(1))?;
+        write!(f, " dlvrd:{:03}", self.dlvrd.unwrap_or(0))?;
+        write!(f, " submit date:{}", format_date(self.submit_date))?;
+        write!(f, " done date:{}", format_date(self.done_date))?;
+        write!(f, " stat:{}", self.stat.stat())?;
+        write!(f, " err:{}", self.err.as_deref().unwrap_or("000"))?;
+        write!(f, " text:{}", self.text)
+    }
+}
+
+/// The fields of a receipt text, each of which may be missing.
+#[derive(Default)]
+struct Fields {
+    id: Option<String>,
+    sub: Option<u32>,
+    dlvrd: Option<u32>,
+    submit_date: Option<NaiveDateTime>,
+    done_date: Option<NaiveDateTime>,
+    stat: Option<MessageState>,
+    err: Option<String>,
+    text: Option<String>,
+}
+
+impl Fields {
+    /// Field names and the spellings they are found under.
+    const KEYS: [(&'static str, &'static [&'static str]); 8] = [
+        ("id", &["id"]),
+        ("sub", &["sub"]),
+        ("dlvrd", &["dlvrd", "dlvr"]),
+        ("submit date", &["submit date", "submit_date", "submitdate"]),
+        ("done date", &["done date", "done_date", "donedate"]),
+        ("stat", &["stat", "status"]),
+        ("err", &["err", "error"]),
+        ("text", &["text"]),
+    ];
+
+    fn parse(s: &str) -> Self {
+        let lower = s.to_ascii_lowercase();
+
+        // The text is the last field and may contain anything, including
+        // other field names, so nothing after it is looked at.
+        let text_start = find_key(&lower, "text", lower.len());
+        let end = text_start.map_or(lower.len(), |(at, _)| at);
+
+        let mut found: Vec<(usize, usize, &str)> = Self::KEYS
+            .iter()
+            .filter(|(name, _)| *name != "text")
+            .filter_map(|(name, spellings)| {
+                spellings
+                    .iter()
+                    .find_map(|spelling| find_key(&lower, spelling, end))
+                    .map(|(at, value)| (at, value, *name))
+            })
+            .collect();
+        found.sort_unstable();
+
+        let mut fields = Self {
+            text: text_start.map(|(_, value)| s[value..].trim().to_string()),
+            ..Self::default()
+        };
+
+        for (i, (_, value, name)) in found.iter().enumerate() {
+            let value_end = found.get(i + 1).map_or(end, |(at, ..)| *at);
+            let value = s[*value..value_end].trim();
+
+            match *name {
+                "id" => fields.id = Some(value.to_string()),
+                "sub" => fields.sub = value.parse().ok(),
+                "dlvrd" => fields.dlvrd = value.parse().ok(),
+                "submit date" => fields.submit_date = parse_date(value),
+                "done date" => fields.done_date = parse_date(value),
+                "stat" => fields.stat = MessageState::from_stat(value),
+                "err" => fields.err = Some(value.to_string()).filter(|err| !err.is_empty()),
+                _ => {}
+            }
+        }
+
+        fields
+    }
+
+    fn into_receipt(self) -> DeliveryReceipt {
+        DeliveryReceipt {
+            id: self.id.unwrap_or_default(),
+            sub: self.sub,
+            dlvrd: self.dlvrd,
+            submit_date: self.submit_date,
+            done_date: self.done_date,
+            stat: self.stat.unwrap_or(MessageState::Unknown),
+            err: self.err,
+            text: self.text.unwrap_or_default(),
+        }
+    }
+}
+
+/// Finds `key` followed by `:` at the start of `s` or after whitespace, before
+/// `end`. Returns the offsets of the key and of its value.
+fn find_key(s: &str, key: &str, end: usize) -> Option<(usize, usize)> {
+    let pattern = format!("{}:", key);
+
+    s[..end].match_indices(&pattern).find_map(|(at, _)| {
+        let boundary = at == 0 || s[..at].ends_with(|c: char| c.is_whitespace());
+        boundary.then(|| (at, at + pattern.len()))
+    })
+}
+
+/// Parses a date in the form YYMMDDhhmm or YYMMDDhhmmss.
+fn parse_date(s: &str) -> Option<NaiveDateTime> {
+    if !(s.len() == 10 || s.len() == 12) || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let n = |i: usize| s[i..i + 2].parse::<u32>().ok();
+    let ss = if s.len() == 12 { n(10)? } else { 0 };
+
+    NaiveDate::from_ymd_opt(2000 + i32::try_from(n(0)?).ok()?, n(2)?, n(4)?)?.and_hms_opt(n(6)?, n(8)?, ss)
+}
+
+/// Formats a date as YYMMDDhhmm, the form given by the specification. Seconds
+/// are dropped.
+fn format_date(date: Option<NaiveDateTime>) -> String {
+    date.map(|date| date.format("%y%m%d%H%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Extracts the error code from a *network_error_code* TLV, which holds the
+/// network type in its first octet and the code in the following two.
+fn network_error_code(tlv: &TLV) -> Option<u16> {
+    match tlv.val[..] {
+        [_, hi, lo] => Some(u16::from_be_bytes([hi, lo])),
+        _ => None,
+    }
+}
+
+/// Compares message IDs, tolerating SMSCs (v5: MCs) that report the ID in
+/// decimal in one place and in hexadecimal in the other.
+fn same_id(a: &str, b: &str) -> bool {
+    if a.eq_ignore_ascii_case(b) {
+        return true;
+    }
+
+    let dec = |s: &str| s.parse::<u128>().ok();
+    let hex = |s: &str| u128::from_str_radix(s, 16).ok();
+
+    matches!((dec(a), hex(b)), (Some(x), Some(y)) if x == y) || matches!((hex(a), dec(b)), (Some(x), Some(y)) if x == y)
+}
+
+/// Errors that occur while reading a delivery receipt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReceiptError {
+    /// The *esm_class* does not mark the message as a delivery receipt.
+    NotAReceipt,
+    /// A field required to identify the receipt is missing.
+    MissingField(&'static str),
+    /// The text contradicts the TLV with the given tag.
+    Mismatch(Tag),
+}
+
+impl fmt::Display for ReceiptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAReceipt => write!(f, "not a delivery receipt"),
+            Self::MissingField(field) => write!(f, "delivery receipt lacks the {} field", field),
+            Self::Mismatch(tag) => write!(f, "delivery receipt contradicts the {:?} TLV", tag),
+        }
+    }
+}
+
+impl std::error::Error for ReceiptError {}
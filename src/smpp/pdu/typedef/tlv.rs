@@ -17,6 +17,57 @@ pub struct TLV {
     pub val: Vec<u8>,
 }
 
+impl TLV {
+    /// Decodes a single TLV, or returns `None` if the input is exhausted.
+    ///
+    /// # Note:
+    ///
+    /// The end of input can only be detected if the decoder reads from a
+    /// slice, which should hold exactly one PDU body, e.g.
+    /// [`Frame::body`](crate::smpp::pdu::Frame::body).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if there is input left but it does
+    /// not hold a complete TLV, or if the decoder does not read from a slice,
+    /// e.g. with [`bincode::decode_from_std_read`].
+    pub fn decode_optional<D: bincode::de::Decoder>(
+        decoder: &mut D,
+    ) -> Result<Option<Self>, bincode::error::DecodeError> {
+        use bincode::de::read::Reader;
+
+        if decoder.reader().peek_read(1).is_none() {
+            // Only readers that can look ahead, such as a slice, return an
+            // empty peek rather than none at all.
+            if decoder.reader().peek_read(0).is_none() {
+                return Err(bincode::error::DecodeError::Other(
+                    "TLVs can only be decoded from a slice",
+                ));
+            }
+
+            return Ok(None);
+        }
+
+        <Self as bincode::Decode>::decode(decoder).map(Some)
+    }
+
+    /// Decodes TLVs until the input is exhausted.
+    ///
+    /// See [`TLV::decode_optional`] for how the end of input is detected.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the last TLV is truncated.
+    pub fn decode_all<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Vec<Self>, bincode::error::DecodeError> {
+        let mut v = vec![];
+        while let Some(t) = Self::decode_optional(decoder)? {
+            v.push(t);
+        }
+
+        Ok(v)
+    }
+}
+
 impl bincode::Decode for TLV {
     fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<Self, bincode::error::DecodeError> {
         use bincode::de::read::Reader;
@@ -42,7 +93,7 @@ impl bincode::Encode for TLV {
 }
 
 /// SMPP Optional Parameter Tag
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[repr(u16)]
 pub enum Tag {
     DestAddrSubunit,          /* = 0x0005 */
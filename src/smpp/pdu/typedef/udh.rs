@@ -0,0 +1,346 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::fmt;
+
+use super::{
+    data_coding::{decode_with_fill, encode_with_fill},
+    DcsIndicationType, GsmEncodeError, NationalLanguage, ShiftTables,
+};
+
+/// The User Data Header (3GPP TS 23.040) at the start of the *short_message*
+/// of a message whose *esm_class* has [`UDHI`](super::EsmClassGsm::UDHI)
+/// set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserDataHeader {
+    pub elements: Vec<InformationElement>,
+}
+
+impl UserDataHeader {
+    /// Splits a *short_message* into its UDH and the data following it.
+    ///
+    /// # Note:
+    ///
+    /// For text in the GSM 7-bit default alphabet, the data starts with fill
+    /// bits; use [`UserDataHeader::split_gsm`] instead.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the UDH is longer than the
+    /// *short_message* or an information element is longer than the UDH.
+    pub fn split(short_message: &[u8]) -> Result<(Self, &[u8]), UdhError> {
+        let (len, rest) = short_message.split_first().ok_or(UdhError::Truncated)?;
+        let len = usize::from(*len);
+        if rest.len() < len {
+            return Err(UdhError::Truncated);
+        }
+
+        let (mut ies, data) = rest.split_at(len);
+        let mut elements = vec![];
+        while let [iei, len, tail @ ..] = ies {
+            let len = usize::from(*len);
+            if tail.len() < len {
+                return Err(UdhError::Truncated);
+            }
+
+            let (ie, tail) = tail.split_at(len);
+            elements.push(InformationElement::from_bytes(*iei, ie));
+            ies = tail;
+        }
+
+        if !ies.is_empty() {
+            return Err(UdhError::Truncated);
+        }
+
+        Ok((Self { elements }, data))
+    }
+
+    /// Splits a *short_message* carrying GSM 7-bit packed text into its UDH
+    /// and the text, which is decoded with the national language tables the
+    /// UDH announces.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the UDH is malformed, as for
+    /// [`UserDataHeader::split`].
+    pub fn split_gsm(short_message: &[u8]) -> Result<(Self, String), UdhError> {
+        let (udh, data) = Self::split(short_message)?;
+        let text = decode_with_fill(data, udh.shift_tables(), udh.fill_bits());
+
+        Ok((udh, text))
+    }
+
+    /// Writes the UDH followed by `data`, the reverse of
+    /// [`UserDataHeader::split`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the UDH is longer than 255
+    /// octets, as for [`UserDataHeader::to_bytes`].
+    pub fn join(&self, data: &[u8]) -> Result<Vec<u8>, UdhError> {
+        let mut v = self.to_bytes()?;
+        v.extend_from_slice(data);
+
+        Ok(v)
+    }
+
+    /// Writes the UDH followed by `s` in GSM 7-bit packed septets, using the
+    /// national language tables the UDH announces. The reverse of
+    /// [`UserDataHeader::split_gsm`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the UDH is longer than 255
+    /// octets, or for the first character the tables cannot represent.
+    pub fn join_gsm(&self, s: &str) -> Result<Vec<u8>, UdhError> {
+        let text = encode_with_fill(s, self.shift_tables(), self.fill_bits())?;
+
+        self.join(&text)
+    }
+
+    /// Encodes the UDH, starting with its length octet.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the information elements take
+    /// more than 255 octets, which the length octet cannot count.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, UdhError> {
+        // Each information element is shorter than the whole, so its own
+        // length octet cannot overflow either.
+        let len = u8::try_from(self.encoded_len() - 1).map_err(|_| UdhError::TooLong)?;
+
+        let mut v = vec![len];
+        for element in &self.elements {
+            element.write(&mut v);
+        }
+
+        Ok(v)
+    }
+
+    /// The length of the encoded UDH in octets, counting its length octet.
+    /// An empty UDH still takes that one octet.
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        1 + self.elements.iter().map(|e| 2 + e.data_len()).sum::<usize>()
+    }
+
+    /// Returns `true` if the UDH holds no information element.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// The number of fill bits between the UDH and GSM 7-bit text, which
+    /// starts on a septet boundary counted from the start of the UDH.
+    #[must_use]
+    pub fn fill_bits(&self) -> u32 {
+        #[allow(clippy::cast_possible_truncation)]
+        let bits = (self.encoded_len() * 8 % 7) as u32;
+
+        (7 - bits) % 7
+    }
+
+    /// Returns the *reference*, *total* and *sequence* of a concatenated
+    /// message, from either concatenation information element.
+    #[must_use]
+    pub fn concatenation(&self) -> Option<(u16, u8, u8)> {
+        self.elements.iter().find_map(|e| match *e {
+            InformationElement::Concatenated8 {
+                reference,
+                total,
+                sequence,
+            } => Some((u16::from(reference), total, sequence)),
+            InformationElement::Concatenated16 {
+                reference,
+                total,
+                sequence,
+            } => Some((reference, total, sequence)),
+            _ => None,
+        })
+    }
+
+    /// Returns the national language tables announced by the UDH.
+    #[must_use]
+    pub fn shift_tables(&self) -> ShiftTables {
+        ShiftTables::from_information_elements(&self.elements)
+    }
+}
+
+/// A UDH announcing the national language tables, which is empty for the
+/// default alphabet.
+impl From<ShiftTables> for UserDataHeader {
+    fn from(tables: ShiftTables) -> Self {
+        Self {
+            elements: tables.information_elements(),
+        }
+    }
+}
+
+/// An information element of a [`UserDataHeader`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InformationElement {
+    /// 0x00: Concatenated short messages, 8-bit reference number.
+    Concatenated8 { reference: u8, total: u8, sequence: u8 },
+    /// 0x01: Special SMS Message Indication.
+    SpecialSmsIndication {
+        /// Whether the message is stored, or else discarded, after the
+        /// indication is updated.
+        store: bool,
+        /// The profile ID and extended message indication type, as in bits
+        /// 6 to 2 of the first octet.
+        extended: u8,
+        indication: DcsIndicationType,
+        /// The number of messages waiting.
+        count: u8,
+    },
+    /// 0x04: Application port addressing scheme, 8 bit address.
+    ApplicationPort8 { destination: u8, source: u8 },
+    /// 0x05: Application port addressing scheme, 16 bit address.
+    ApplicationPort16 { destination: u16, source: u16 },
+    /// 0x08: Concatenated short messages, 16-bit reference number.
+    Concatenated16 { reference: u16, total: u8, sequence: u8 },
+    /// 0x24: National Language Single Shift.
+    NationalSingleShift(NationalLanguage),
+    /// 0x25: National Language Locking Shift.
+    NationalLockingShift(NationalLanguage),
+    /// An information element of another kind, or a known one with unexpected
+    /// data, kept as is.
+    Unknown { iei: u8, data: Vec<u8> },
+}
+
+impl InformationElement {
+    /// The Information Element Identifier.
+    #[must_use]
+    pub const fn iei(&self) -> u8 {
+        match self {
+            Self::Concatenated8 { .. } => 0x00,
+            Self::SpecialSmsIndication { .. } => 0x01,
+            Self::ApplicationPort8 { .. } => 0x04,
+            Self::ApplicationPort16 { .. } => 0x05,
+            Self::Concatenated16 { .. } => 0x08,
+            Self::NationalSingleShift(_) => 0x24,
+            Self::NationalLockingShift(_) => 0x25,
+            Self::Unknown { iei, .. } => *iei,
+        }
+    }
+
+    fn from_bytes(iei: u8, data: &[u8]) -> Self {
+        use num_traits::FromPrimitive;
+
+        match (iei, data) {
+            (0x00, &[reference, total, sequence]) => Self::Concatenated8 {
+                reference,
+                total,
+                sequence,
+            },
+            (0x01, &[flags, count]) => Self::SpecialSmsIndication {
+                store: flags & 0b10000000 != 0,
+                extended: (flags & 0b01111100) >> 2,
+                indication: FromPrimitive::from_u8(flags & 0b00000011).unwrap_or_default(),
+                count,
+            },
+            (0x04, &[destination, source]) => Self::ApplicationPort8 { destination, source },
+            (0x05, &[d1, d2, s1, s2]) => Self::ApplicationPort16 {
+                destination: u16::from_be_bytes([d1, d2]),
+                source: u16::from_be_bytes([s1, s2]),
+            },
+            (0x08, &[r1, r2, total, sequence]) => Self::Concatenated16 {
+                reference: u16::from_be_bytes([r1, r2]),
+                total,
+                sequence,
+            },
+            (0x24, &[lang]) => {
+                NationalLanguage::from_u8(lang).map_or_else(|| Self::unknown(iei, data), Self::NationalSingleShift)
+            }
+            (0x25, &[lang]) => {
+                NationalLanguage::from_u8(lang).map_or_else(|| Self::unknown(iei, data), Self::NationalLockingShift)
+            }
+            _ => Self::unknown(iei, data),
+        }
+    }
+
+    fn unknown(iei: u8, data: &[u8]) -> Self {
+        Self::Unknown {
+            iei,
+            data: data.to_vec(),
+        }
+    }
+
+    fn data_len(&self) -> usize {
+        match self {
+            Self::Concatenated8 { .. } => 3,
+            Self::SpecialSmsIndication { .. } | Self::ApplicationPort8 { .. } => 2,
+            Self::ApplicationPort16 { .. } | Self::Concatenated16 { .. } => 4,
+            Self::NationalSingleShift(_) | Self::NationalLockingShift(_) => 1,
+            Self::Unknown { data, .. } => data.len(),
+        }
+    }
+
+    /// Writes the element, whose length [`UserDataHeader::to_bytes`] has
+    /// checked to fit in an octet.
+    fn write(&self, v: &mut Vec<u8>) {
+        #[allow(clippy::cast_possible_truncation)]
+        v.extend([self.iei(), self.data_len() as u8]);
+
+        match self {
+            Self::Concatenated8 {
+                reference,
+                total,
+                sequence,
+            } => v.extend([*reference, *total, *sequence]),
+            Self::SpecialSmsIndication {
+                store,
+                extended,
+                indication,
+                count,
+            } => {
+                let store = if *store { 0b10000000 } else { 0 };
+                v.extend([store | (extended & 0b00011111) << 2 | *indication as u8, *count]);
+            }
+            Self::ApplicationPort8 { destination, source } => v.extend([*destination, *source]),
+            Self::ApplicationPort16 { destination, source } => {
+                v.extend(destination.to_be_bytes());
+                v.extend(source.to_be_bytes());
+            }
+            Self::Concatenated16 {
+                reference,
+                total,
+                sequence,
+            } => {
+                v.extend(reference.to_be_bytes());
+                v.extend([*total, *sequence]);
+            }
+            Self::NationalSingleShift(lang) | Self::NationalLockingShift(lang) => v.push(*lang as u8),
+            Self::Unknown { data, .. } => v.extend_from_slice(data),
+        }
+    }
+}
+
+/// Errors that occur while reading or writing a [`UserDataHeader`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UdhError {
+    /// The UDH or one of its information elements is cut short.
+    Truncated,
+    /// The UDH is longer than 255 octets.
+    TooLong,
+    /// The text following the UDH cannot be represented in GSM 7-bit septets.
+    Unrepresentable(GsmEncodeError),
+}
+
+impl fmt::Display for UdhError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "truncated user data header"),
+            Self::TooLong => write!(f, "user data header longer than 255 octets"),
+            Self::Unrepresentable(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for UdhError {}
+
+impl From<GsmEncodeError> for UdhError {
+    fn from(e: GsmEncodeError) -> Self {
+        Self::Unrepresentable(e)
+    }
+}
@@ -0,0 +1,323 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+//! An asynchronous SMSC (v5: MC) server.
+//!
+//! [`Server`] accepts ESME connections over TCP. Bind requests are checked by
+//! an [`Authenticator`], and the operations requested by a bound ESME are
+//! passed to a [`Handler`]. Every bound [`Session`] is kept in [`Sessions`],
+//! through which messages may be delivered to receivers and transceivers.
+
+use std::{
+    collections::HashMap,
+    fmt, io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use futures_util::future::BoxFuture;
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    time,
+};
+
+pub use super::session::BindMode;
+pub use connection::Session;
+
+use super::{
+    pdu::{
+        command::{
+            Bind, CancelSm, CancelSmResp, DataSm, DataSmResp, OutBind, QuerySm, QuerySmResp, ReplaceSm, ReplaceSmResp,
+            Status, SubmitMulti, SubmitMultiResp, SubmitSm, SubmitSmResp,
+        },
+        typedef::COctet,
+    },
+    session::RequestError,
+};
+
+mod connection;
+
+/// How long to pause accepting connections after an error such as running out
+/// of file descriptors.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Decides which ESMEs may bind.
+pub trait Authenticator: Send + Sync + 'static {
+    /// Checks a bind request. A rejected request is answered with the
+    /// returned *command_status*, such as `ESME_RINVPASWD` or
+    /// `ESME_RINVSYSID`.
+    fn authenticate<'a>(&'a self, mode: BindMode, bind: &'a Bind) -> BoxFuture<'a, Result<(), Status>>;
+}
+
+/// The answer of a [`Handler`] to a request: the *command_status* and the body
+/// of the response.
+///
+/// A response without a body is sent as the header alone, which is how the
+/// SMSC (v5: MC) usually reports an error.
+#[derive(Clone, Debug)]
+pub struct Reply<T> {
+    pub status: Status,
+    pub body: Option<T>,
+}
+
+impl<T> Reply<T> {
+    /// A positive response carrying `body`.
+    pub fn ok(body: T) -> Self {
+        Self {
+            status: Status::ESME_ROK,
+            body: Some(body),
+        }
+    }
+
+    /// A negative response without a body.
+    pub fn error(status: Status) -> Self {
+        Self { status, body: None }
+    }
+
+    fn map<U>(self, f: impl FnOnce(T) -> U) -> Reply<U> {
+        Reply {
+            status: self.status,
+            body: self.body.map(f),
+        }
+    }
+}
+
+/// Serves the requests of bound ESMEs.
+///
+/// Each request is handled in a task of its own, so a slow handler does not
+/// hold back the session. Requests whose handler is not implemented are
+/// answered with `ESME_RINVCMDID`.
+#[allow(unused_variables)]
+pub trait Handler: Send + Sync + 'static {
+    fn submit_sm<'a>(&'a self, session: &'a Session, submit_sm: SubmitSm) -> BoxFuture<'a, Reply<SubmitSmResp>> {
+        unsupported()
+    }
+
+    fn submit_multi<'a>(
+        &'a self,
+        session: &'a Session,
+        submit_multi: SubmitMulti,
+    ) -> BoxFuture<'a, Reply<SubmitMultiResp>> {
+        unsupported()
+    }
+
+    fn data_sm<'a>(&'a self, session: &'a Session, data_sm: DataSm) -> BoxFuture<'a, Reply<DataSmResp>> {
+        unsupported()
+    }
+
+    fn query_sm<'a>(&'a self, session: &'a Session, query_sm: QuerySm) -> BoxFuture<'a, Reply<QuerySmResp>> {
+        unsupported()
+    }
+
+    fn cancel_sm<'a>(&'a self, session: &'a Session, cancel_sm: CancelSm) -> BoxFuture<'a, Reply<CancelSmResp>> {
+        unsupported()
+    }
+
+    fn replace_sm<'a>(&'a self, session: &'a Session, replace_sm: ReplaceSm) -> BoxFuture<'a, Reply<ReplaceSmResp>> {
+        unsupported()
+    }
+}
+
+fn unsupported<'a, T: Send + 'a>() -> BoxFuture<'a, Reply<T>> {
+    Box::pin(async { Reply::error(Status::ESME_RINVCMDID) })
+}
+
+/// Settings of a [`Server`].
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// The identifier of the SMSC (v5: MC) reported in the bind response.
+    pub system_id: COctet,
+    /// The SMPP version reported in the *sc_interface_version* TLV of the
+    /// bind response.
+    pub interface_version: u8,
+    /// How long to wait for the response to a request sent to an ESME.
+    pub request_timeout: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            system_id: COctet::default(),
+            interface_version: if cfg!(feature = "v5") { 0x50 } else { 0x34 },
+            request_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// An SMSC (v5: MC) accepting ESME connections on a TCP listener.
+pub struct Server<A, H> {
+    listener: TcpListener,
+    authenticator: Arc<A>,
+    handler: Arc<H>,
+    config: Arc<Config>,
+    sessions: Sessions,
+    next_id: AtomicU64,
+}
+
+impl<A: Authenticator, H: Handler> Server<A, H> {
+    /// Listens on `addr`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the listener cannot be bound.
+    pub async fn bind<T: ToSocketAddrs>(addr: T, authenticator: A, handler: H, config: Config) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr).await?,
+            authenticator: Arc::new(authenticator),
+            handler: Arc::new(handler),
+            config: Arc::new(config),
+            sessions: Sessions::default(),
+            next_id: AtomicU64::new(0),
+        })
+    }
+
+    /// The address the server listens on.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the address cannot be obtained.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// The sessions bound to the server, which stays valid while the server
+    /// runs.
+    #[must_use]
+    pub fn sessions(&self) -> Sessions {
+        self.sessions.clone()
+    }
+
+    /// Accepts connections and serves each of them in a task of its own,
+    /// until the returned future is dropped.
+    ///
+    /// Failing to accept a connection does not stop the server. A connection
+    /// aborted before it is accepted is skipped; on any other error, such as
+    /// running out of file descriptors, accepting pauses for a moment.
+    pub async fn run(&self) {
+        loop {
+            let stream = match self.listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    if !matches!(
+                        e.kind(),
+                        io::ErrorKind::ConnectionAborted | io::ErrorKind::ConnectionReset | io::ErrorKind::Interrupted
+                    ) {
+                        time::sleep(ACCEPT_BACKOFF).await;
+                    }
+
+                    continue;
+                }
+            };
+
+            self.serve(stream, None);
+        }
+    }
+
+    /// Connects to an ESME waiting at `addr` for the SMSC (v5: MC) to open the
+    /// session, and sends it `outbind`. Once the ESME binds as a receiver, the
+    /// session is served like an accepted one.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the connection cannot be
+    /// established.
+    pub async fn outbind<T: ToSocketAddrs>(&self, addr: T, outbind: OutBind) -> io::Result<()> {
+        let stream = TcpStream::connect(addr).await?;
+        self.serve(stream, Some(outbind));
+
+        Ok(())
+    }
+
+    fn serve(&self, stream: TcpStream, outbind: Option<OutBind>) {
+        tokio::spawn(connection::serve(
+            stream,
+            self.next_id.fetch_add(1, Ordering::Relaxed),
+            outbind,
+            self.authenticator.clone(),
+            self.handler.clone(),
+            self.config.clone(),
+            self.sessions.clone(),
+        ));
+    }
+}
+
+/// The sessions currently bound to a [`Server`].
+#[derive(Clone, Default)]
+pub struct Sessions {
+    inner: Arc<Mutex<HashMap<u64, Session>>>,
+}
+
+impl Sessions {
+    /// All bound sessions.
+    #[must_use]
+    pub fn list(&self) -> Vec<Session> {
+        self.inner.lock().unwrap().values().cloned().collect()
+    }
+
+    /// The bound sessions that messages may be delivered to, i.e. those bound
+    /// as a receiver or transceiver.
+    #[must_use]
+    pub fn receivers(&self) -> Vec<Session> {
+        self.inner
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|session| session.state().can_receive())
+            .cloned()
+            .collect()
+    }
+
+    fn insert(&self, session: Session) {
+        self.inner.lock().unwrap().insert(session.id(), session);
+    }
+
+    fn remove(&self, id: u64) {
+        self.inner.lock().unwrap().remove(&id);
+    }
+}
+
+/// Errors that occur in a server session.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The request is not allowed in the current state of the session.
+    Rejected(Status),
+    /// The ESME did not answer the request positively.
+    Request(RequestError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::Rejected(status) => write!(f, "request not allowed in this state: {:?}", status),
+            Self::Request(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Request(e) => Some(e),
+            Self::Rejected(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<RequestError> for Error {
+    fn from(e: RequestError) -> Self {
+        Self::Request(e)
+    }
+}
@@ -0,0 +1,333 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_util::{future::BoxFuture, StreamExt};
+use tokio::{
+    net::TcpStream,
+    sync::{mpsc, watch},
+};
+use tokio_util::codec::Framed;
+
+use super::{Authenticator, BindMode, Config, Error, Handler, Reply, Sessions};
+use crate::smpp::{
+    codec::{self, SmppCodec},
+    pdu::{
+        command::{Bind, BindResp, DeliverSm, DeliverSmResp, EnquireLinkResp, Id, OutBind, Status, Unbind, UnbindResp},
+        typedef::{COctet, Tag, TLV},
+        Header, Pdu,
+    },
+    session::{write_loop, PendingRequests, RequestError, Role, Sequence, State, StateMachine},
+};
+
+/// A connection of an ESME to a [`Server`](super::Server).
+///
+/// Cloning a session yields another handle to the same connection.
+#[derive(Clone)]
+pub struct Session {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    id: u64,
+    outbound: mpsc::UnboundedSender<(Header, Pdu)>,
+    closed: watch::Sender<bool>,
+    sequence: Sequence,
+    pending: Arc<PendingRequests>,
+    state: Mutex<StateMachine>,
+    bind: Mutex<Option<(BindMode, COctet)>>,
+    request_timeout: Duration,
+}
+
+impl Session {
+    /// A number identifying the connection among those accepted by the
+    /// server.
+    #[must_use]
+    pub fn id(&self) -> u64 {
+        self.inner.id
+    }
+
+    /// The *system_id* the ESME bound with, or `None` if it has not bound.
+    #[must_use]
+    pub fn system_id(&self) -> Option<COctet> {
+        self.inner
+            .bind
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|(_, system_id)| system_id.clone())
+    }
+
+    /// The mode the ESME bound in, or `None` if it has not bound.
+    #[must_use]
+    pub fn mode(&self) -> Option<BindMode> {
+        self.inner.bind.lock().unwrap().as_ref().map(|(mode, _)| *mode)
+    }
+
+    #[must_use]
+    pub fn state(&self) -> State {
+        self.inner.state.lock().unwrap().state()
+    }
+
+    /// Delivers a short message to the ESME and waits for it to be
+    /// acknowledged.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the session is not bound as a
+    /// receiver or transceiver, or the ESME does not acknowledge the message.
+    pub async fn deliver(&self, deliver_sm: DeliverSm) -> Result<DeliverSmResp, Error> {
+        match self.request(Pdu::DeliverSm(deliver_sm)).await? {
+            (_, Pdu::DeliverSmResp(resp)) => Ok(resp),
+            (_, pdu) => Err(RequestError::UnexpectedResponse(pdu.command_id()).into()),
+        }
+    }
+
+    /// Unbinds the session and waits for the ***unbind_resp***, after which
+    /// the connection is closed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the session is not bound or the
+    /// ESME does not answer.
+    pub async fn unbind(&self) -> Result<(), Error> {
+        self.request(Pdu::Unbind(Unbind {})).await.map(|_| ())
+    }
+
+    /// Closes the connection without unbinding.
+    pub fn close(&self) {
+        self.inner.closed.send_replace(true);
+    }
+
+    fn send(&self, header: Header, pdu: Pdu) -> Result<(), Error> {
+        self.inner
+            .state
+            .lock()
+            .unwrap()
+            .outbound(&header)
+            .map_err(Error::Rejected)?;
+        self.inner
+            .outbound
+            .send((header, pdu))
+            .map_err(|_| RequestError::Closed.into())
+    }
+
+    async fn request(&self, pdu: Pdu) -> Result<(Header, Pdu), Error> {
+        let header = Header::new(pdu.command_id(), Status::ESME_ROK, self.inner.sequence.allocate());
+        let response = self.inner.pending.register(&header)?;
+
+        self.send(header, pdu)?;
+
+        Ok(response.wait(self.inner.request_timeout).await?)
+    }
+
+    fn respond(&self, request: &Header, reply: Reply<Pdu>) {
+        let command_id = request.command_id.response();
+        let pdu = reply.body.unwrap_or_else(|| Pdu::Raw {
+            command_id: command_id.clone(),
+            body: vec![],
+        });
+
+        let _ = self.send(Header::new(command_id, reply.status, request.sequence_number), pdu);
+    }
+
+    fn reject(&self, request: &Header, status: Status) {
+        if let Some((header, pdu)) = StateMachine::reject(request, status) {
+            let _ = self.send(header, pdu);
+        }
+    }
+}
+
+pub(super) async fn serve<A, H>(
+    stream: TcpStream,
+    id: u64,
+    outbind: Option<OutBind>,
+    authenticator: Arc<A>,
+    handler: Arc<H>,
+    config: Arc<Config>,
+    sessions: Sessions,
+) where
+    A: Authenticator,
+    H: Handler,
+{
+    let (sink, mut stream) = Framed::new(stream, SmppCodec::new()).split();
+    let (outbound, outbound_rx) = mpsc::unbounded_channel();
+
+    let session = Session {
+        inner: Arc::new(Inner {
+            id,
+            outbound,
+            closed: watch::Sender::new(false),
+            sequence: Sequence::new(),
+            pending: Arc::new(PendingRequests::new()),
+            state: Mutex::new(StateMachine::new(Role::Smsc)),
+            bind: Mutex::new(None),
+            request_timeout: config.request_timeout,
+        }),
+    };
+
+    tokio::spawn(write_loop(sink, outbound_rx, session.inner.closed.subscribe()));
+
+    if let Some(outbind) = outbind {
+        let header = Header::new(Id::Outbind, Status::ESME_ROK, session.inner.sequence.allocate());
+        let _ = session.send(header, Pdu::Outbind(outbind));
+    }
+
+    let mut closed = session.inner.closed.subscribe();
+    // After a decoding error the stream yields `None` once, then resumes.
+    let mut errored = false;
+    loop {
+        let item = tokio::select! {
+            item = stream.next() => item,
+            _ = closed.wait_for(|closed| *closed) => break,
+        };
+
+        let (header, pdu) = match item {
+            Some(Ok(item)) => item,
+            // The body is malformed but the header tells whom to answer.
+            Some(Err(codec::Error::Body(header, e))) => {
+                errored = true;
+                if header.command_id.is_response() {
+                    let malformed = RequestError::Malformed(header.command_id.clone());
+                    session.inner.pending.fail(&header, malformed);
+                } else {
+                    session.reject(&header, e.command_status());
+                }
+                continue;
+            }
+            None if std::mem::take(&mut errored) => continue,
+            _ => break,
+        };
+
+        let checked = session.inner.state.lock().unwrap().inbound(&header);
+        if let Err(status) = checked {
+            session.reject(&header, status);
+            continue;
+        }
+
+        if header.command_id.is_response() {
+            let unbound = matches!(pdu, Pdu::UnbindResp(_));
+            let _ = session.inner.pending.resolve(header, pdu);
+
+            if unbound {
+                break;
+            }
+
+            continue;
+        }
+
+        match pdu {
+            Pdu::BindTransmitter(bind) => {
+                accept(
+                    &session,
+                    &*authenticator,
+                    &config,
+                    &sessions,
+                    &header,
+                    BindMode::Transmitter,
+                    bind,
+                )
+                .await;
+            }
+            Pdu::BindReceiver(bind) => {
+                accept(
+                    &session,
+                    &*authenticator,
+                    &config,
+                    &sessions,
+                    &header,
+                    BindMode::Receiver,
+                    bind,
+                )
+                .await;
+            }
+            Pdu::BindTransceiver(bind) => {
+                accept(
+                    &session,
+                    &*authenticator,
+                    &config,
+                    &sessions,
+                    &header,
+                    BindMode::Transceiver,
+                    bind,
+                )
+                .await;
+            }
+            Pdu::EnquireLink(_) => session.respond(&header, Reply::ok(Pdu::EnquireLinkResp(EnquireLinkResp {}))),
+            Pdu::Unbind(_) => {
+                session.respond(&header, Reply::ok(Pdu::UnbindResp(UnbindResp {})));
+                break;
+            }
+            Pdu::SubmitSm(body) => dispatch(&session, &handler, header, body, H::submit_sm, Pdu::SubmitSmResp),
+            Pdu::SubmitMulti(body) => {
+                dispatch(&session, &handler, header, body, H::submit_multi, Pdu::SubmitMultiResp);
+            }
+            Pdu::DataSm(body) => dispatch(&session, &handler, header, body, H::data_sm, Pdu::DataSmResp),
+            Pdu::QuerySm(body) => dispatch(&session, &handler, header, body, H::query_sm, Pdu::QuerySmResp),
+            Pdu::CancelSm(body) => dispatch(&session, &handler, header, body, H::cancel_sm, Pdu::CancelSmResp),
+            Pdu::ReplaceSm(body) => dispatch(&session, &handler, header, body, H::replace_sm, Pdu::ReplaceSmResp),
+            _ => session.reject(&header, Status::ESME_RINVCMDID),
+        }
+    }
+
+    sessions.remove(id);
+    session.close();
+    session.inner.state.lock().unwrap().close();
+    // Dropping the waiters wakes them up with `RequestError::Closed`.
+    session.inner.pending.close();
+}
+
+async fn accept<A: Authenticator>(
+    session: &Session,
+    authenticator: &A,
+    config: &Config,
+    sessions: &Sessions,
+    header: &Header,
+    mode: BindMode,
+    bind: Bind,
+) {
+    if let Err(status) = authenticator.authenticate(mode, &bind).await {
+        session.reject(header, status);
+        return;
+    }
+
+    *session.inner.bind.lock().unwrap() = Some((mode, bind.system_id));
+
+    let bind_resp = BindResp {
+        system_id: config.system_id.clone(),
+        sc_interface_version: Some(TLV {
+            tag: Tag::ScInterfaceVersion,
+            len: 1,
+            val: vec![config.interface_version],
+        }),
+    };
+    // Registered before the ESME learns that it is bound.
+    sessions.insert(session.clone());
+    session.respond(header, Reply::ok(mode.response(bind_resp)));
+}
+
+/// Passes a request to its handler in a task of its own and sends the reply.
+fn dispatch<H, B, T>(
+    session: &Session,
+    handler: &Arc<H>,
+    header: Header,
+    body: B,
+    handle: for<'a> fn(&'a H, &'a Session, B) -> BoxFuture<'a, Reply<T>>,
+    wrap: fn(T) -> Pdu,
+) where
+    H: Handler,
+    B: Send + 'static,
+    T: Send + 'static,
+{
+    let session = session.clone();
+    let handler = handler.clone();
+
+    tokio::spawn(async move {
+        let reply = handle(&handler, &session, body).await;
+        session.respond(&header, reply.map(wrap));
+    });
+}
@@ -0,0 +1,41 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use futures_util::{Sink, SinkExt};
+use tokio::sync::{mpsc, watch};
+
+use crate::smpp::{
+    codec,
+    pdu::{Header, Pdu},
+};
+
+/// Writes the PDUs queued on `rx` to `sink` until the queue is dropped, the
+/// sink fails or `closed` becomes `true`, then closes the sink.
+pub(crate) async fn write_loop<S>(
+    mut sink: S,
+    mut rx: mpsc::UnboundedReceiver<(Header, Pdu)>,
+    mut closed: watch::Receiver<bool>,
+) where
+    S: Sink<(Header, Pdu), Error = codec::Error> + Unpin,
+{
+    loop {
+        // PDUs queued before the session was closed, such as a final
+        // unbind_resp, are still written.
+        let item = tokio::select! {
+            biased;
+            item = rx.recv() => item,
+            _ = closed.wait_for(|closed| *closed) => None,
+        };
+
+        match item {
+            Some(item) => {
+                if sink.send(item).await.is_err() {
+                    break;
+                }
+            }
+            None => break,
+        }
+    }
+
+    let _ = sink.close().await;
+}
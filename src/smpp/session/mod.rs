@@ -0,0 +1,28 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+//! Building blocks of an SMPP session, shared by both ends of a connection.
+//!
+//! [`Sequence`] allocates the *sequence_number* of each request,
+//! [`PendingRequests`] matches every response back to the request it answers,
+//! and [`Window`] bounds the number of requests awaiting a response.
+//! [`StateMachine`] enforces which PDUs may be exchanged in each state of the
+//! session.
+
+#[cfg(any(feature = "client", feature = "server"))]
+pub(crate) use link::write_loop;
+#[cfg(any(feature = "client", feature = "server"))]
+pub use pending::{PendingRequests, RequestError, Response};
+pub use sequence::Sequence;
+pub use state::{BindMode, Role, State, StateMachine};
+#[cfg(feature = "client")]
+pub use window::{Slot, Window};
+
+#[cfg(any(feature = "client", feature = "server"))]
+mod link;
+#[cfg(any(feature = "client", feature = "server"))]
+mod pending;
+mod sequence;
+mod state;
+#[cfg(feature = "client")]
+mod window;
@@ -0,0 +1,189 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::sync::oneshot;
+
+use crate::smpp::pdu::{
+    command::{Id, Status},
+    Header, Pdu,
+};
+
+/// What a request in flight is resolved with: the response, or the reason
+/// there is none.
+type Outcome = Result<(Header, Pdu), RequestError>;
+
+/// The requests of a session that are still waiting for a response, keyed by
+/// *sequence_number*.
+#[derive(Debug, Default)]
+pub struct PendingRequests {
+    waiters: Mutex<HashMap<u32, oneshot::Sender<Outcome>>>,
+}
+
+impl PendingRequests {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the request described by `header` as in flight. The returned
+    /// [`Response`] resolves once a response with the same *sequence_number*
+    /// is [resolved](PendingRequests::resolve).
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`RequestError::InFlight`] if a request with
+    /// the same *sequence_number* is still waiting for its response.
+    pub fn register(self: &Arc<Self>, header: &Header) -> Result<Response, RequestError> {
+        let (tx, rx) = oneshot::channel();
+
+        match self.waiters.lock().unwrap().entry(header.sequence_number) {
+            Entry::Occupied(_) => return Err(RequestError::InFlight(header.sequence_number)),
+            Entry::Vacant(entry) => entry.insert(tx),
+        };
+
+        Ok(Response {
+            seq: header.sequence_number,
+            command_id: header.command_id.clone(),
+            rx: Some(rx),
+            pending: self.clone(),
+        })
+    }
+
+    /// Hands a response PDU to the request waiting for it. The PDU is given
+    /// back if no request with its *sequence_number* is in flight.
+    pub fn resolve(&self, header: Header, pdu: Pdu) -> Option<(Header, Pdu)> {
+        let tx = self.waiters.lock().unwrap().remove(&header.sequence_number);
+
+        match tx {
+            Some(tx) => tx.send(Ok((header, pdu))).err().and_then(Result::ok),
+            None => Some((header, pdu)),
+        }
+    }
+
+    /// Fails the request waiting for the response described by `header` with
+    /// `e`, e.g. when the body of the response cannot be decoded. Returns
+    /// `false` if no request with its *sequence_number* is in flight.
+    pub fn fail(&self, header: &Header, e: RequestError) -> bool {
+        let tx = self.waiters.lock().unwrap().remove(&header.sequence_number);
+
+        tx.is_some_and(|tx| tx.send(Err(e)).is_ok())
+    }
+
+    /// The number of requests in flight.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.waiters.lock().unwrap().len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fails every request in flight with [`RequestError::Closed`].
+    pub fn close(&self) {
+        self.waiters.lock().unwrap().clear();
+    }
+
+    fn remove(&self, seq: u32) {
+        self.waiters.lock().unwrap().remove(&seq);
+    }
+}
+
+/// The pending response to a request registered with
+/// [`PendingRequests::register`].
+///
+/// Dropping it before the response arrives withdraws the request, so that a
+/// late response is handed back by [`PendingRequests::resolve`].
+#[derive(Debug)]
+pub struct Response {
+    seq: u32,
+    command_id: Id,
+    rx: Option<oneshot::Receiver<Outcome>>,
+    pending: Arc<PendingRequests>,
+}
+
+impl Response {
+    /// The *sequence_number* of the request.
+    #[must_use]
+    pub const fn sequence_number(&self) -> u32 {
+        self.seq
+    }
+
+    /// Waits at most `timeout` for the response.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the peer answers with a
+    /// ***generic_nack***, a non-zero *command_status*, a PDU other than the
+    /// response to the request or one that cannot be decoded, or if no
+    /// response arrives in time.
+    pub async fn wait(mut self, timeout: Duration) -> Result<(Header, Pdu), RequestError> {
+        let rx = self.rx.take().ok_or(RequestError::Closed)?;
+
+        let (header, pdu) = match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(Ok(resp))) => resp,
+            Ok(Ok(Err(e))) => return Err(e),
+            Ok(Err(_)) => return Err(RequestError::Closed),
+            Err(_) => return Err(RequestError::Timeout),
+        };
+
+        if header.command_id == Id::GenericNack {
+            Err(RequestError::Nack(header.command_status))
+        } else if header.command_id != self.command_id.response() {
+            Err(RequestError::UnexpectedResponse(header.command_id))
+        } else if header.command_status != Status::ESME_ROK {
+            Err(RequestError::Status(header.command_status))
+        } else {
+            Ok((header, pdu))
+        }
+    }
+}
+
+impl Drop for Response {
+    fn drop(&mut self) {
+        self.pending.remove(self.seq);
+    }
+}
+
+/// The ways in which a request may fail to get a positive response.
+#[derive(Clone, Debug)]
+pub enum RequestError {
+    /// The peer responded with a non-zero *command_status*.
+    Status(Status),
+    /// The peer responded with a ***generic_nack***.
+    Nack(Status),
+    /// The peer responded with a PDU other than the response to the request.
+    UnexpectedResponse(Id),
+    /// The peer responded with a PDU whose body cannot be decoded.
+    Malformed(Id),
+    /// No response arrived in time.
+    Timeout,
+    /// The session was closed before the response arrived.
+    Closed,
+    /// A request with the same *sequence_number* is still in flight.
+    InFlight(u32),
+}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Status(status) => write!(f, "request failed with {:?}", status),
+            Self::Nack(status) => write!(f, "request rejected by generic_nack with {:?}", status),
+            Self::UnexpectedResponse(id) => write!(f, "unexpected response {:?}", id),
+            Self::Malformed(id) => write!(f, "malformed response {:?}", id),
+            Self::Timeout => write!(f, "request timed out"),
+            Self::Closed => write!(f, "session closed"),
+            Self::InFlight(seq) => write!(f, "sequence_number {} already in flight", seq),
+        }
+    }
+}
+
+impl std::error::Error for RequestError {}
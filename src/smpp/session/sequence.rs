@@ -0,0 +1,56 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Allocates *sequence_number* values for requests originated by one end of a
+/// session.
+///
+/// Values are allocated in the range 0x00000001 to 0x7FFFFFFF and wrap around
+/// to 0x00000001 once the range is exhausted.
+#[derive(Debug)]
+pub struct Sequence {
+    next: AtomicU32,
+}
+
+impl Sequence {
+    /// The largest *sequence_number* allowed.
+    pub const MAX: u32 = 0x7FFFFFFF;
+    /// The smallest *sequence_number* allowed.
+    pub const MIN: u32 = 0x00000001;
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::starting_at(Self::MIN)
+    }
+
+    /// Creates an allocator whose first value is `seq`. Values outside the
+    /// allowed range start from [`Sequence::MIN`] instead.
+    #[must_use]
+    pub const fn starting_at(seq: u32) -> Self {
+        let seq = if seq < Self::MIN || seq > Self::MAX {
+            Self::MIN
+        } else {
+            seq
+        };
+
+        Self {
+            next: AtomicU32::new(seq),
+        }
+    }
+
+    /// Allocates the next *sequence_number*.
+    pub fn allocate(&self) -> u32 {
+        let step = |seq: u32| Some(if seq >= Self::MAX { Self::MIN } else { seq + 1 });
+
+        match self.next.fetch_update(Ordering::Relaxed, Ordering::Relaxed, step) {
+            Ok(seq) | Err(seq) => seq,
+        }
+    }
+}
+
+impl Default for Sequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use crate::smpp::pdu::{
+    command::{Bind, BindResp, Id, Status},
+    Header, Pdu,
+};
+
+/// The mode in which an ESME binds to the SMSC (v5: MC).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindMode {
+    Transmitter,
+    Receiver,
+    Transceiver,
+}
+
+impl BindMode {
+    /// The state of a session bound in this mode.
+    #[must_use]
+    pub const fn state(self) -> State {
+        match self {
+            Self::Transmitter => State::BoundTx,
+            Self::Receiver => State::BoundRx,
+            Self::Transceiver => State::BoundTrx,
+        }
+    }
+
+    /// Wraps a bind request in the PDU for this mode.
+    #[must_use]
+    pub const fn request(self, bind: Bind) -> Pdu {
+        match self {
+            Self::Transmitter => Pdu::BindTransmitter(bind),
+            Self::Receiver => Pdu::BindReceiver(bind),
+            Self::Transceiver => Pdu::BindTransceiver(bind),
+        }
+    }
+
+    /// Wraps a bind response in the PDU for this mode.
+    #[must_use]
+    pub const fn response(self, bind_resp: BindResp) -> Pdu {
+        match self {
+            Self::Transmitter => Pdu::BindTransmitterResp(bind_resp),
+            Self::Receiver => Pdu::BindReceiverResp(bind_resp),
+            Self::Transceiver => Pdu::BindTransceiverResp(bind_resp),
+        }
+    }
+}
+
+/// The end of the session a [`StateMachine`] runs on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Esme,
+    /// The SMSC (v5: MC).
+    Smsc,
+}
+
+impl Role {
+    const fn peer(self) -> Self {
+        match self {
+            Self::Esme => Self::Smsc,
+            Self::Smsc => Self::Esme,
+        }
+    }
+}
+
+/// The states of an SMPP session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    /// The connection is established but no bind has succeeded yet.
+    Open,
+    /// Bound as a transmitter.
+    BoundTx,
+    /// Bound as a receiver.
+    BoundRx,
+    /// Bound as a transceiver.
+    BoundTrx,
+    /// An ***unbind*** has been issued; only responses may still be
+    /// exchanged.
+    Unbound,
+    /// The connection is closed.
+    Closed,
+}
+
+impl State {
+    /// Returns `true` in any of the bound states.
+    #[must_use]
+    pub const fn is_bound(self) -> bool {
+        matches!(self, Self::BoundTx | Self::BoundRx | Self::BoundTrx)
+    }
+
+    /// Returns `true` if the ESME may submit messages in this state.
+    #[must_use]
+    pub const fn can_transmit(self) -> bool {
+        matches!(self, Self::BoundTx | Self::BoundTrx)
+    }
+
+    /// Returns `true` if the SMSC (v5: MC) may deliver messages in this
+    /// state.
+    #[must_use]
+    pub const fn can_receive(self) -> bool {
+        matches!(self, Self::BoundRx | Self::BoundTrx)
+    }
+}
+
+/// Tracks the state of one end of an SMPP session and checks every PDU sent or
+/// received against it.
+///
+/// The state machine performs no I/O. Call [`outbound`](StateMachine::outbound)
+/// before sending a PDU and [`inbound`](StateMachine::inbound) after receiving
+/// one; a PDU that is not allowed is rejected with the *command_status* to
+/// report, and leaves the state unchanged.
+///
+/// # Note:
+///
+/// Responses are allowed in every state but [`State::Closed`]. Matching them
+/// to the requests they answer is left to
+/// [`PendingRequests`](super::PendingRequests).
+#[derive(Clone, Debug)]
+pub struct StateMachine {
+    role: Role,
+    state: State,
+}
+
+impl StateMachine {
+    /// Creates the state machine of a freshly opened connection.
+    #[must_use]
+    pub const fn new(role: Role) -> Self {
+        Self {
+            role,
+            state: State::Open,
+        }
+    }
+
+    #[must_use]
+    pub const fn role(&self) -> Role {
+        self.role
+    }
+
+    #[must_use]
+    pub const fn state(&self) -> State {
+        self.state
+    }
+
+    /// Checks a PDU about to be sent and advances the state.
+    ///
+    /// # Errors
+    ///
+    /// This function will return the *command_status* describing why the PDU
+    /// may not be sent in the current state.
+    pub fn outbound(&mut self, header: &Header) -> Result<(), Status> {
+        self.advance(self.role, header)
+    }
+
+    /// Checks a PDU received from the peer and advances the state.
+    ///
+    /// # Errors
+    ///
+    /// This function will return the *command_status* to report to the peer,
+    /// see [`StateMachine::reject`].
+    pub fn inbound(&mut self, header: &Header) -> Result<(), Status> {
+        self.advance(self.role.peer(), header)
+    }
+
+    /// Marks the connection as closed.
+    pub fn close(&mut self) {
+        self.state = State::Closed;
+    }
+
+    /// Builds the negative response to a request that was rejected with
+    /// `status`. Returns `None` if `header` is not a request, as responses are
+    /// never answered.
+    ///
+    /// A PDU with an unknown *command_id* is answered with a
+    /// ***generic_nack***; any other request with its own response carrying
+    /// no body.
+    #[must_use]
+    pub fn reject(header: &Header, status: Status) -> Option<(Header, Pdu)> {
+        let command_id = match &header.command_id {
+            id if id.is_response() => return None,
+            Id::Reserved(_) => Id::GenericNack,
+            id => id.response(),
+        };
+
+        Some((
+            Header::new(command_id.clone(), status, header.sequence_number),
+            Pdu::Raw {
+                command_id,
+                body: vec![],
+            },
+        ))
+    }
+
+    fn advance(&mut self, sender: Role, header: &Header) -> Result<(), Status> {
+        self.check(sender, &header.command_id)?;
+
+        let accepted = matches!(header.command_status, Status::ESME_ROK);
+        self.state = match (&header.command_id, self.state) {
+            (Id::BindTransmitterResp, State::Open) if accepted => BindMode::Transmitter.state(),
+            (Id::BindReceiverResp, State::Open) if accepted => BindMode::Receiver.state(),
+            (Id::BindTransceiverResp, State::Open) if accepted => BindMode::Transceiver.state(),
+            (Id::Unbind, _) => State::Unbound,
+            (_, state) => state,
+        };
+
+        Ok(())
+    }
+
+    fn check(&self, sender: Role, id: &Id) -> Result<(), Status> {
+        let state = self.state;
+        if state == State::Closed {
+            return Err(Status::ESME_RINVBNDSTS);
+        }
+
+        if let Id::Reserved(_) = id {
+            return Err(Status::ESME_RINVCMDID);
+        }
+
+        if id.is_response() {
+            return Ok(());
+        }
+
+        let allowed = match (sender, id) {
+            (Role::Esme, Id::BindTransmitter | Id::BindReceiver | Id::BindTransceiver) => {
+                return match state {
+                    State::Open => Ok(()),
+                    State::BoundTx | State::BoundRx | State::BoundTrx => Err(Status::ESME_RALYBND),
+                    State::Unbound | State::Closed => Err(Status::ESME_RINVBNDSTS),
+                };
+            }
+            (Role::Smsc, Id::Outbind) => state == State::Open,
+            (_, Id::EnquireLink) => state == State::Open || state.is_bound(),
+            (_, Id::Unbind) => state.is_bound(),
+            (Role::Esme, Id::SubmitSm | Id::SubmitMulti | Id::DataSm | Id::QuerySm | Id::CancelSm | Id::ReplaceSm) => {
+                state.can_transmit()
+            }
+            #[cfg(feature = "v5")]
+            (Role::Esme, Id::BroadcastSm | Id::QueryBroadcastSm | Id::CancelBroadcastSm) => state.can_transmit(),
+            (Role::Smsc, Id::DeliverSm | Id::DataSm | Id::AlertNotification) => state.can_receive(),
+            _ => false,
+        };
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(Status::ESME_RINVBNDSTS)
+        }
+    }
+}
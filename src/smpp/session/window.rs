@@ -0,0 +1,118 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+use std::{sync::Mutex, time::Duration};
+
+use tokio::{
+    sync::{Semaphore, SemaphorePermit},
+    time::{self, Instant},
+};
+
+use crate::smpp::pdu::command::Status;
+
+/// Limits the number of requests in flight, and holds new requests back for a
+/// while after the peer reports that it is overloaded.
+///
+/// A request takes a [`Slot`] before it is sent and keeps it until its
+/// response, be it the matching response or a ***generic_nack***, arrives.
+/// Each *command_status* received is then [recorded](Window::record): an
+/// `ESME_RTHROTTLED` or `ESME_RMSGQFUL` starts a backoff period, which doubles
+/// with each further one up to a maximum and is cleared by an `ESME_ROK`.
+#[derive(Debug)]
+pub struct Window {
+    slots: Semaphore,
+    size: usize,
+    backoff: Duration,
+    max_backoff: Duration,
+    state: Mutex<Backoff>,
+}
+
+#[derive(Debug, Default)]
+struct Backoff {
+    until: Option<Instant>,
+    failures: u32,
+}
+
+impl Window {
+    /// Creates a window of `size` slots, which is at least one. The backoff
+    /// period starts at `backoff` and is at most `max_backoff`.
+    #[must_use]
+    pub fn new(size: usize, backoff: Duration, max_backoff: Duration) -> Self {
+        let size = size.clamp(1, Semaphore::MAX_PERMITS);
+
+        Self {
+            slots: Semaphore::new(size),
+            size,
+            backoff,
+            max_backoff,
+            state: Mutex::new(Backoff::default()),
+        }
+    }
+
+    /// The number of slots in the window.
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The number of slots taken.
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.size - self.slots.available_permits()
+    }
+
+    /// The end of the current backoff period, if any.
+    #[must_use]
+    pub fn backoff_until(&self) -> Option<Instant> {
+        self.state.lock().unwrap().until.filter(|until| *until > Instant::now())
+    }
+
+    /// Waits for the backoff period to end and a slot to be free.
+    pub async fn acquire(&self) -> Slot<'_> {
+        while let Some(until) = self.backoff_until() {
+            time::sleep_until(until).await;
+        }
+
+        // The semaphore is never closed.
+        let permit = self.slots.acquire().await.unwrap();
+
+        Slot { _permit: permit }
+    }
+
+    /// Takes a slot without waiting. Returns `None` if the window is full or
+    /// a backoff period is in progress.
+    #[must_use]
+    pub fn try_acquire(&self) -> Option<Slot<'_>> {
+        if self.backoff_until().is_some() {
+            return None;
+        }
+
+        self.slots.try_acquire().ok().map(|permit| Slot { _permit: permit })
+    }
+
+    /// Records the *command_status* of a response, starting or clearing the
+    /// backoff period.
+    pub fn record(&self, status: &Status) {
+        let mut state = self.state.lock().unwrap();
+
+        match status {
+            Status::ESME_RTHROTTLED | Status::ESME_RMSGQFUL => {
+                let delay = self
+                    .backoff
+                    .saturating_mul(1 << state.failures.min(16))
+                    .min(self.max_backoff);
+
+                state.until = Some(Instant::now() + delay);
+                state.failures = state.failures.saturating_add(1);
+            }
+            Status::ESME_ROK => *state = Backoff::default(),
+            _ => {}
+        }
+    }
+}
+
+/// A slot in a [`Window`], freed when dropped.
+#[derive(Debug)]
+pub struct Slot<'a> {
+    _permit: SemaphorePermit<'a>,
+}
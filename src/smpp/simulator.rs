@@ -0,0 +1,327 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+//! An in-process SMSC (v5: MC) for testing ESME code without a live carrier.
+//!
+//! [`Simulator`] listens on an ephemeral port of the loopback interface,
+//! accepts binds with the configured credentials and acknowledges every
+//! ***submit_sm*** with a generated *message_id*. If the message asks for a
+//! delivery receipt, one is delivered to the submitting ESME after
+//! [`Config::receipt_delay`]. Errors may be [injected](Simulator::inject_error)
+//! to exercise the error paths of the ESME.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::Duration,
+};
+
+use chrono::Timelike;
+use futures_util::future::BoxFuture;
+use tokio::{net::ToSocketAddrs, task::JoinHandle};
+
+use super::{
+    pdu::{
+        command::{Bind, DeliverSm, OutBind, Status, SubmitSm, SubmitSmResp},
+        typedef::{
+            decode_text, COctet, DataCoding, DataCodingScheme, DeliveryReceipt, EsmClass, EsmClassMessageType,
+            MessageState, Tag, TLV,
+        },
+    },
+    server::{self, Authenticator, BindMode, Handler, Reply, Server, Session, Sessions},
+};
+
+/// Settings of a [`Simulator`].
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// The *system_id* and password pairs accepted in bind requests.
+    pub credentials: HashMap<String, String>,
+    /// How long after acknowledging a message its delivery receipt is sent.
+    /// `None` disables delivery receipts.
+    pub receipt_delay: Option<Duration>,
+    pub server: server::Config,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            credentials: HashMap::from([("test".to_string(), "test".to_string())]),
+            receipt_delay: Some(Duration::from_millis(100)),
+            server: server::Config {
+                system_id: COctet::new("SIMULATOR").unwrap(),
+                ..server::Config::default()
+            },
+        }
+    }
+}
+
+/// A running SMSC (v5: MC) simulator. It stops when dropped.
+pub struct Simulator {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    server: Arc<Server<SimulatorAuthenticator, SimulatorHandler>>,
+    task: JoinHandle<()>,
+}
+
+struct Shared {
+    credentials: HashMap<String, String>,
+    receipt_delay: Option<Duration>,
+    next_message_id: AtomicU64,
+    errors: Mutex<VecDeque<Status>>,
+    submitted: Mutex<Vec<(String, SubmitSm)>>,
+    sessions: OnceLock<Sessions>,
+}
+
+impl Simulator {
+    /// Starts a simulator listening on an ephemeral port of `127.0.0.1`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the listener cannot be bound.
+    pub async fn start(config: Config) -> io::Result<Self> {
+        let shared = Arc::new(Shared {
+            credentials: config.credentials,
+            receipt_delay: config.receipt_delay,
+            next_message_id: AtomicU64::new(1),
+            errors: Mutex::new(VecDeque::new()),
+            submitted: Mutex::new(vec![]),
+            sessions: OnceLock::new(),
+        });
+
+        let server = Server::bind(
+            "127.0.0.1:0",
+            SimulatorAuthenticator(shared.clone()),
+            SimulatorHandler(shared.clone()),
+            config.server,
+        )
+        .await?;
+
+        let addr = server.local_addr()?;
+        let _ = shared.sessions.set(server.sessions());
+
+        let server = Arc::new(server);
+        let task = tokio::spawn({
+            let server = server.clone();
+            async move { server.run().await }
+        });
+
+        Ok(Self {
+            addr,
+            shared,
+            server,
+            task,
+        })
+    }
+
+    /// The address to connect to.
+    #[must_use]
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The sessions currently bound to the simulator.
+    #[must_use]
+    pub fn sessions(&self) -> Sessions {
+        self.shared.sessions.get().cloned().unwrap_or_default()
+    }
+
+    /// Opens a session to an ESME listening at `addr`, e.g. with
+    /// `EsmeClient::accept_outbind`, by sending it `outbind`. The session is
+    /// served like any other once the ESME binds as a receiver.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the connection cannot be
+    /// established.
+    pub async fn outbind<A: ToSocketAddrs>(&self, addr: A, outbind: OutBind) -> io::Result<()> {
+        self.server.outbind(addr, outbind).await
+    }
+
+    /// Answers the next ***submit_sm*** with `status` instead of accepting it,
+    /// e.g. `ESME_RTHROTTLED`. Errors injected repeatedly are used in order,
+    /// one per message.
+    pub fn inject_error(&self, status: Status) {
+        self.shared.errors.lock().unwrap().push_back(status);
+    }
+
+    /// The messages accepted so far, together with the *message_id* each was
+    /// acknowledged with.
+    #[must_use]
+    pub fn submitted(&self) -> Vec<(String, SubmitSm)> {
+        self.shared.submitted.lock().unwrap().clone()
+    }
+}
+
+impl Drop for Simulator {
+    fn drop(&mut self) {
+        self.task.abort();
+        for session in self.sessions().list() {
+            session.close();
+        }
+    }
+}
+
+struct SimulatorAuthenticator(Arc<Shared>);
+
+impl Authenticator for SimulatorAuthenticator {
+    fn authenticate<'a>(&'a self, _mode: BindMode, bind: &'a Bind) -> BoxFuture<'a, Result<(), Status>> {
+        let system_id = bind.system_id.as_ref().to_string_lossy();
+        let password = bind.password.as_ref().to_string_lossy();
+
+        let result = match self.0.credentials.get(system_id.as_ref()) {
+            None => Err(Status::ESME_RINVSYSID),
+            Some(expected) if *expected != password => Err(Status::ESME_RINVPASWD),
+            Some(_) => Ok(()),
+        };
+
+        Box::pin(async { result })
+    }
+}
+
+struct SimulatorHandler(Arc<Shared>);
+
+impl Handler for SimulatorHandler {
+    fn submit_sm<'a>(&'a self, session: &'a Session, submit_sm: SubmitSm) -> BoxFuture<'a, Reply<SubmitSmResp>> {
+        Box::pin(async move {
+            if let Some(status) = self.0.errors.lock().unwrap().pop_front() {
+                return Reply::error(status);
+            }
+
+            let message_id = format!("{:010X}", self.0.next_message_id.fetch_add(1, Ordering::Relaxed));
+
+            // Only a receipt on final delivery outcome is supported, which is
+            // what registered_delivery 0bxxxxxx01 asks for.
+            if let Some(delay) = self
+                .0
+                .receipt_delay
+                .filter(|_| submit_sm.registered_delivery & 0x03 == 0x01)
+            {
+                let receipt = receipt(&message_id, &submit_sm);
+                let target = receipt_target(session, self.0.sessions.get());
+
+                tokio::spawn(async move {
+                    tokio::time::sleep(delay).await;
+                    if let Some(target) = target {
+                        let _ = target.deliver(receipt).await;
+                    }
+                });
+            }
+
+            self.0.submitted.lock().unwrap().push((message_id.clone(), submit_sm));
+
+            Reply::ok(SubmitSmResp {
+                message_id: COctet::new(message_id).unwrap(),
+
+                #[cfg(feature = "v5")]
+                msg_submission_resp_tlv: vec![],
+            })
+        })
+    }
+}
+
+/// Picks the session to deliver the receipt of a message to: the submitting
+/// session if bound as a transceiver, or else a receiver bound with the same
+/// *system_id*.
+fn receipt_target(session: &Session, sessions: Option<&Sessions>) -> Option<Session> {
+    if session.state().can_receive() {
+        return Some(session.clone());
+    }
+
+    let system_id = session.system_id()?;
+    sessions?
+        .receivers()
+        .into_iter()
+        .find(|receiver| receiver.system_id().is_some_and(|id| id.as_ref() == system_id.as_ref()))
+}
+
+/// The first 20 characters of a message, or nothing if it does not carry
+/// text. As the receipt is sent in ASCII, other characters are replaced with
+/// `?`.
+fn receipt_text(submit_sm: &SubmitSm) -> String {
+    DataCodingScheme::from(submit_sm.data_coding)
+        .text_coding()
+        .and_then(|data_coding| decode_text(data_coding, &submit_sm.short_message).ok())
+        .map(|text| {
+            text.chars()
+                .take(20)
+                .map(|c| if c.is_ascii() { c } else { '?' })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn receipt(message_id: &str, submit_sm: &SubmitSm) -> DeliverSm {
+    let now = chrono::Local::now().naive_local().with_nanosecond(0);
+    let short_message = DeliveryReceipt {
+        id: message_id.to_string(),
+        sub: Some(1),
+        dlvrd: Some(1),
+        submit_date: now,
+        done_date: now,
+        stat: MessageState::Delivered,
+        err: None,
+        text: receipt_text(submit_sm),
+    }
+    .to_string()
+    .into_bytes();
+
+    let esm_class = EsmClass {
+        message_mode: Default::default(),
+        message_type: EsmClassMessageType::DeliveryReceipt,
+        ansi41: Default::default(),
+        gsm: Default::default(),
+    };
+
+    let mut receipted_message_id = message_id.as_bytes().to_vec();
+    receipted_message_id.push(0);
+
+    let mut msg_delivery_tlv = vec![
+        TLV {
+            tag: Tag::ReceiptedMessageId,
+            len: u16::try_from(receipted_message_id.len()).expect("a message ID of the simulator"),
+            val: receipted_message_id,
+        },
+        MessageState::Delivered.into(),
+    ];
+
+    // A receipt too long for the short_message is carried in the
+    // message_payload TLV instead.
+    let (sm_length, short_message) = match u8::try_from(short_message.len()) {
+        Ok(len) if len < u8::MAX => (len, short_message),
+        _ => {
+            msg_delivery_tlv.push(TLV {
+                tag: Tag::MessagePayload,
+                len: u16::try_from(short_message.len()).expect("a receipt shorter than 64K"),
+                val: short_message,
+            });
+            (0, vec![])
+        }
+    };
+
+    DeliverSm {
+        service_type: COctet::default(),
+        source_addr_ton: submit_sm.dest_addr_ton,
+        source_addr_npi: submit_sm.dest_addr_npi,
+        source_addr: submit_sm.destination_addr.clone(),
+        dest_addr_ton: submit_sm.source_addr_ton,
+        dest_addr_npi: submit_sm.source_addr_npi,
+        destination_addr: submit_sm.source_addr.clone(),
+        esm_class: esm_class.into(),
+        protocol_id: 0,
+        priority_flag: 0,
+        schedule_delivery_time: COctet::default(),
+        validity_period: COctet::default(),
+        registered_delivery: 0,
+        replace_if_present_flag: 0,
+        data_coding: DataCoding::Ascii.into(),
+        sm_default_msg_id: 0,
+        sm_length,
+        short_message,
+        msg_delivery_tlv,
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate bincode;
+extern crate short_msg;
+
+use short_msg::smpp::prelude::*;
+
+fn config() -> impl bincode::config::Config {
+    bincode::config::standard().with_big_endian().with_fixed_int_encoding()
+}
+
+#[test]
+fn query_sm_round_trip() {
+    let query_sm = QuerySm {
+        message_id: COctet::new("4711").unwrap(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+    };
+
+    let bytes = bincode::encode_to_vec(query_sm, config()).unwrap();
+    assert_eq!(bytes, b"4711\0\x01\x0185212345678\0");
+
+    let (decoded, len) = bincode::decode_from_slice::<QuerySm, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.message_id.as_ref().to_bytes(), b"4711");
+    assert_eq!(decoded.source_addr_ton, 1);
+    assert_eq!(decoded.source_addr_npi, 1);
+    assert_eq!(decoded.source_addr.as_ref().to_bytes(), b"85212345678");
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn query_sm_resp_round_trip() {
+    let query_sm_resp = QuerySmResp {
+        message_id: COctet::new("4711").unwrap(),
+        final_date: COctet::new("000001020000000R").unwrap(),
+        message_state: MessageState::Delivered,
+        error_code: 0,
+
+        #[cfg(feature = "v5")]
+        query_sm_resp_tlv: vec![],
+    };
+
+    let bytes = bincode::encode_to_vec(query_sm_resp, config()).unwrap();
+    assert_eq!(bytes, b"4711\x00000001020000000R\x00\x02\x00");
+
+    let (decoded, len) = bincode::decode_from_slice::<QuerySmResp, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.message_id.as_ref().to_bytes(), b"4711");
+    assert_eq!(decoded.final_date.as_ref().to_bytes(), b"000001020000000R");
+    assert_eq!(decoded.message_state, MessageState::Delivered);
+    assert_eq!(decoded.error_code, 0);
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn query_sm_resp_null_final_date() {
+    let bytes = b"4711\0\0\x01\x00";
+
+    let (decoded, _) = bincode::decode_from_slice::<QuerySmResp, _>(bytes, config()).unwrap();
+    assert!(decoded.final_date.as_ref().to_bytes().is_empty());
+    assert_eq!(decoded.message_state, MessageState::Enroute);
+}
+
+#[test]
+fn query_sm_resp_malformed_final_date() {
+    // The date is kept as is rather than parsed, so a malformed one from the
+    // SMSC cannot fail the decoding.
+    let bytes = b"4711\0AB9999999999000+\0\x02\x00";
+
+    let (decoded, len) = bincode::decode_from_slice::<QuerySmResp, _>(bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.final_date.as_ref().to_bytes(), b"AB9999999999000+");
+    assert_eq!(decoded.message_state, MessageState::Delivered);
+}
+
+#[test]
+fn query_sm_resp_out_of_range_message_state() {
+    let bytes = b"4711\0\0\x2A\x00";
+
+    let (decoded, _) = bincode::decode_from_slice::<QuerySmResp, _>(bytes, config()).unwrap();
+    assert_eq!(decoded.message_state, MessageState::Unknown);
+    assert_eq!(MessageState::try_from(0x2A), Err(0x2A));
+}
+
+#[test]
+fn cancel_sm_round_trip() {
+    let cancel_sm = CancelSm {
+        service_type: COctet::new("").unwrap(),
+        message_id: COctet::new("4711").unwrap(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        dest_addr_ton: 2,
+        dest_addr_npi: 8,
+        destination_addr: COctet::new("85287654321").unwrap(),
+    };
+
+    let bytes = bincode::encode_to_vec(cancel_sm, config()).unwrap();
+    assert_eq!(bytes, b"\x004711\0\x01\x0185212345678\0\x02\x0885287654321\0");
+
+    let (decoded, len) = bincode::decode_from_slice::<CancelSm, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert!(decoded.service_type.as_ref().to_bytes().is_empty());
+    assert_eq!(decoded.message_id.as_ref().to_bytes(), b"4711");
+    assert_eq!(decoded.dest_addr_ton, 2);
+    assert_eq!(decoded.dest_addr_npi, 8);
+    assert_eq!(decoded.destination_addr.as_ref().to_bytes(), b"85287654321");
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn cancel_sm_resp_and_replace_sm_resp_are_empty() {
+    assert!(bincode::encode_to_vec(CancelSmResp {}, config()).unwrap().is_empty());
+    assert!(bincode::encode_to_vec(ReplaceSmResp {}, config()).unwrap().is_empty());
+
+    let (_, len) = bincode::decode_from_slice::<CancelSmResp, _>(&[], config()).unwrap();
+    assert_eq!(len, 0);
+    let (_, len) = bincode::decode_from_slice::<ReplaceSmResp, _>(&[], config()).unwrap();
+    assert_eq!(len, 0);
+}
+
+fn replace_sm(short_message: &[u8]) -> ReplaceSm {
+    ReplaceSm {
+        message_id: COctet::new("4711").unwrap(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        schedule_delivery_time: COctet::default(),
+        validity_period: COctet::new("000001000000000R").unwrap(),
+        registered_delivery: 1,
+        sm_default_msg_id: 0,
+        sm_length: short_message.len() as u8,
+        short_message: short_message.to_vec(),
+
+        #[cfg(feature = "v5")]
+        message_payload: None,
+    }
+}
+
+#[test]
+fn replace_sm_round_trip() {
+    let bytes = bincode::encode_to_vec(replace_sm(b"hello"), config()).unwrap();
+    assert_eq!(
+        bytes,
+        b"4711\0\x01\x0185212345678\0\x00000001000000000R\0\x01\x00\x05hello"
+    );
+
+    let (decoded, len) = bincode::decode_from_slice::<ReplaceSm, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.message_id.as_ref().to_bytes(), b"4711");
+    assert!(decoded.schedule_delivery_time.as_ref().to_bytes().is_empty());
+    assert_eq!(decoded.validity_period.as_ref().to_bytes(), b"000001000000000R");
+    assert_eq!(decoded.registered_delivery, 1);
+    assert_eq!(decoded.sm_length, 5);
+    assert_eq!(decoded.short_message, b"hello");
+    #[cfg(feature = "v5")]
+    assert!(decoded.message_payload.is_none());
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn replace_sm_malformed_time() {
+    // The times are kept as is rather than parsed, so a malformed one from
+    // the peer cannot fail the decoding.
+    let mut replace_sm = replace_sm(b"hello");
+    replace_sm.schedule_delivery_time = COctet::new("99999999999999+Z").unwrap();
+    replace_sm.validity_period = COctet::new("AB").unwrap();
+    let bytes = bincode::encode_to_vec(replace_sm, config()).unwrap();
+
+    let (decoded, len) = bincode::decode_from_slice::<ReplaceSm, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.schedule_delivery_time.as_ref().to_bytes(), b"99999999999999+Z");
+    assert_eq!(decoded.validity_period.as_ref().to_bytes(), b"AB");
+    assert_eq!(decoded.short_message, b"hello");
+}
+
+#[cfg(feature = "v5")]
+#[test]
+fn replace_sm_message_payload_round_trip() {
+    let mut replace_sm = replace_sm(b"");
+    replace_sm.message_payload = Some(TLV {
+        tag: Tag::MessagePayload,
+        len: 5,
+        val: b"hello".to_vec(),
+    });
+
+    let bytes = bincode::encode_to_vec(replace_sm, config()).unwrap();
+    assert!(bytes.ends_with(b"\x01\x00\x00\x04\x24\x00\x05hello"));
+
+    let (decoded, len) = bincode::decode_from_slice::<ReplaceSm, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.sm_length, 0);
+    assert!(decoded.short_message.is_empty());
+    let message_payload = decoded.message_payload.clone().unwrap();
+    assert_eq!(message_payload.tag, Tag::MessagePayload);
+    assert_eq!(message_payload.val, b"hello");
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
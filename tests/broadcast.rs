@@ -0,0 +1,251 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+#![cfg(feature = "v5")]
+
+extern crate bincode;
+extern crate short_msg;
+
+use short_msg::smpp::prelude::*;
+
+fn config() -> impl bincode::config::Config {
+    bincode::config::standard().with_big_endian().with_fixed_int_encoding()
+}
+
+fn tlv(tag: Tag, val: &[u8]) -> TLV {
+    TLV {
+        tag,
+        len: val.len() as u16,
+        val: val.to_vec(),
+    }
+}
+
+/// The fixed fields of [`broadcast_sm`].
+const BROADCAST_SM_FIXED: &[u8] = b"\0\x01\x0185212345678\0\0\0\0\0\0\0\0";
+
+fn broadcast_sm() -> BroadcastSm {
+    BroadcastSm {
+        service_type: COctet::new("").unwrap(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        message_id: COctet::new("").unwrap(),
+        priority_flag: 0,
+        schedule_delivery_time: COctet::default(),
+        validity_period: COctet::default(),
+        replace_if_present_flag: 0,
+        data_coding: 0,
+        sm_default_msg_id: 0,
+        broadcast_area_identifier: vec![
+            tlv(Tag::BroadcastAreaIdentifier, b"\x00area1"),
+            tlv(Tag::BroadcastAreaIdentifier, b"\x00area2"),
+        ],
+        broadcast_content_type: tlv(Tag::BroadcastContentType, b"\x01\x00\x00"),
+        broadcast_rep_num: tlv(Tag::BroadcastRepNum, b"\x00\x03"),
+        broadcast_frequency_interval: tlv(Tag::BroadcastFrequencyInterval, b"\x09\x00\x05"),
+        broadcast_request_optional_tlv: vec![tlv(Tag::MessagePayload, b"hello")],
+    }
+}
+
+fn query_broadcast_sm_resp() -> QueryBroadcastSmResp {
+    QueryBroadcastSmResp {
+        message_id: COctet::new("4711").unwrap(),
+        message_state: MessageState::Enroute.into(),
+        broadcast_area_identifier: vec![tlv(Tag::BroadcastAreaIdentifier, b"\x00area1")],
+        broadcast_area_success: vec![tlv(Tag::BroadcastAreaSuccess, b"\x64")],
+        broadcast_query_response_optional_tlv: vec![],
+    }
+}
+
+#[test]
+fn broadcast_sm_round_trip() {
+    let bytes = bincode::encode_to_vec(broadcast_sm(), config()).unwrap();
+    assert!(bytes.starts_with(BROADCAST_SM_FIXED));
+
+    let (decoded, len) = bincode::decode_from_slice::<BroadcastSm, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.broadcast_area_identifier.len(), 2);
+    assert_eq!(decoded.broadcast_area_identifier[1].val, b"\x00area2");
+    assert_eq!(decoded.broadcast_content_type.val, b"\x01\x00\x00");
+    assert_eq!(decoded.broadcast_rep_num.val, b"\x00\x03");
+    assert_eq!(decoded.broadcast_frequency_interval.val, b"\x09\x00\x05");
+    assert_eq!(decoded.broadcast_request_optional_tlv.len(), 1);
+    assert_eq!(decoded.broadcast_request_optional_tlv[0].tag, Tag::MessagePayload);
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn broadcast_sm_malformed_time() {
+    // The times are kept as is rather than parsed, so a malformed one from
+    // the peer cannot fail the decoding.
+    let broadcast_sm = BroadcastSm {
+        schedule_delivery_time: COctet::new("99999999999999+Z").unwrap(),
+        validity_period: COctet::new("AB").unwrap(),
+        ..broadcast_sm()
+    };
+    let bytes = bincode::encode_to_vec(broadcast_sm, config()).unwrap();
+
+    let (decoded, len) = bincode::decode_from_slice::<BroadcastSm, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.schedule_delivery_time.as_ref().to_bytes(), b"99999999999999+Z");
+    assert_eq!(decoded.validity_period.as_ref().to_bytes(), b"AB");
+}
+
+#[test]
+fn broadcast_sm_mandatory_tlvs_in_any_order() {
+    let mut bytes = BROADCAST_SM_FIXED.to_vec();
+    for t in [
+        tlv(Tag::BroadcastFrequencyInterval, b"\x09\x00\x05"),
+        tlv(Tag::MessagePayload, b"hello"),
+        tlv(Tag::BroadcastAreaIdentifier, b"\x00area1"),
+        tlv(Tag::BroadcastRepNum, b"\x00\x03"),
+        tlv(Tag::BroadcastContentType, b"\x01\x00\x00"),
+    ] {
+        bytes.extend(bincode::encode_to_vec(t, config()).unwrap());
+    }
+
+    let (decoded, _) = bincode::decode_from_slice::<BroadcastSm, _>(&bytes, config()).unwrap();
+    assert_eq!(decoded.broadcast_area_identifier.len(), 1);
+    assert_eq!(decoded.broadcast_content_type.val, b"\x01\x00\x00");
+    assert_eq!(decoded.broadcast_rep_num.val, b"\x00\x03");
+    assert_eq!(decoded.broadcast_frequency_interval.val, b"\x09\x00\x05");
+    assert_eq!(decoded.broadcast_request_optional_tlv.len(), 1);
+}
+
+#[test]
+fn broadcast_sm_missing_mandatory_tlv() {
+    let mut bytes = BROADCAST_SM_FIXED.to_vec();
+    for t in [
+        tlv(Tag::BroadcastAreaIdentifier, b"\x00area1"),
+        tlv(Tag::BroadcastContentType, b"\x01\x00\x00"),
+        tlv(Tag::BroadcastRepNum, b"\x00\x03"),
+    ] {
+        bytes.extend(bincode::encode_to_vec(t, config()).unwrap());
+    }
+    assert!(bincode::decode_from_slice::<BroadcastSm, _>(&bytes, config()).is_err());
+
+    let broadcast_sm = BroadcastSm {
+        broadcast_area_identifier: vec![],
+        ..broadcast_sm()
+    };
+    assert!(bincode::encode_to_vec(broadcast_sm, config()).is_err());
+}
+
+#[test]
+fn broadcast_sm_mismatched_tag() {
+    let mut broadcast_sm = broadcast_sm();
+    broadcast_sm.broadcast_content_type.tag = Tag::BroadcastRepNum;
+    assert!(bincode::encode_to_vec(broadcast_sm, config()).is_err());
+
+    let mut broadcast_sm = self::broadcast_sm();
+    broadcast_sm.broadcast_rep_num.tag = Tag::BroadcastContentType;
+    assert!(bincode::encode_to_vec(broadcast_sm, config()).is_err());
+
+    let mut broadcast_sm = self::broadcast_sm();
+    broadcast_sm.broadcast_frequency_interval.tag = Tag::MessagePayload;
+    assert!(bincode::encode_to_vec(broadcast_sm, config()).is_err());
+
+    let mut broadcast_sm = self::broadcast_sm();
+    broadcast_sm.broadcast_area_identifier[1].tag = Tag::BroadcastAreaSuccess;
+    assert!(bincode::encode_to_vec(broadcast_sm, config()).is_err());
+}
+
+#[test]
+fn broadcast_sm_resp_round_trip() {
+    let broadcast_sm_resp = BroadcastSmResp {
+        message_id: COctet::new("4711").unwrap(),
+        broadcast_response_optional_tlv: vec![tlv(Tag::BroadcastErrorStatus, b"\x00\x00\x00\x00")],
+    };
+
+    let bytes = bincode::encode_to_vec(broadcast_sm_resp, config()).unwrap();
+    assert_eq!(bytes, b"4711\0\x06\x07\x00\x04\x00\x00\x00\x00");
+
+    let (decoded, len) = bincode::decode_from_slice::<BroadcastSmResp, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.message_id.as_ref().to_bytes(), b"4711");
+    assert_eq!(
+        decoded.broadcast_response_optional_tlv[0].tag,
+        Tag::BroadcastErrorStatus
+    );
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn query_broadcast_sm_round_trip() {
+    let query_broadcast_sm = QueryBroadcastSm {
+        message_id: COctet::new("4711").unwrap(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        broadcast_query_optional_tlv: vec![],
+    };
+
+    let bytes = bincode::encode_to_vec(query_broadcast_sm, config()).unwrap();
+    assert_eq!(bytes, b"4711\0\x01\x0185212345678\0");
+
+    let (decoded, len) = bincode::decode_from_slice::<QueryBroadcastSm, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.source_addr.as_ref().to_bytes(), b"85212345678");
+    assert!(decoded.broadcast_query_optional_tlv.is_empty());
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn query_broadcast_sm_resp_round_trip() {
+    let bytes = bincode::encode_to_vec(query_broadcast_sm_resp(), config()).unwrap();
+    assert_eq!(
+        bytes,
+        b"4711\0\x04\x27\x00\x01\x01\x06\x06\x00\x06\x00area1\x06\x08\x00\x01\x64"
+    );
+
+    let (decoded, len) = bincode::decode_from_slice::<QueryBroadcastSmResp, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(
+        MessageState::from_tlv(&decoded.message_state),
+        Some(MessageState::Enroute)
+    );
+    assert_eq!(decoded.broadcast_area_identifier.len(), 1);
+    assert_eq!(decoded.broadcast_area_success[0].val, b"\x64");
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn query_broadcast_sm_resp_mismatched_tag() {
+    let mut query_broadcast_sm_resp = query_broadcast_sm_resp();
+    query_broadcast_sm_resp.message_state.tag = Tag::MessagePayload;
+    assert!(bincode::encode_to_vec(query_broadcast_sm_resp, config()).is_err());
+
+    let mut query_broadcast_sm_resp = self::query_broadcast_sm_resp();
+    query_broadcast_sm_resp.broadcast_area_success[0].tag = Tag::BroadcastAreaIdentifier;
+    assert!(bincode::encode_to_vec(query_broadcast_sm_resp, config()).is_err());
+}
+
+#[test]
+fn cancel_broadcast_sm_round_trip() {
+    let cancel_broadcast_sm = CancelBroadcastSm {
+        service_type: COctet::new("").unwrap(),
+        message_id: COctet::new("4711").unwrap(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        broadcast_cancel_optional_tlv: vec![],
+    };
+
+    let bytes = bincode::encode_to_vec(cancel_broadcast_sm, config()).unwrap();
+    assert_eq!(bytes, b"\x004711\0\x01\x0185212345678\0");
+
+    let (decoded, len) = bincode::decode_from_slice::<CancelBroadcastSm, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.message_id.as_ref().to_bytes(), b"4711");
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn cancel_broadcast_sm_resp_is_empty() {
+    assert!(bincode::encode_to_vec(CancelBroadcastSmResp {}, config())
+        .unwrap()
+        .is_empty());
+
+    let (_, len) = bincode::decode_from_slice::<CancelBroadcastSmResp, _>(&[], config()).unwrap();
+    assert_eq!(len, 0);
+}
@@ -0,0 +1,548 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+#![cfg(feature = "client")]
+
+extern crate futures;
+extern crate short_msg;
+extern crate tokio;
+extern crate tokio_util;
+
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use short_msg::smpp::{
+    client::{Backoff, BindMode, Config, Error, EsmeClient, Event, Supervisor, WindowPolicy},
+    codec::SmppCodec,
+    prelude::*,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt, DuplexStream},
+    net::TcpListener,
+    time,
+};
+use tokio_util::codec::Framed;
+
+type Smsc = Framed<DuplexStream, SmppCodec>;
+
+fn bind() -> Bind {
+    Bind {
+        system_id: COctet::new("esme").unwrap(),
+        password: COctet::new("secret").unwrap(),
+        system_type: COctet::new("").unwrap(),
+        interface_version: 0x34,
+        addr_ton: 0,
+        addr_npi: 0,
+        address_range: COctet::new("").unwrap(),
+    }
+}
+
+fn config() -> Config {
+    Config {
+        request_timeout: Duration::from_secs(5),
+        enquire_link_interval: None,
+        ..Config::default()
+    }
+}
+
+async fn next<T: AsyncRead + Unpin>(smsc: &mut Framed<T, SmppCodec>) -> Option<(Header, Pdu)> {
+    time::timeout(Duration::from_secs(5), smsc.next())
+        .await
+        .expect("no PDU from the client")
+        .map(Result::unwrap)
+}
+
+/// Answers the bind request of the client with `status`.
+async fn answer_bind<T: AsyncRead + AsyncWrite + Unpin>(smsc: &mut Framed<T, SmppCodec>, status: Status) {
+    let (header, pdu) = next(smsc).await.unwrap();
+    assert!(matches!(pdu, Pdu::BindTransceiver(_)));
+
+    let resp = BindResp {
+        system_id: COctet::new("smsc").unwrap(),
+        sc_interface_version: None,
+    };
+    let header = Header::new(Id::BindTransceiverResp, status, header.sequence_number);
+    smsc.send((header, Pdu::BindTransceiverResp(resp))).await.unwrap();
+}
+
+fn submit_sm() -> SubmitSm {
+    SubmitSm {
+        service_type: COctet::default(),
+        source_addr_ton: 0,
+        source_addr_npi: 0,
+        source_addr: COctet::new("esme").unwrap(),
+        dest_addr_ton: 1,
+        dest_addr_npi: 1,
+        destination_addr: COctet::new("85212345678").unwrap(),
+        esm_class: 0,
+        protocol_id: 0,
+        priority_flag: 0,
+        schedule_delivery_time: COctet::default(),
+        validity_period: COctet::default(),
+        registered_delivery: 0,
+        replace_if_present_flag: 0,
+        data_coding: 0,
+        sm_default_msg_id: 0,
+        sm_length: 5,
+        short_message: b"hello".to_vec(),
+        msg_submission_tlv: vec![],
+    }
+}
+
+async fn connect() -> (EsmeClient, Smsc) {
+    connect_with(config()).await
+}
+
+async fn connect_with(config: Config) -> (EsmeClient, Smsc) {
+    let (esme, smsc) = tokio::io::duplex(1024);
+    let mut smsc = Framed::new(smsc, SmppCodec::new());
+
+    let (client, _) = tokio::join!(
+        EsmeClient::bind(esme, BindMode::Transceiver, bind(), config),
+        answer_bind(&mut smsc, Status::ESME_ROK),
+    );
+
+    (client.unwrap(), smsc)
+}
+
+#[tokio::test]
+async fn bind_transceiver() {
+    let (client, _smsc) = connect().await;
+    assert_eq!(client.bind_resp().system_id.as_ref().to_bytes(), b"smsc");
+    assert!(!client.is_closed());
+}
+
+#[tokio::test]
+async fn failed_bind_closes_connection() {
+    let (esme, smsc) = tokio::io::duplex(1024);
+    let mut smsc = Framed::new(smsc, SmppCodec::new());
+
+    let (client, _) = tokio::join!(
+        EsmeClient::bind(esme, BindMode::Transceiver, bind(), config()),
+        answer_bind(&mut smsc, Status::ESME_RBINDFAIL),
+    );
+
+    assert!(matches!(client, Err(Error::Status(Status::ESME_RBINDFAIL))));
+    assert!(next(&mut smsc).await.is_none());
+}
+
+#[tokio::test]
+async fn malformed_body_is_nacked() {
+    let (mut client, mut smsc) = connect().await;
+    let mut events = client.events().unwrap();
+
+    // A deliver_sm whose body ends in the middle of the service_type.
+    let mut bytes = vec![];
+    bytes.extend(19u32.to_be_bytes());
+    bytes.extend(0x00000005u32.to_be_bytes());
+    bytes.extend(0u32.to_be_bytes());
+    bytes.extend(7u32.to_be_bytes());
+    bytes.extend(b"CMT");
+    smsc.get_mut().write_all(&bytes).await.unwrap();
+
+    let (header, pdu) = next(&mut smsc).await.unwrap();
+    assert!(matches!(pdu, Pdu::GenericNack(_)));
+    assert_ne!(header.command_status, Status::ESME_ROK);
+    assert_eq!(header.sequence_number, 7);
+
+    // The session goes on.
+    let header = Header::new(Id::EnquireLink, Status::ESME_ROK, 8);
+    smsc.send((header, Pdu::EnquireLink(EnquireLink {}))).await.unwrap();
+
+    let (header, pdu) = next(&mut smsc).await.unwrap();
+    assert!(matches!(pdu, Pdu::EnquireLinkResp(_)));
+    assert_eq!(header.sequence_number, 8);
+    assert!(!client.is_closed());
+
+    // The connection is only given up once it is broken.
+    drop(smsc);
+    let event = time::timeout(Duration::from_secs(5), events.next()).await.unwrap();
+    assert_eq!(event, Some(Event::Disconnected));
+    assert!(client.is_closed());
+    let event = time::timeout(Duration::from_secs(5), events.next()).await.unwrap();
+    assert_eq!(event, None);
+}
+
+#[tokio::test]
+async fn malformed_response_fails_request() {
+    let (client, mut smsc) = connect().await;
+
+    let smsc = async {
+        let (header, pdu) = next(&mut smsc).await.unwrap();
+        assert!(matches!(pdu, Pdu::SubmitSm(_)));
+
+        // A submit_sm_resp whose message_id is not terminated.
+        let mut bytes = vec![];
+        bytes.extend(20u32.to_be_bytes());
+        bytes.extend(0x80000004u32.to_be_bytes());
+        bytes.extend(0u32.to_be_bytes());
+        bytes.extend(header.sequence_number.to_be_bytes());
+        bytes.extend(b"4711");
+        smsc.get_mut().write_all(&bytes).await.unwrap();
+        smsc
+    };
+
+    // The request fails at once rather than after the request timeout.
+    let (submit, mut smsc) = time::timeout(Duration::from_secs(1), async {
+        tokio::join!(client.submit(submit_sm()), smsc)
+    })
+    .await
+    .unwrap();
+    assert!(matches!(submit, Err(Error::Malformed(Id::SubmitSmResp))));
+
+    // Responses are not nacked, and the session goes on.
+    let header = Header::new(Id::EnquireLink, Status::ESME_ROK, 8);
+    smsc.send((header, Pdu::EnquireLink(EnquireLink {}))).await.unwrap();
+    let (_, pdu) = next(&mut smsc).await.unwrap();
+    assert!(matches!(pdu, Pdu::EnquireLinkResp(_)));
+}
+
+#[tokio::test]
+async fn reject_policy() {
+    let (client, mut smsc) = connect_with(Config {
+        window_size: 1,
+        window_policy: WindowPolicy::Reject,
+        throttle_backoff: Duration::from_secs(60),
+        ..config()
+    })
+    .await;
+
+    let smsc = async {
+        let (header, pdu) = next(&mut smsc).await.unwrap();
+        assert!(matches!(pdu, Pdu::SubmitSm(_)));
+
+        // The only slot is taken by the first message.
+        assert!(matches!(client.submit(submit_sm()).await, Err(Error::WindowFull)));
+
+        let resp = SubmitSmResp {
+            message_id: COctet::default(),
+
+            #[cfg(feature = "v5")]
+            msg_submission_resp_tlv: vec![],
+        };
+        let header = Header::new(Id::SubmitSmResp, Status::ESME_RTHROTTLED, header.sequence_number);
+        smsc.send((header, Pdu::SubmitSmResp(resp))).await.unwrap();
+    };
+
+    let (first, _) = tokio::join!(client.submit(submit_sm()), smsc);
+    assert!(matches!(first, Err(Error::Status(Status::ESME_RTHROTTLED))));
+
+    // The slot is free again, but the SMSC asked to slow down.
+    assert!(matches!(client.submit(submit_sm()).await, Err(Error::WindowFull)));
+}
+
+#[tokio::test]
+async fn unbind_ends_events() {
+    let (mut client, mut smsc) = connect().await;
+    let mut events = client.events().unwrap();
+    let mut deliveries = client.deliveries().unwrap();
+
+    let smsc = async {
+        let (header, pdu) = next(&mut smsc).await.unwrap();
+        assert!(matches!(pdu, Pdu::Unbind(_)));
+
+        let header = Header::new(Id::UnbindResp, Status::ESME_ROK, header.sequence_number);
+        smsc.send((header, Pdu::UnbindResp(UnbindResp {}))).await.unwrap();
+    };
+
+    let (unbind, _) = tokio::join!(client.unbind(), smsc);
+    unbind.unwrap();
+
+    // Both streams end without an event, as the client unbound on its own.
+    let event = time::timeout(Duration::from_secs(5), events.next()).await.unwrap();
+    assert_eq!(event, None);
+    let delivery = time::timeout(Duration::from_secs(5), deliveries.next()).await.unwrap();
+    assert!(delivery.is_none());
+}
+
+#[tokio::test]
+async fn drop_without_unbind_resp_closes() {
+    let (client, mut smsc) = connect_with(Config {
+        request_timeout: Duration::from_millis(100),
+        ..config()
+    })
+    .await;
+
+    drop(client);
+    let (_, pdu) = next(&mut smsc).await.unwrap();
+    assert!(matches!(pdu, Pdu::Unbind(_)));
+
+    // The unbind_resp never comes, so the connection is closed after the
+    // request timeout.
+    assert!(next(&mut smsc).await.is_none());
+}
+
+#[tokio::test]
+async fn unbind_without_unbind_resp_closes() {
+    let (client, mut smsc) = connect_with(Config {
+        request_timeout: Duration::from_millis(100),
+        ..config()
+    })
+    .await;
+
+    let smsc = async {
+        let (_, pdu) = next(&mut smsc).await.unwrap();
+        assert!(matches!(pdu, Pdu::Unbind(_)));
+        assert!(next(&mut smsc).await.is_none());
+    };
+
+    let (unbind, _) = tokio::join!(client.unbind(), smsc);
+    assert!(matches!(unbind, Err(Error::Timeout)));
+    assert!(client.is_closed());
+}
+
+#[tokio::test]
+async fn supervisor_shutdown_unbinds() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let supervisor = Supervisor::start(addr, BindMode::Transceiver, bind(), config(), Backoff::default());
+
+    let (stream, _) = listener.accept().await.unwrap();
+    let mut smsc = Framed::new(stream, SmppCodec::new());
+    let (header, pdu) = next(&mut smsc).await.unwrap();
+    assert!(matches!(pdu, Pdu::BindTransceiver(_)));
+
+    let resp = BindResp {
+        system_id: COctet::new("smsc").unwrap(),
+        sc_interface_version: None,
+    };
+    let header = Header::new(Id::BindTransceiverResp, Status::ESME_ROK, header.sequence_number);
+    smsc.send((header, Pdu::BindTransceiverResp(resp))).await.unwrap();
+
+    let smsc = async {
+        let (header, pdu) = next(&mut smsc).await.unwrap();
+        assert!(matches!(pdu, Pdu::Unbind(_)));
+
+        let header = Header::new(Id::UnbindResp, Status::ESME_ROK, header.sequence_number);
+        smsc.send((header, Pdu::UnbindResp(UnbindResp {}))).await.unwrap();
+        assert!(next(&mut smsc).await.is_none());
+    };
+
+    let shutdown = async {
+        while !supervisor.is_bound() {
+            time::sleep(Duration::from_millis(1)).await;
+        }
+        supervisor.shutdown().await;
+    };
+
+    tokio::join!(shutdown, smsc);
+}
+
+#[tokio::test]
+async fn supervisor_replays_submit_after_link_loss() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let backoff = Backoff {
+        initial: Duration::from_millis(10),
+        max: Duration::from_millis(10),
+        jitter: 0.0,
+    };
+
+    let supervisor = Supervisor::start(addr, BindMode::Transceiver, bind(), config(), backoff);
+
+    let smsc = async {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut smsc = Framed::new(stream, SmppCodec::new());
+        answer_bind(&mut smsc, Status::ESME_ROK).await;
+
+        // The link is lost while the message is in flight.
+        let (_, pdu) = next(&mut smsc).await.unwrap();
+        assert!(matches!(pdu, Pdu::SubmitSm(_)));
+        drop(smsc);
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut smsc = Framed::new(stream, SmppCodec::new());
+        answer_bind(&mut smsc, Status::ESME_ROK).await;
+
+        let (header, pdu) = next(&mut smsc).await.unwrap();
+        assert!(matches!(pdu, Pdu::SubmitSm(_)));
+
+        let resp = SubmitSmResp {
+            message_id: COctet::new("1").unwrap(),
+
+            #[cfg(feature = "v5")]
+            msg_submission_resp_tlv: vec![],
+        };
+        let header = Header::new(Id::SubmitSmResp, Status::ESME_ROK, header.sequence_number);
+        smsc.send((header, Pdu::SubmitSmResp(resp))).await.unwrap();
+    };
+
+    let submit = async {
+        while !supervisor.is_bound() {
+            time::sleep(Duration::from_millis(1)).await;
+        }
+        supervisor.submit(submit_sm()).await
+    };
+
+    let (submit, ()) = tokio::join!(submit, smsc);
+    assert!(matches!(submit, Ok(resp) if resp.message_id.as_ref().to_bytes() == b"1"));
+}
+
+#[tokio::test]
+async fn supervisor_replays_submit_queued_on_lost_link() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let config = Config {
+        window_size: 1,
+        window_policy: WindowPolicy::Block,
+        ..config()
+    };
+    let backoff = Backoff {
+        initial: Duration::from_millis(10),
+        max: Duration::from_millis(10),
+        jitter: 0.0,
+    };
+
+    let supervisor = Supervisor::start(addr, BindMode::Transceiver, bind(), config, backoff);
+
+    let smsc = async {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut smsc = Framed::new(stream, SmppCodec::new());
+        answer_bind(&mut smsc, Status::ESME_ROK).await;
+
+        // The second message is still waiting for the window when the link
+        // is lost.
+        let (_, pdu) = next(&mut smsc).await.unwrap();
+        assert!(matches!(pdu, Pdu::SubmitSm(_)));
+        drop(smsc);
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut smsc = Framed::new(stream, SmppCodec::new());
+        answer_bind(&mut smsc, Status::ESME_ROK).await;
+
+        for _ in 0..2 {
+            let (header, pdu) = next(&mut smsc).await.unwrap();
+            assert!(matches!(pdu, Pdu::SubmitSm(_)));
+
+            let resp = SubmitSmResp {
+                message_id: COctet::default(),
+
+                #[cfg(feature = "v5")]
+                msg_submission_resp_tlv: vec![],
+            };
+            let header = Header::new(Id::SubmitSmResp, Status::ESME_ROK, header.sequence_number);
+            smsc.send((header, Pdu::SubmitSmResp(resp))).await.unwrap();
+        }
+    };
+
+    let submit = async {
+        while !supervisor.is_bound() {
+            time::sleep(Duration::from_millis(1)).await;
+        }
+        tokio::join!(supervisor.submit(submit_sm()), supervisor.submit(submit_sm()))
+    };
+
+    let ((first, second), ()) = tokio::join!(submit, smsc);
+    assert!(first.is_ok());
+    assert!(second.is_ok());
+}
+
+fn outbind(system_id: &str, password: &str) -> OutBind {
+    OutBind {
+        system_id: COctet::new(system_id).unwrap(),
+        password: COctet::new(password).unwrap(),
+    }
+}
+
+/// Opens the session from the SMSC side with `outbind`.
+async fn send_outbind(smsc: &mut Smsc, outbind: OutBind) {
+    let header = Header::new(Id::Outbind, Status::ESME_ROK, 1);
+    smsc.send((header, Pdu::Outbind(outbind))).await.unwrap();
+}
+
+#[tokio::test]
+async fn outbind_binds_receiver() {
+    let (esme, smsc) = tokio::io::duplex(1024);
+    let mut smsc = Framed::new(smsc, SmppCodec::new());
+    let expected = outbind("smsc", "secret");
+
+    let smsc_side = async {
+        send_outbind(&mut smsc, outbind("smsc", "secret")).await;
+
+        let (header, pdu) = next(&mut smsc).await.unwrap();
+        assert!(matches!(&pdu, Pdu::BindReceiver(bind) if bind.system_id.as_ref().to_bytes() == b"esme"));
+
+        let resp = BindResp {
+            system_id: COctet::new("smsc").unwrap(),
+            sc_interface_version: None,
+        };
+        let header = Header::new(Id::BindReceiverResp, Status::ESME_ROK, header.sequence_number);
+        smsc.send((header, Pdu::BindReceiverResp(resp))).await.unwrap();
+    };
+
+    let (client, _) = tokio::join!(EsmeClient::outbind(esme, &expected, bind(), config()), smsc_side);
+    let mut client = client.unwrap();
+    let mut deliveries = client.deliveries().unwrap();
+
+    // Messages may be delivered but not submitted.
+    let deliver_sm = DeliverSm {
+        service_type: COctet::default(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        dest_addr_ton: 0,
+        dest_addr_npi: 0,
+        destination_addr: COctet::new("esme").unwrap(),
+        esm_class: 0,
+        protocol_id: 0,
+        priority_flag: 0,
+        schedule_delivery_time: COctet::default(),
+        validity_period: COctet::default(),
+        registered_delivery: 0,
+        replace_if_present_flag: 0,
+        data_coding: 0,
+        sm_default_msg_id: 0,
+        sm_length: 5,
+        short_message: b"hello".to_vec(),
+        msg_delivery_tlv: vec![],
+    };
+    let header = Header::new(Id::DeliverSm, Status::ESME_ROK, 2);
+    smsc.send((header, Pdu::DeliverSm(deliver_sm))).await.unwrap();
+
+    let (header, pdu) = next(&mut smsc).await.unwrap();
+    assert!(matches!(pdu, Pdu::DeliverSmResp(_)));
+    assert_eq!(header.sequence_number, 2);
+
+    let delivered = time::timeout(Duration::from_secs(5), deliveries.next())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivered.short_message, b"hello");
+
+    assert!(matches!(client.submit(submit_sm()).await, Err(Error::Rejected(_))));
+}
+
+#[tokio::test]
+async fn outbind_with_wrong_credentials() {
+    let expected = outbind("smsc", "secret");
+
+    for outbind in [outbind("other", "secret"), outbind("smsc", "guess")] {
+        let (esme, smsc) = tokio::io::duplex(1024);
+        let mut smsc = Framed::new(smsc, SmppCodec::new());
+
+        let (client, _) = tokio::join!(
+            EsmeClient::outbind(esme, &expected, bind(), config()),
+            send_outbind(&mut smsc, outbind),
+        );
+        assert!(matches!(client, Err(Error::InvalidOutbind)));
+
+        // No bind is attempted.
+        assert!(next(&mut smsc).await.is_none());
+    }
+}
+
+#[tokio::test]
+async fn outbind_expected_first() {
+    let (esme, smsc) = tokio::io::duplex(1024);
+    let mut smsc = Framed::new(smsc, SmppCodec::new());
+    let expected = outbind("smsc", "secret");
+
+    let enquire_link = async {
+        let header = Header::new(Id::EnquireLink, Status::ESME_ROK, 1);
+        smsc.send((header, Pdu::EnquireLink(EnquireLink {}))).await.unwrap();
+    };
+
+    let (client, _) = tokio::join!(EsmeClient::outbind(esme, &expected, bind(), config()), enquire_link,);
+    assert!(matches!(client, Err(Error::InvalidOutbind)));
+}
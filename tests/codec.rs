@@ -0,0 +1,113 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+#![cfg(feature = "tokio")]
+
+extern crate bytes;
+extern crate short_msg;
+extern crate tokio_util;
+
+use bytes::BytesMut;
+use short_msg::smpp::{
+    codec::{Error, SmppCodec},
+    prelude::*,
+};
+use tokio_util::codec::{Decoder, Encoder};
+
+fn bind() -> Pdu {
+    Pdu::BindTransmitter(BindTransmitter {
+        system_id: COctet::new("esme").unwrap(),
+        password: COctet::new("secret").unwrap(),
+        system_type: COctet::new("").unwrap(),
+        interface_version: 0x34,
+        addr_ton: 0,
+        addr_npi: 0,
+        address_range: COctet::new("").unwrap(),
+    })
+}
+
+#[test]
+fn decode_fragments() {
+    let bytes = bind().encode(1).unwrap();
+    let mut codec = SmppCodec::new();
+    let mut buf = BytesMut::new();
+
+    // Fed one octet at a time, nothing is decoded until the last one arrives,
+    // including while the command_length itself is incomplete.
+    for &b in &bytes[..bytes.len() - 1] {
+        buf.extend_from_slice(&[b]);
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+    }
+
+    buf.extend_from_slice(&bytes[bytes.len() - 1..]);
+    let (header, pdu) = codec.decode(&mut buf).unwrap().unwrap();
+    assert_eq!(header.command_id, Id::BindTransmitter);
+    assert_eq!(header.sequence_number, 1);
+    assert!(matches!(pdu, Pdu::BindTransmitter(_)));
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn decode_two_pdus_in_one_buffer() {
+    let mut buf = BytesMut::new();
+    buf.extend_from_slice(&bind().encode(1).unwrap());
+    buf.extend_from_slice(&Pdu::EnquireLink(EnquireLink {}).encode(2).unwrap());
+
+    let mut codec = SmppCodec::new();
+    let (header, pdu) = codec.decode(&mut buf).unwrap().unwrap();
+    assert_eq!(header.sequence_number, 1);
+    assert!(matches!(pdu, Pdu::BindTransmitter(_)));
+
+    let (header, pdu) = codec.decode(&mut buf).unwrap().unwrap();
+    assert_eq!(header.sequence_number, 2);
+    assert!(matches!(pdu, Pdu::EnquireLink(_)));
+
+    assert!(codec.decode(&mut buf).unwrap().is_none());
+    assert!(codec.decode_eof(&mut buf).unwrap().is_none());
+}
+
+#[test]
+fn decode_rejects_command_length_above_max() {
+    let mut codec = SmppCodec::new().with_max_command_length(64);
+
+    // Only the command_length is needed to reject the PDU.
+    let mut buf = BytesMut::from(&65u32.to_be_bytes()[..]);
+    let e = codec.decode(&mut buf).unwrap_err();
+    assert!(matches!(e, Error::CommandLengthExceeded(65)));
+    assert_eq!(e.command_status(), Status::ESME_RINVCMDLEN);
+
+    let mut buf = BytesMut::from(&bind().encode(1).unwrap()[..]);
+    assert!(codec.decode(&mut buf).unwrap().is_some());
+}
+
+#[test]
+fn decode_rejects_command_length_below_header() {
+    let mut buf = BytesMut::from(&15u32.to_be_bytes()[..]);
+    let e = SmppCodec::new().decode(&mut buf).unwrap_err();
+    assert_eq!(e.command_status(), Status::ESME_RINVCMDLEN);
+}
+
+#[test]
+fn decode_eof_with_incomplete_pdu() {
+    let bytes = bind().encode(1).unwrap();
+    let mut buf = BytesMut::from(&bytes[..10]);
+    assert!(matches!(SmppCodec::new().decode_eof(&mut buf), Err(Error::Io(_))));
+}
+
+#[test]
+fn encode_keeps_status_and_sequence_number() {
+    let mut codec = SmppCodec::new();
+    let mut buf = BytesMut::new();
+
+    // The command_length and command_id of the header are derived from the PDU.
+    let header = Header::new(Id::GenericNack, Status::ESME_RTHROTTLED, 3);
+    codec
+        .encode((header, Pdu::EnquireLinkResp(EnquireLinkResp {})), &mut buf)
+        .unwrap();
+
+    let (header, pdu) = codec.decode(&mut buf).unwrap().unwrap();
+    assert_eq!(header.command_id, Id::EnquireLinkResp);
+    assert_eq!(header.command_status, Status::ESME_RTHROTTLED);
+    assert_eq!(header.sequence_number, 3);
+    assert!(matches!(pdu, Pdu::EnquireLinkResp(_)));
+}
@@ -0,0 +1,97 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate short_msg;
+
+use short_msg::smpp::prelude::*;
+
+#[test]
+fn pack() {
+    let bytes = gsm_encode("hello").unwrap();
+    assert_eq!(bytes, b"\xE8\x32\x9B\xFD\x06");
+    assert_eq!(gsm_decode(&bytes), "hello");
+
+    assert_eq!(gsm_encode("").unwrap(), b"");
+    assert_eq!(gsm_decode(b""), "");
+
+    // '@' is the septet 0x00.
+    assert_eq!(gsm_encode("@").unwrap(), b"\x00");
+    assert_eq!(gsm_decode(b"\x00"), "@");
+}
+
+#[test]
+fn seven_septets_are_padded_with_cr() {
+    // The seven spare bits would read as '@', so they hold a CR instead.
+    let bytes = gsm_encode("abcdefg").unwrap();
+    assert_eq!(bytes, b"\x61\xF1\x98\x5C\x36\x9F\x1B");
+    assert_eq!(gsm_decode(&bytes), "abcdefg");
+
+    // A CR of the text itself is kept.
+    let bytes = gsm_encode("abcdef\r").unwrap();
+    assert_eq!(bytes, b"\x61\xF1\x98\x5C\x36\x37\x1A");
+    assert_eq!(gsm_decode(&bytes), "abcdef\r");
+}
+
+#[test]
+fn eight_septets_ending_in_cr() {
+    // The final CR ends on an octet boundary and would be taken for padding,
+    // so another one is added. The receiver sees both, which 3GPP TS 23.038
+    // deems harmless.
+    let bytes = gsm_encode("abcdefg\r").unwrap();
+    assert_eq!(bytes, b"\x61\xF1\x98\x5C\x36\x9F\x1B\x0D");
+    assert_eq!(gsm_decode(&bytes), "abcdefg\r\r");
+
+    // Without the CR, nothing is added.
+    let bytes = gsm_encode("abcdefgh").unwrap();
+    assert_eq!(bytes.len(), 7);
+    assert_eq!(gsm_decode(&bytes), "abcdefgh");
+
+    let bytes = gsm_encode("abcdefgh\r").unwrap();
+    assert_eq!(bytes.len(), 8);
+    assert_eq!(gsm_decode(&bytes), "abcdefgh\r");
+}
+
+#[test]
+fn extension_table() {
+    // Each character takes an escape and a code.
+    let bytes = gsm_encode("€{}[]").unwrap();
+    assert_eq!(bytes, b"\x9B\xF2\x06\xB5\x49\x6D\x78\x1B\x1F");
+    assert_eq!(gsm_decode(&bytes), "€{}[]");
+
+    let text = "^{}\\[~]|€\u{000C}";
+    assert_eq!(gsm_decode(&gsm_encode(text).unwrap()), text);
+
+    // An escape before a code the extension table does not define shows the
+    // basic character.
+    assert_eq!(gsm_decode(b"\x9B\x20"), "A");
+}
+
+#[test]
+fn basic_table_round_trip() {
+    let text = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+                ¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+    assert_eq!(text.chars().count(), 127);
+
+    let bytes = gsm_encode(text).unwrap();
+    assert_eq!(bytes.len(), 112);
+    assert_eq!(gsm_decode(&bytes), text);
+}
+
+#[test]
+fn encode_error_index() {
+    assert_eq!(gsm_encode("abc你好"), Err(GsmEncodeError { index: 3, char: '你' }));
+
+    // The index counts characters, not septets or octets.
+    assert_eq!(gsm_encode("€é€😀").unwrap_err().index, 3);
+}
+
+#[test]
+fn lossy() {
+    let (bytes, lost) = gsm_encode_lossy("a你b€😀");
+    assert_eq!(lost, vec![(1, '你'), (4, '😀')]);
+    assert_eq!(gsm_decode(&bytes), "a?b€?");
+
+    let (bytes, lost) = gsm_encode_lossy("hello");
+    assert!(lost.is_empty());
+    assert_eq!(bytes, gsm_encode("hello").unwrap());
+}
@@ -0,0 +1,211 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate short_msg;
+
+use short_msg::smpp::prelude::*;
+
+const ESC: u8 = 0x1B;
+
+fn pack(septets: &[u8]) -> Vec<u8> {
+    let mut v = vec![0u8; (septets.len() * 7).div_ceil(8)];
+    for (i, septet) in septets.iter().enumerate() {
+        let bit = i * 7;
+        let septet = u16::from(*septet) << (bit % 8);
+        v[bit / 8] |= septet as u8;
+        if let Some(next) = v.get_mut(bit / 8 + 1) {
+            *next |= (septet >> 8) as u8;
+        }
+    }
+
+    v
+}
+
+fn round_trip(text: &str, tables: ShiftTables) -> Vec<u8> {
+    let bytes = gsm_encode_with(text, tables).unwrap_or_else(|e| panic!("{tables:?}: {e:?}"));
+    assert_eq!(gsm_decode_with(&bytes, tables), text, "{tables:?}");
+
+    bytes
+}
+
+#[test]
+fn locking_shift_round_trip() {
+    for lang in NationalLanguage::ALL
+        .into_iter()
+        .filter(|lang| lang.has_locking_shift())
+    {
+        let tables = ShiftTables {
+            locking: Some(lang),
+            single: None,
+        };
+
+        let mut text = String::new();
+        for code in (0..0x80).filter(|code| *code != ESC) {
+            let c = gsm_decode_with(&pack(&[code]), tables);
+            if c.is_empty() {
+                continue;
+            }
+
+            // Every character of the table takes a single septet.
+            assert_eq!(round_trip(&c, tables).len(), 1, "{lang:?} {code:#04X}");
+            text.push_str(&c);
+        }
+
+        assert!(text.chars().count() > 64, "{lang:?}");
+        round_trip(&text, tables);
+    }
+}
+
+#[test]
+fn single_shift_round_trip() {
+    for lang in NationalLanguage::ALL {
+        let tables = ShiftTables {
+            locking: None,
+            single: Some(lang),
+        };
+
+        let basic: String = (0..0x80).map(|code| gsm_decode_with(&pack(&[code]), tables)).collect();
+
+        let mut text = String::new();
+        for code in (0..0x80).filter(|code| *code != ESC) {
+            let c = gsm_decode_with(&pack(&[ESC, code]), tables);
+            if c.is_empty() || c == gsm_decode_with(&pack(&[code]), tables) {
+                // Undefined, shown as the character of the basic table.
+                continue;
+            }
+
+            // Every character of the table takes an escape and a code, unless
+            // the basic table has it too.
+            let septets = if basic.contains(&c) { 1 } else { 2 };
+            assert_eq!(round_trip(&c, tables).len(), septets, "{lang:?} {code:#04X}");
+            text.push_str(&c);
+        }
+
+        assert!(!text.is_empty(), "{lang:?}");
+        round_trip(&text, tables);
+    }
+}
+
+#[test]
+fn without_locking_shift() {
+    assert!(!NationalLanguage::Spanish.has_locking_shift());
+
+    // Spanish falls back to the default alphabet.
+    let tables = ShiftTables {
+        locking: Some(NationalLanguage::Spanish),
+        single: None,
+    };
+    assert_eq!(round_trip("hello", tables), gsm_encode("hello").unwrap());
+}
+
+#[test]
+fn information_elements() {
+    let tables = ShiftTables {
+        locking: Some(NationalLanguage::Turkish),
+        single: Some(NationalLanguage::Spanish),
+    };
+    let elements = tables.information_elements();
+    assert_eq!(elements, [
+        InformationElement::NationalSingleShift(NationalLanguage::Spanish),
+        InformationElement::NationalLockingShift(NationalLanguage::Turkish),
+    ]);
+    assert_eq!(ShiftTables::from_information_elements(&elements), tables);
+
+    let udh = UserDataHeader::from(tables);
+    assert_eq!(udh.to_bytes().unwrap(), [6, 0x24, 1, 0x02, 0x25, 1, 0x01]);
+    assert_eq!(
+        UserDataHeader::split(&udh.to_bytes().unwrap())
+            .unwrap()
+            .0
+            .shift_tables(),
+        tables
+    );
+
+    assert!(ShiftTables::default().information_elements().is_empty());
+    assert!(UserDataHeader::from(ShiftTables::default()).is_empty());
+}
+
+/// Decodes the *short_message* returned by [`gsm_encode_national`].
+fn decode_national(short_message: &[u8], udh: &UserDataHeader) -> String {
+    if udh.is_empty() {
+        return gsm_decode(short_message);
+    }
+
+    let (read, text) = UserDataHeader::split_gsm(short_message).unwrap();
+    assert_eq!(&read, udh);
+
+    text
+}
+
+#[test]
+fn encode_national_picks_the_cheapest_tables() {
+    use NationalLanguage::*;
+
+    let languages = [Turkish, Spanish, Portuguese];
+
+    // The default alphabet needs no UDH.
+    let (bytes, udh) = gsm_encode_national("hello", &languages).unwrap();
+    assert!(udh.is_empty());
+    assert_eq!(bytes, gsm_encode("hello").unwrap());
+
+    // A UDH of 4 octets and 3 fill bits, followed by 4 septets.
+    let (bytes, udh) = gsm_encode_national("Ağaç", &languages).unwrap();
+    assert_eq!(udh.shift_tables(), ShiftTables {
+        locking: Some(Turkish),
+        single: None,
+    });
+    assert_eq!(bytes.len(), 8);
+    assert_eq!(decode_national(&bytes, &udh), "Ağaç");
+
+    // Portuguese has a locking shift for 'á' too, so leave it out.
+    let languages = [Turkish, Spanish];
+    let (bytes, udh) = gsm_encode_national("á", &languages).unwrap();
+    assert_eq!(udh.shift_tables(), ShiftTables {
+        locking: None,
+        single: Some(Spanish),
+    });
+    assert_eq!(decode_national(&bytes, &udh), "á");
+
+    // A UDH of 7 octets needs no fill bits.
+    let (bytes, udh) = gsm_encode_national("ğá", &languages).unwrap();
+    assert_eq!(udh.shift_tables(), ShiftTables {
+        locking: Some(Turkish),
+        single: Some(Spanish),
+    });
+    assert_eq!(udh.fill_bits(), 0);
+    assert_eq!(decode_national(&bytes, &udh), "ğá");
+}
+
+#[test]
+fn encode_national_counts_the_udh() {
+    let languages = [NationalLanguage::Turkish];
+
+    // Four escaped euros take 8 septets; a locking shift takes 4 septets
+    // behind a UDH that takes 5 septets with its fill bits.
+    let (bytes, udh) = gsm_encode_national("€€€€", &languages).unwrap();
+    assert!(udh.is_empty());
+    assert_eq!(bytes.len(), 7);
+
+    // Five take 10 septets either way, and the default alphabet wins a tie.
+    let (_, udh) = gsm_encode_national("€€€€€", &languages).unwrap();
+    assert!(udh.is_empty());
+
+    // Six take 12 septets, against 11.
+    let (bytes, udh) = gsm_encode_national("€€€€€€", &languages).unwrap();
+    assert_eq!(udh.shift_tables().locking, Some(NationalLanguage::Turkish));
+    assert_eq!(udh.fill_bits(), 3);
+    assert_eq!(bytes.len(), 10);
+    assert_eq!(decode_national(&bytes, &udh), "€€€€€€");
+}
+
+#[test]
+fn encode_national_error() {
+    assert_eq!(
+        gsm_encode_national("ğ", &[]),
+        Err(GsmEncodeError { index: 0, char: 'ğ' })
+    );
+
+    // The error names the character the best combination stops at.
+    let e = gsm_encode_national("ğ你", &[NationalLanguage::Turkish]).unwrap_err();
+    assert_eq!(e.index, 1);
+}
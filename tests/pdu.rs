@@ -0,0 +1,188 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate bincode;
+extern crate short_msg;
+
+use short_msg::smpp::{
+    pdu::{Error, Header},
+    prelude::*,
+};
+
+fn config() -> impl bincode::config::Config {
+    bincode::config::standard().with_big_endian().with_fixed_int_encoding()
+}
+
+fn bind() -> BindTransmitter {
+    BindTransmitter {
+        system_id: COctet::new("esme").unwrap(),
+        password: COctet::new("secret").unwrap(),
+        system_type: COctet::new("").unwrap(),
+        interface_version: 0x34,
+        addr_ton: 0,
+        addr_npi: 0,
+        address_range: COctet::new("").unwrap(),
+    }
+}
+
+fn pdu_bytes(command_id: u32, command_status: u32, sequence_number: u32, body: &[u8]) -> Vec<u8> {
+    let mut v = vec![];
+    v.extend((16 + body.len() as u32).to_be_bytes());
+    v.extend(command_id.to_be_bytes());
+    v.extend(command_status.to_be_bytes());
+    v.extend(sequence_number.to_be_bytes());
+    v.extend_from_slice(body);
+    v
+}
+
+#[test]
+fn encode_writes_header() {
+    let bytes = Pdu::BindTransmitter(bind()).encode(7).unwrap();
+    assert_eq!(bytes, pdu_bytes(0x00000002, 0, 7, b"esme\0secret\0\0\x34\x00\x00\0"));
+}
+
+#[test]
+fn decode_dispatches_on_command_id() {
+    let bytes = Pdu::BindTransmitter(bind()).encode(7).unwrap();
+    let (header, pdu) = Pdu::decode(&bytes).unwrap();
+    assert_eq!(header.command_id, Id::BindTransmitter);
+    assert_eq!(header.command_length as usize, bytes.len());
+    assert_eq!(header.sequence_number, 7);
+    assert!(matches!(&pdu, Pdu::BindTransmitter(b) if b.system_id.as_ref().to_bytes() == b"esme"));
+    assert_eq!(pdu.command_id(), Id::BindTransmitter);
+
+    // Same body layout, different command_id.
+    let bytes = pdu_bytes(0x00000009, 0, 8, b"esme\0secret\0\0\x34\x00\x00\0");
+    assert!(matches!(Pdu::decode(&bytes).unwrap().1, Pdu::BindTransceiver(_)));
+
+    let bytes = pdu_bytes(0x80000015, 0, 9, b"");
+    assert!(matches!(Pdu::decode(&bytes).unwrap().1, Pdu::EnquireLinkResp(_)));
+
+    let bytes = pdu_bytes(0x80000004, 0, 10, b"4711\0");
+    let (_, pdu) = Pdu::decode(&bytes).unwrap();
+    assert!(matches!(&pdu, Pdu::SubmitSmResp(r) if r.message_id.as_ref().to_bytes() == b"4711"));
+    assert_eq!(pdu.encode(10).unwrap(), bytes);
+}
+
+#[test]
+fn encode_with_status() {
+    let pdu = Pdu::GenericNack(GenericNack {});
+    let bytes = pdu.encode_with_status(Status::ESME_RINVCMDID, 3).unwrap();
+    assert_eq!(bytes, pdu_bytes(0x80000000, 0x00000003, 3, b""));
+
+    let (header, pdu) = Pdu::decode(&bytes).unwrap();
+    assert_eq!(header.command_status, Status::ESME_RINVCMDID);
+    assert!(matches!(pdu, Pdu::GenericNack(_)));
+}
+
+#[test]
+fn unknown_command_id_is_raw() {
+    let bytes = pdu_bytes(0x00010200, 0, 1, b"vendor specific");
+    let (header, pdu) = Pdu::decode(&bytes).unwrap();
+    assert_eq!(header.command_id, Id::Reserved(0x00010200));
+    assert!(matches!(
+        &pdu,
+        Pdu::Raw { command_id: Id::Reserved(0x00010200), body } if body == b"vendor specific"
+    ));
+    assert_eq!(pdu.encode(1).unwrap(), bytes);
+}
+
+#[test]
+fn bodiless_negative_response_is_raw() {
+    let bytes = pdu_bytes(0x80000002, 0x0000000E, 1, b"");
+    let (header, pdu) = Pdu::decode(&bytes).unwrap();
+    assert_eq!(header.command_status, Status::ESME_RINVPASWD);
+    assert!(matches!(&pdu, Pdu::Raw { command_id: Id::BindTransmitterResp, body } if body.is_empty()));
+    assert_eq!(pdu.encode_with_status(Status::ESME_RINVPASWD, 1).unwrap(), bytes);
+
+    // A negative response with a body is still decoded.
+    let bytes = pdu_bytes(0x80000002, 0x0000000E, 1, b"smsc\0");
+    assert!(matches!(Pdu::decode(&bytes).unwrap().1, Pdu::BindTransmitterResp(_)));
+
+    // So is a bodiless request.
+    let bytes = pdu_bytes(0x00000006, 0, 1, b"");
+    assert!(matches!(Pdu::decode(&bytes).unwrap().1, Pdu::Unbind(_)));
+}
+
+#[test]
+fn id_is_response() {
+    assert!(!Id::SubmitSm.is_response());
+    assert!(Id::SubmitSmResp.is_response());
+    assert!(Id::GenericNack.is_response());
+    assert!(!Id::Outbind.is_response());
+    assert!(!Id::Reserved(0x00010200).is_response());
+    assert!(Id::Reserved(0x80010200).is_response());
+
+    assert_eq!(Id::SubmitSm.response(), Id::SubmitSmResp);
+    assert_eq!(Id::EnquireLink.response(), Id::EnquireLinkResp);
+    assert_eq!(Id::BindTransceiver.response(), Id::BindTransceiverResp);
+    assert_eq!(Id::SubmitSmResp.response(), Id::SubmitSmResp);
+}
+
+#[test]
+fn header_new_with_body_matches_encode() {
+    let bytes = Header::new_with_body(Id::BindTransmitter, Status::ESME_ROK, 7, bind()).unwrap();
+    assert_eq!(bytes, Pdu::BindTransmitter(bind()).encode(7).unwrap());
+}
+
+/// The fixed fields of an empty ***data_sm***, which are followed by TLVs.
+const DATA_SM: &[u8] = b"\0\0\0\0\0\0\0\0\0\0";
+
+#[test]
+fn trailing_tlvs() {
+    let body = [DATA_SM, b"\x04\x24\x00\x02hi\x02\x0c\x00\x02\x12\x34"].concat();
+    let (_, pdu) = Pdu::decode(&pdu_bytes(0x00000103, 0, 1, &body)).unwrap();
+    let Pdu::DataSm(data_sm) = pdu else {
+        panic!("not a data_sm: {:?}", pdu)
+    };
+    assert_eq!(data_sm.msg_submission_tlv.len(), 2);
+    assert_eq!(data_sm.msg_submission_tlv[0].tag, Tag::MessagePayload);
+    assert_eq!(data_sm.msg_submission_tlv[1].tag, Tag::SarMsgRefNum);
+    assert_eq!(data_sm.msg_submission_tlv[1].val, b"\x12\x34");
+}
+
+#[test]
+fn truncated_tlv_is_an_error() {
+    // The value is cut short, as is the header of a TLV.
+    for tlv in [&b"\x04\x24\x00\x05hi"[..], b"\x04\x24\x00"] {
+        let body = [DATA_SM, tlv].concat();
+        assert!(bincode::decode_from_slice::<DataSm, _>(&body, config()).is_err());
+
+        let e = Pdu::decode(&pdu_bytes(0x00000103, 0, 1, &body)).unwrap_err();
+        assert_eq!(e.command_status(), Status::ESME_RINVCMDLEN);
+    }
+}
+
+#[test]
+fn tlvs_need_a_slice() {
+    let body = [DATA_SM, b"\x04\x24\x00\x02hi"].concat();
+    assert!(bincode::decode_from_std_read::<DataSm, _, _>(&mut &body[..], config()).is_err());
+}
+
+#[test]
+fn trailing_bytes_are_invalid_command_length() {
+    let e = Pdu::decode(&pdu_bytes(0x00000015, 0, 1, b"\0")).unwrap_err();
+    assert!(matches!(e, Error::InvalidCommandLength(17)));
+    assert_eq!(e.command_status(), Status::ESME_RINVCMDLEN);
+
+    let e = Pdu::decode(&pdu_bytes(0x00000003, 0, 1, b"4711\0\x01\x0185212345678\0\0")).unwrap_err();
+    assert_eq!(e.command_status(), Status::ESME_RINVCMDLEN);
+}
+
+#[test]
+fn short_body_is_invalid_command_length() {
+    let e = Pdu::decode(&pdu_bytes(0x00000003, 0, 1, b"4711\0\x01")).unwrap_err();
+    assert!(matches!(e, Error::InvalidCommandLength(22)));
+    assert_eq!(e.command_status(), Status::ESME_RINVCMDLEN);
+
+    // The command_length promises more than the buffer holds.
+    let mut bytes = pdu_bytes(0x00000015, 0, 1, b"");
+    bytes[3] = 20;
+    let e = Pdu::decode(&bytes).unwrap_err();
+    assert_eq!(e.command_status(), Status::ESME_RINVCMDLEN);
+
+    // The command_length is shorter than the header.
+    bytes[3] = 8;
+    let e = Pdu::decode(&bytes).unwrap_err();
+    assert!(matches!(e, Error::InvalidCommandLength(8)));
+}
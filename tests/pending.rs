@@ -0,0 +1,165 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+#![cfg(any(feature = "client", feature = "server"))]
+
+extern crate short_msg;
+extern crate tokio;
+
+use std::{sync::Arc, time::Duration};
+
+use short_msg::smpp::{
+    prelude::*,
+    session::{PendingRequests, RequestError, Sequence},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn enquire_link(seq: u32) -> Header {
+    Header::new(Id::EnquireLink, Status::ESME_ROK, seq)
+}
+
+fn enquire_link_resp(status: Status, seq: u32) -> (Header, Pdu) {
+    (
+        Header::new(Id::EnquireLinkResp, status, seq),
+        Pdu::EnquireLinkResp(EnquireLinkResp {}),
+    )
+}
+
+#[test]
+fn sequence_wraps() {
+    let sequence = Sequence::starting_at(Sequence::MAX - 1);
+    assert_eq!(sequence.allocate(), 0x7FFFFFFE);
+    assert_eq!(sequence.allocate(), 0x7FFFFFFF);
+    assert_eq!(sequence.allocate(), 0x00000001);
+    assert_eq!(sequence.allocate(), 0x00000002);
+
+    assert_eq!(Sequence::new().allocate(), 1);
+    assert_eq!(Sequence::starting_at(0).allocate(), 1);
+    assert_eq!(Sequence::starting_at(0x80000000).allocate(), 1);
+}
+
+#[tokio::test]
+async fn resolve() {
+    let pending = Arc::new(PendingRequests::new());
+    let response = pending.register(&enquire_link(1)).unwrap();
+    assert_eq!(response.sequence_number(), 1);
+    assert_eq!(pending.len(), 1);
+
+    // Nobody waits for sequence_number 2.
+    let (header, pdu) = enquire_link_resp(Status::ESME_ROK, 2);
+    assert!(pending.resolve(header, pdu).is_some());
+
+    let (header, pdu) = enquire_link_resp(Status::ESME_ROK, 1);
+    assert!(pending.resolve(header, pdu).is_none());
+    assert!(pending.is_empty());
+
+    let (header, pdu) = response.wait(TIMEOUT).await.unwrap();
+    assert_eq!(header.sequence_number, 1);
+    assert!(matches!(pdu, Pdu::EnquireLinkResp(_)));
+}
+
+#[tokio::test]
+async fn fail() {
+    let pending = Arc::new(PendingRequests::new());
+    let response = pending.register(&enquire_link(1)).unwrap();
+
+    let (header, _) = enquire_link_resp(Status::ESME_ROK, 2);
+    assert!(!pending.fail(&header, RequestError::Malformed(Id::EnquireLinkResp)));
+
+    let (header, _) = enquire_link_resp(Status::ESME_ROK, 1);
+    assert!(pending.fail(&header, RequestError::Malformed(Id::EnquireLinkResp)));
+    assert!(pending.is_empty());
+
+    assert!(matches!(
+        response.wait(TIMEOUT).await,
+        Err(RequestError::Malformed(Id::EnquireLinkResp))
+    ));
+}
+
+#[tokio::test]
+async fn negative_responses() {
+    let pending = Arc::new(PendingRequests::new());
+
+    let response = pending.register(&enquire_link(1)).unwrap();
+    let (header, pdu) = enquire_link_resp(Status::ESME_RSYSERR, 1);
+    pending.resolve(header, pdu);
+    assert!(matches!(
+        response.wait(TIMEOUT).await,
+        Err(RequestError::Status(Status::ESME_RSYSERR))
+    ));
+
+    let response = pending.register(&enquire_link(2)).unwrap();
+    let header = Header::new(Id::GenericNack, Status::ESME_RINVCMDID, 2);
+    pending.resolve(header, Pdu::GenericNack(GenericNack {}));
+    assert!(matches!(
+        response.wait(TIMEOUT).await,
+        Err(RequestError::Nack(Status::ESME_RINVCMDID))
+    ));
+
+    let response = pending.register(&enquire_link(3)).unwrap();
+    let header = Header::new(Id::UnbindResp, Status::ESME_ROK, 3);
+    pending.resolve(header, Pdu::UnbindResp(UnbindResp {}));
+    assert!(matches!(
+        response.wait(TIMEOUT).await,
+        Err(RequestError::UnexpectedResponse(Id::UnbindResp))
+    ));
+}
+
+#[tokio::test]
+async fn duplicate_sequence_number() {
+    let pending = Arc::new(PendingRequests::new());
+    let first = pending.register(&enquire_link(1)).unwrap();
+
+    assert!(matches!(
+        pending.register(&enquire_link(1)),
+        Err(RequestError::InFlight(1))
+    ));
+
+    // The earlier request still gets its response.
+    let (header, pdu) = enquire_link_resp(Status::ESME_ROK, 1);
+    assert!(pending.resolve(header, pdu).is_none());
+    assert!(first.wait(TIMEOUT).await.is_ok());
+
+    // Once answered, the sequence_number may be used again.
+    assert!(pending.register(&enquire_link(1)).is_ok());
+}
+
+#[tokio::test]
+async fn timeout() {
+    let pending = Arc::new(PendingRequests::new());
+    let response = pending.register(&enquire_link(1)).unwrap();
+
+    assert!(matches!(
+        response.wait(Duration::from_millis(10)).await,
+        Err(RequestError::Timeout)
+    ));
+
+    // The request is withdrawn, so the late response is handed back.
+    assert!(pending.is_empty());
+    let (header, pdu) = enquire_link_resp(Status::ESME_ROK, 1);
+    assert!(pending.resolve(header, pdu).is_some());
+}
+
+#[tokio::test]
+async fn dropped_response() {
+    let pending = Arc::new(PendingRequests::new());
+    drop(pending.register(&enquire_link(1)).unwrap());
+
+    assert!(pending.is_empty());
+    let (header, pdu) = enquire_link_resp(Status::ESME_ROK, 1);
+    assert!(pending.resolve(header, pdu).is_some());
+}
+
+#[tokio::test]
+async fn close() {
+    let pending = Arc::new(PendingRequests::new());
+    let first = pending.register(&enquire_link(1)).unwrap();
+    let second = pending.register(&enquire_link(2)).unwrap();
+
+    pending.close();
+    assert!(pending.is_empty());
+
+    assert!(matches!(first.wait(TIMEOUT).await, Err(RequestError::Closed)));
+    assert!(matches!(second.wait(TIMEOUT).await, Err(RequestError::Closed)));
+}
@@ -0,0 +1,276 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate chrono;
+extern crate short_msg;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use short_msg::smpp::prelude::*;
+
+const RECEIPT: &str =
+    "id:0123456789 sub:001 dlvrd:001 submit date:2203150930 done date:220315093145 stat:DELIVRD err:000 text:Hello";
+
+fn date(h: u32, m: u32, s: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2022, 3, 15)
+        .unwrap()
+        .and_hms_opt(h, m, s)
+        .unwrap()
+}
+
+fn deliver_sm(text: &str, tlv: Vec<TLV>) -> DeliverSm {
+    let esm_class = EsmClass {
+        message_mode: Default::default(),
+        message_type: EsmClassMessageType::DeliveryReceipt,
+        ansi41: Default::default(),
+        gsm: Default::default(),
+    };
+
+    DeliverSm {
+        service_type: COctet::default(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        dest_addr_ton: 0,
+        dest_addr_npi: 0,
+        destination_addr: COctet::new("esme").unwrap(),
+        esm_class: esm_class.into(),
+        protocol_id: 0,
+        priority_flag: 0,
+        schedule_delivery_time: COctet::default(),
+        validity_period: COctet::default(),
+        registered_delivery: 0,
+        replace_if_present_flag: 0,
+        data_coding: 0,
+        sm_default_msg_id: 0,
+        sm_length: text.len() as u8,
+        short_message: text.as_bytes().to_vec(),
+        msg_delivery_tlv: tlv,
+    }
+}
+
+fn receipted_message_id(id: &str) -> TLV {
+    let mut val = id.as_bytes().to_vec();
+    val.push(0);
+
+    TLV {
+        tag: Tag::ReceiptedMessageId,
+        len: val.len() as u16,
+        val,
+    }
+}
+
+fn network_error_code(code: u16) -> TLV {
+    let [hi, lo] = code.to_be_bytes();
+
+    TLV {
+        tag: Tag::NetworkErrorCode,
+        len: 3,
+        val: vec![3, hi, lo],
+    }
+}
+
+#[test]
+fn parse() {
+    let receipt: DeliveryReceipt = RECEIPT.parse().unwrap();
+    assert_eq!(receipt, DeliveryReceipt {
+        id: "0123456789".to_string(),
+        sub: Some(1),
+        dlvrd: Some(1),
+        submit_date: Some(date(9, 30, 0)),
+        done_date: Some(date(9, 31, 45)),
+        stat: MessageState::Delivered,
+        err: Some("000".to_string()),
+        text: "Hello".to_string(),
+    });
+}
+
+#[test]
+fn dates() {
+    let receipt: DeliveryReceipt = "id:1 submit date:2203150930 done date:220315093145 stat:DELIVRD"
+        .parse()
+        .unwrap();
+    assert_eq!(receipt.submit_date, Some(date(9, 30, 0)));
+    assert_eq!(receipt.done_date, Some(date(9, 31, 45)));
+
+    // Neither 10 nor 12 digits.
+    let receipt: DeliveryReceipt = "id:1 submit date:22031509 done date:2203150931450 stat:DELIVRD"
+        .parse()
+        .unwrap();
+    assert_eq!(receipt.submit_date, None);
+    assert_eq!(receipt.done_date, None);
+
+    // Out of range.
+    let receipt: DeliveryReceipt = "id:1 submit date:2213150930 stat:DELIVRD".parse().unwrap();
+    assert_eq!(receipt.submit_date, None);
+}
+
+#[test]
+fn display_writes_ten_digit_dates() {
+    let receipt: DeliveryReceipt = RECEIPT.parse().unwrap();
+    assert_eq!(
+        receipt.to_string(),
+        "id:0123456789 sub:001 dlvrd:001 submit date:2203150930 done date:2203150931 stat:DELIVRD err:000 text:Hello"
+    );
+
+    // The seconds are lost, but the text still parses.
+    let parsed: DeliveryReceipt = receipt.to_string().parse().unwrap();
+    assert_eq!(parsed.done_date, Some(date(9, 31, 0)));
+    assert_eq!(
+        DeliveryReceipt {
+            done_date: Some(date(9, 31, 0)),
+            ..receipt
+        },
+        parsed
+    );
+}
+
+#[test]
+fn field_name_variants() {
+    let receipt: DeliveryReceipt =
+        "ID:42 Sub:001 DLVRD:001 submit_date:2203150930 Done_Date:2203150931 Stat:DELIVRD Err:000 Text:Hi there"
+            .parse()
+            .unwrap();
+    assert_eq!(receipt.id, "42");
+    assert_eq!(receipt.submit_date, Some(date(9, 30, 0)));
+    assert_eq!(receipt.done_date, Some(date(9, 31, 0)));
+    assert_eq!(receipt.stat, MessageState::Delivered);
+    assert_eq!(receipt.text, "Hi there");
+
+    let receipt: DeliveryReceipt = "id:42 stat:DELIVRD text:stat:UNDELIV id:43".parse().unwrap();
+    assert_eq!(receipt.id, "42");
+    assert_eq!(receipt.stat, MessageState::Delivered);
+    assert_eq!(receipt.text, "stat:UNDELIV id:43");
+}
+
+#[test]
+fn lowercase_stat() {
+    let receipt: DeliveryReceipt = "id:42 stat:delivrd".parse().unwrap();
+    assert_eq!(receipt.stat, MessageState::Delivered);
+
+    let receipt: DeliveryReceipt = "id:42 stat:undeliverable".parse().unwrap();
+    assert_eq!(receipt.stat, MessageState::Undeliverable);
+}
+
+#[test]
+fn missing_fields() {
+    let receipt: DeliveryReceipt = "id:42 stat:EXPIRED err:".parse().unwrap();
+    assert_eq!(receipt.sub, None);
+    assert_eq!(receipt.dlvrd, None);
+    assert_eq!(receipt.submit_date, None);
+    assert_eq!(receipt.err, None);
+    assert_eq!(receipt.text, "");
+
+    assert_eq!(
+        "stat:DELIVRD".parse::<DeliveryReceipt>(),
+        Err(ReceiptError::MissingField("id"))
+    );
+    assert_eq!(
+        "id:42".parse::<DeliveryReceipt>(),
+        Err(ReceiptError::MissingField("stat"))
+    );
+}
+
+#[test]
+fn accepted_is_final() {
+    let receipt: DeliveryReceipt = "id:42 stat:ACCEPTD".parse().unwrap();
+    assert!(MessageState::Accepted.is_final());
+    assert_eq!(receipt.final_state(), Some(MessageState::Accepted));
+
+    let receipt: DeliveryReceipt = "id:42 stat:ENROUTE".parse().unwrap();
+    assert_eq!(receipt.final_state(), None);
+
+    for state in [MessageState::Scheduled, MessageState::Enroute, MessageState::Unknown] {
+        assert!(!state.is_final());
+    }
+    for state in [
+        MessageState::Delivered,
+        MessageState::Expired,
+        MessageState::Deleted,
+        MessageState::Undeliverable,
+        MessageState::Rejected,
+        MessageState::Skipped,
+    ] {
+        assert!(state.is_final());
+    }
+}
+
+#[test]
+fn from_deliver_sm() {
+    let tlv = vec![
+        receipted_message_id("0123456789"),
+        MessageState::Delivered.into(),
+        network_error_code(0),
+    ];
+    let receipt = DeliveryReceipt::from_deliver_sm(&deliver_sm(RECEIPT, tlv)).unwrap();
+    assert_eq!(receipt, RECEIPT.parse().unwrap());
+
+    // The text may come in the message_payload.
+    let payload = TLV {
+        tag: Tag::MessagePayload,
+        len: RECEIPT.len() as u16,
+        val: RECEIPT.as_bytes().to_vec(),
+    };
+    let receipt = DeliveryReceipt::from_deliver_sm(&deliver_sm("", vec![payload])).unwrap();
+    assert_eq!(receipt.id, "0123456789");
+}
+
+#[test]
+fn from_deliver_sm_fills_in_from_tlvs() {
+    let tlv = vec![receipted_message_id("4711"), MessageState::Expired.into()];
+    let receipt = DeliveryReceipt::from_deliver_sm(&deliver_sm("text:Hello", tlv)).unwrap();
+    assert_eq!(receipt.id, "4711");
+    assert_eq!(receipt.stat, MessageState::Expired);
+    assert_eq!(receipt.text, "Hello");
+
+    assert_eq!(
+        DeliveryReceipt::from_deliver_sm(&deliver_sm("stat:DELIVRD", vec![])),
+        Err(ReceiptError::MissingField("id"))
+    );
+}
+
+#[test]
+fn from_deliver_sm_cross_checks_tlvs() {
+    let check = |tlv: Vec<TLV>| DeliveryReceipt::from_deliver_sm(&deliver_sm(RECEIPT, tlv));
+
+    assert_eq!(
+        check(vec![receipted_message_id("9876543210")]),
+        Err(ReceiptError::Mismatch(Tag::ReceiptedMessageId))
+    );
+    assert_eq!(
+        check(vec![MessageState::Undeliverable.into()]),
+        Err(ReceiptError::Mismatch(Tag::MessageState))
+    );
+    assert_eq!(
+        check(vec![network_error_code(1)]),
+        Err(ReceiptError::Mismatch(Tag::NetworkErrorCode))
+    );
+
+    // The message_id in decimal in the text and in hexadecimal in the TLV.
+    assert!(check(vec![receipted_message_id("75BCD15")]).is_ok());
+    assert!(check(vec![receipted_message_id("75bcd15")]).is_ok());
+}
+
+#[test]
+fn from_deliver_sm_reads_out_of_range_message_state_as_unknown() {
+    let out_of_range = TLV {
+        tag: Tag::MessageState,
+        len: 1,
+        val: vec![0xFF],
+    };
+
+    let receipt = DeliveryReceipt::from_deliver_sm(&deliver_sm(RECEIPT, vec![out_of_range.clone()])).unwrap();
+    assert_eq!(receipt.stat, MessageState::Delivered);
+
+    let receipt = DeliveryReceipt::from_deliver_sm(&deliver_sm("id:1", vec![out_of_range])).unwrap();
+    assert_eq!(receipt.stat, MessageState::Unknown);
+}
+
+#[test]
+fn from_deliver_sm_needs_a_receipt() {
+    let mut deliver_sm = deliver_sm(RECEIPT, vec![]);
+    deliver_sm.esm_class = 0;
+    assert_eq!(
+        DeliveryReceipt::from_deliver_sm(&deliver_sm),
+        Err(ReceiptError::NotAReceipt)
+    );
+}
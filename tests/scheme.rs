@@ -0,0 +1,146 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate short_msg;
+
+use short_msg::smpp::prelude::*;
+
+#[test]
+fn every_octet_is_kept() {
+    for u in 0..=u8::MAX {
+        let dcs = DataCodingScheme::from(u);
+        assert_eq!(u8::from(dcs), u, "{dcs:?}");
+        assert_eq!(DataCodingScheme::from(u8::from(dcs)), dcs);
+    }
+}
+
+#[test]
+fn alphabet() {
+    assert_eq!(
+        DataCodingScheme::from(0x00),
+        DataCodingScheme::Alphabet(DataCoding::GSM)
+    );
+    assert_eq!(DataCodingScheme::from(0x00).text_coding(), Some(DataCoding::GSM));
+
+    let dcs = DataCodingScheme::from(0x08);
+    assert_eq!(dcs, DataCodingScheme::Alphabet(DataCoding::Ucs2));
+    assert_eq!(dcs.text_coding(), Some(DataCoding::Ucs2));
+    assert_eq!(dcs.message_class(), None);
+
+    let dcs = DataCodingScheme::from(0x04);
+    assert_eq!(dcs, DataCodingScheme::Alphabet(DataCoding::Reserved(0x04)));
+    assert_eq!(dcs.text_coding(), None);
+
+    assert_eq!(DataCodingScheme::from(DataCoding::Latin1), DataCodingScheme::from(0x03));
+}
+
+#[test]
+fn general_with_class() {
+    let dcs = DataCodingScheme::from(0x11);
+    assert_eq!(dcs, DataCodingScheme::General {
+        auto_delete: false,
+        compressed: false,
+        alphabet: DcsAlphabet::Gsm7,
+        class: Some(DcsMessageClass::Class1),
+    });
+    assert_eq!(dcs.text_coding(), Some(DataCoding::GSM));
+    assert_eq!(dcs.message_class(), Some(DcsMessageClass::Class1));
+
+    let dcs = DataCodingScheme::from(0x1A);
+    assert_eq!(dcs.text_coding(), Some(DataCoding::Ucs2));
+    assert_eq!(dcs.message_class(), Some(DcsMessageClass::Class2));
+
+    // 8-bit data.
+    let dcs = DataCodingScheme::from(0x16);
+    assert_eq!(dcs.text_coding(), None);
+    assert_eq!(dcs.message_class(), Some(DcsMessageClass::Class2));
+
+    // Compressed.
+    let dcs = DataCodingScheme::from(0x30);
+    assert_eq!(dcs, DataCodingScheme::General {
+        auto_delete: false,
+        compressed: true,
+        alphabet: DcsAlphabet::Gsm7,
+        class: Some(DcsMessageClass::Class0),
+    });
+    assert_eq!(dcs.text_coding(), None);
+
+    // The reserved alphabet.
+    assert_eq!(DataCodingScheme::from(0x1C), DataCodingScheme::Reserved(0x1C));
+    assert_eq!(DataCodingScheme::from(0x1C).text_coding(), None);
+}
+
+#[test]
+fn auto_delete() {
+    let dcs = DataCodingScheme::from(0x48);
+    assert_eq!(dcs, DataCodingScheme::General {
+        auto_delete: true,
+        compressed: false,
+        alphabet: DcsAlphabet::Ucs2,
+        class: None,
+    });
+    assert_eq!(dcs.text_coding(), Some(DataCoding::Ucs2));
+    assert_eq!(dcs.message_class(), None);
+
+    let dcs = DataCodingScheme::from(0x53);
+    assert_eq!(dcs, DataCodingScheme::General {
+        auto_delete: true,
+        compressed: false,
+        alphabet: DcsAlphabet::Gsm7,
+        class: Some(DcsMessageClass::Class3),
+    });
+    assert_eq!(dcs.text_coding(), Some(DataCoding::GSM));
+}
+
+#[test]
+fn message_waiting() {
+    let dcs = DataCodingScheme::from(0xE8);
+    assert_eq!(dcs, DataCodingScheme::MessageWaiting {
+        group: DcsWaitingGroup::StoreUcs2,
+        active: true,
+        indication: DcsIndicationType::Voicemail,
+    });
+    assert_eq!(dcs.text_coding(), Some(DataCoding::Ucs2));
+    assert_eq!(dcs.message_class(), None);
+
+    let dcs = DataCodingScheme::from(0xD1);
+    assert_eq!(dcs, DataCodingScheme::MessageWaiting {
+        group: DcsWaitingGroup::StoreGsm7,
+        active: false,
+        indication: DcsIndicationType::Fax,
+    });
+    assert_eq!(dcs.text_coding(), Some(DataCoding::GSM));
+
+    assert_eq!(DataCodingScheme::from(0xC0).text_coding(), Some(DataCoding::GSM));
+
+    // Bit 2 is reserved.
+    assert_eq!(DataCodingScheme::from(0xC4), DataCodingScheme::Reserved(0xC4));
+}
+
+#[test]
+fn message_class() {
+    let dcs = DataCodingScheme::from(0xF4);
+    assert_eq!(dcs, DataCodingScheme::MessageClass {
+        octet: true,
+        class: DcsMessageClass::Class0,
+    });
+    assert_eq!(dcs.text_coding(), None);
+    assert_eq!(dcs.message_class(), Some(DcsMessageClass::Class0));
+
+    let dcs = DataCodingScheme::from(0xF1);
+    assert_eq!(dcs.text_coding(), Some(DataCoding::GSM));
+    assert_eq!(dcs.message_class(), Some(DcsMessageClass::Class1));
+
+    // Bit 3 is reserved.
+    assert_eq!(DataCodingScheme::from(0xF8), DataCodingScheme::Reserved(0xF8));
+}
+
+#[test]
+fn reserved() {
+    for u in [0x80, 0x8F, 0x90, 0xA5, 0xBF] {
+        let dcs = DataCodingScheme::from(u);
+        assert_eq!(dcs, DataCodingScheme::Reserved(u));
+        assert_eq!(dcs.text_coding(), None);
+        assert_eq!(dcs.message_class(), None);
+    }
+}
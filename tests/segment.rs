@@ -0,0 +1,226 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate short_msg;
+
+use short_msg::smpp::prelude::*;
+
+const UDHI: u8 = EsmClassGsm::UDHI as u8;
+
+fn submit_sm() -> SubmitSm {
+    SubmitSm {
+        service_type: COctet::new("").unwrap(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        dest_addr_ton: 1,
+        dest_addr_npi: 1,
+        destination_addr: COctet::new("85287654321").unwrap(),
+        esm_class: 0b00000011,
+        protocol_id: 0,
+        priority_flag: 0,
+        schedule_delivery_time: COctet::new("").unwrap(),
+        validity_period: COctet::new("").unwrap(),
+        registered_delivery: 1,
+        replace_if_present_flag: 0,
+        data_coding: 0,
+        sm_default_msg_id: 0,
+        sm_length: 0,
+        short_message: vec![],
+        msg_submission_tlv: vec![],
+    }
+}
+
+fn tlv(pdu: &SubmitSm, tag: Tag) -> Option<&[u8]> {
+    pdu.msg_submission_tlv
+        .iter()
+        .find(|tlv| tlv.tag == tag)
+        .map(|tlv| tlv.val.as_slice())
+}
+
+/// Checks the parts of a message concatenated by UDH and returns their text.
+fn udh_parts(pdus: &[SubmitSm], reference: u16) -> Vec<String> {
+    let total = u8::try_from(pdus.len()).unwrap();
+    let mut parts = vec![];
+    for (sequence, pdu) in (1..).zip(pdus) {
+        assert!(pdu.short_message.len() <= 140);
+        assert_eq!(usize::from(pdu.sm_length), pdu.short_message.len());
+        assert_eq!(pdu.esm_class, 0b00000011 | UDHI);
+        assert!(pdu.msg_submission_tlv.is_empty());
+
+        let data_coding = DataCoding::from(pdu.data_coding);
+        let (udh, text) = if data_coding == DataCoding::GSM {
+            UserDataHeader::split_gsm(&pdu.short_message).unwrap()
+        } else {
+            let (udh, data) = UserDataHeader::split(&pdu.short_message).unwrap();
+            let text = decode_text(data_coding, data).unwrap();
+            (udh, text)
+        };
+        assert_eq!(udh.concatenation(), Some((reference, total, sequence)));
+
+        parts.push(text);
+    }
+
+    parts
+}
+
+#[test]
+fn single() {
+    let pdus = submit_sm()
+        .segment("hello", Segmentation::Udh { reference: 7 })
+        .unwrap();
+    assert_eq!(pdus.len(), 1);
+
+    let pdu = &pdus[0];
+    assert_eq!(pdu.esm_class, 0b00000011);
+    assert_eq!(pdu.data_coding, u8::from(DataCoding::GSM));
+    assert_eq!(pdu.short_message, encode_text("hello").bytes);
+    assert_eq!(usize::from(pdu.sm_length), pdu.short_message.len());
+    assert!(pdu.msg_submission_tlv.is_empty());
+
+    // A full single short message.
+    let text = "a".repeat(160);
+    let pdus = submit_sm().segment(&text, Segmentation::Sar { reference: 7 }).unwrap();
+    assert_eq!(pdus.len(), 1);
+    assert_eq!(pdus[0].sm_length, 140);
+    assert!(pdus[0].msg_submission_tlv.is_empty());
+}
+
+#[test]
+fn udh() {
+    let text = "a".repeat(400);
+    let pdus = submit_sm().segment(&text, Segmentation::Udh { reference: 7 }).unwrap();
+    assert_eq!(pdus.len(), 3);
+
+    let parts = udh_parts(&pdus, 7);
+    assert_eq!(parts[0].len(), 153);
+    assert_eq!(pdus[0].short_message.len(), 140);
+    assert_eq!(parts.concat(), text);
+}
+
+#[test]
+fn udh16() {
+    let text = "a".repeat(400);
+    let pdus = submit_sm()
+        .segment(&text, Segmentation::Udh16 { reference: 0x1234 })
+        .unwrap();
+    assert_eq!(pdus.len(), 3);
+
+    let parts = udh_parts(&pdus, 0x1234);
+    assert_eq!(parts[0].len(), 152);
+    assert_eq!(parts.concat(), text);
+}
+
+#[test]
+fn ucs2() {
+    let text = "你".repeat(100);
+    let pdus = submit_sm().segment(&text, Segmentation::Udh { reference: 7 }).unwrap();
+    assert_eq!(pdus.len(), 2);
+    assert!(pdus.iter().all(|pdu| pdu.data_coding == u8::from(DataCoding::Ucs2)));
+
+    let parts = udh_parts(&pdus, 7);
+    assert_eq!(parts[0].chars().count(), 67);
+    assert_eq!(parts.concat(), text);
+}
+
+#[test]
+fn escape_is_not_split() {
+    let a = "a".repeat(152);
+    let text = format!("{a}€{a}");
+    let pdus = submit_sm().segment(&text, Segmentation::Udh { reference: 7 }).unwrap();
+
+    let parts = udh_parts(&pdus, 7);
+    assert_eq!(parts, [a.clone(), format!("€{}", &a[..151]), "a".to_string()]);
+}
+
+#[test]
+fn surrogate_pair_is_not_split() {
+    let a = "你".repeat(66);
+    let text = format!("{a}😀{a}");
+    let pdus = submit_sm().segment(&text, Segmentation::Udh { reference: 7 }).unwrap();
+
+    // Each part decodes on its own, so no pair is split.
+    let parts = udh_parts(&pdus, 7);
+    assert_eq!(parts[0], a);
+    assert!(parts[1].starts_with('😀'));
+    assert_eq!(parts.concat(), text);
+}
+
+#[test]
+fn sar() {
+    let text = "a".repeat(400);
+    let pdus = submit_sm()
+        .segment(&text, Segmentation::Sar { reference: 0x1234 })
+        .unwrap();
+    assert_eq!(pdus.len(), 3);
+
+    let mut parts = vec![];
+    for (sequence, pdu) in (1..).zip(&pdus) {
+        assert!(pdu.short_message.len() <= 140);
+        assert_eq!(usize::from(pdu.sm_length), pdu.short_message.len());
+        assert_eq!(pdu.esm_class, 0b00000011);
+
+        assert_eq!(tlv(pdu, Tag::SarMsgRefNum), Some(&b"\x12\x34"[..]));
+        assert_eq!(tlv(pdu, Tag::SarTotalSegments), Some(&[3][..]));
+        assert_eq!(tlv(pdu, Tag::SarSegmentSeqnum), Some(&[sequence][..]));
+
+        parts.push(decode_text(DataCoding::GSM, &pdu.short_message).unwrap());
+    }
+
+    // Without a UDH, each part holds a whole short message.
+    assert_eq!(parts[0].len(), 160);
+    assert_eq!(parts.concat(), text);
+}
+
+#[test]
+fn payload() {
+    let text = "a".repeat(400);
+    let pdus = submit_sm().segment(&text, Segmentation::Payload).unwrap();
+    assert_eq!(pdus.len(), 1);
+
+    let pdu = &pdus[0];
+    assert!(pdu.short_message.is_empty());
+    assert_eq!(pdu.sm_length, 0);
+    assert_eq!(pdu.esm_class, 0b00000011);
+    assert_eq!(pdu.data_coding, u8::from(DataCoding::GSM));
+
+    let payload = &pdu.msg_submission_tlv[0];
+    assert_eq!(payload.tag, Tag::MessagePayload);
+    assert_eq!(usize::from(payload.len), payload.val.len());
+    assert_eq!(decode_text(DataCoding::GSM, &payload.val).unwrap(), text);
+
+    // Even a short text goes in the payload.
+    let pdus = submit_sm().segment("hello", Segmentation::Payload).unwrap();
+    assert!(pdus[0].short_message.is_empty());
+    assert_eq!(
+        tlv(&pdus[0], Tag::MessagePayload),
+        Some(&encode_text("hello").bytes[..])
+    );
+}
+
+#[test]
+fn too_long() {
+    // 255 parts of 153 septets.
+    let text = "a".repeat(255 * 153);
+    let pdus = submit_sm().segment(&text, Segmentation::Udh { reference: 7 }).unwrap();
+    assert_eq!(pdus.len(), 255);
+
+    let text = "a".repeat(255 * 153 + 1);
+    assert_eq!(
+        submit_sm().segment(&text, Segmentation::Udh { reference: 7 }).err(),
+        Some(SegmentError::TooLong)
+    );
+    assert_eq!(
+        submit_sm()
+            .segment(&text, Segmentation::Sar { reference: 7 })
+            .map(|pdus| pdus.len()),
+        Ok(244)
+    );
+
+    // 74899 septets take 65537 octets.
+    let text = "a".repeat(74899);
+    assert_eq!(
+        submit_sm().segment(&text, Segmentation::Payload).err(),
+        Some(SegmentError::TooLong)
+    );
+}
@@ -0,0 +1,197 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+#![cfg(feature = "server")]
+
+extern crate futures;
+extern crate short_msg;
+extern crate tokio;
+extern crate tokio_util;
+
+use std::time::Duration;
+
+use futures::{future::BoxFuture, SinkExt, StreamExt};
+use short_msg::smpp::{
+    codec::SmppCodec,
+    prelude::*,
+    server::{Authenticator, BindMode, Config, Error, Handler, Reply, Server, Session},
+    session::RequestError,
+};
+use tokio::{io::AsyncWriteExt, net::TcpStream, time};
+use tokio_util::codec::Framed;
+
+type Esme = Framed<TcpStream, SmppCodec>;
+
+struct Password;
+
+impl Authenticator for Password {
+    fn authenticate<'a>(&'a self, _: BindMode, bind: &'a Bind) -> BoxFuture<'a, Result<(), Status>> {
+        Box::pin(async move {
+            if bind.password.as_ref().to_bytes() == b"secret" {
+                Ok(())
+            } else {
+                Err(Status::ESME_RINVPASWD)
+            }
+        })
+    }
+}
+
+struct Accept;
+
+impl Handler for Accept {
+    fn submit_sm<'a>(&'a self, _: &'a Session, _: SubmitSm) -> BoxFuture<'a, Reply<SubmitSmResp>> {
+        Box::pin(async {
+            Reply::ok(SubmitSmResp {
+                message_id: COctet::new("1").unwrap(),
+
+                #[cfg(feature = "v5")]
+                msg_submission_resp_tlv: vec![],
+            })
+        })
+    }
+}
+
+async fn next(esme: &mut Esme) -> Option<(Header, Pdu)> {
+    time::timeout(Duration::from_secs(5), esme.next())
+        .await
+        .expect("no PDU from the server")
+        .map(Result::unwrap)
+}
+
+async fn start() -> Esme {
+    let server = Server::bind("127.0.0.1:0", Password, Accept, Config::default())
+        .await
+        .unwrap();
+    let addr = server.local_addr().unwrap();
+    tokio::spawn(async move { server.run().await });
+
+    Framed::new(TcpStream::connect(addr).await.unwrap(), SmppCodec::new())
+}
+
+async fn bind(esme: &mut Esme, password: &str) -> Header {
+    let bind = Bind {
+        system_id: COctet::new("esme").unwrap(),
+        password: COctet::new(password).unwrap(),
+        system_type: COctet::default(),
+        interface_version: 0x34,
+        addr_ton: 0,
+        addr_npi: 0,
+        address_range: COctet::default(),
+    };
+    let header = Header::new(Id::BindTransmitter, Status::ESME_ROK, 1);
+    esme.send((header, Pdu::BindTransmitter(bind))).await.unwrap();
+
+    let (header, _) = next(esme).await.unwrap();
+    assert_eq!(header.command_id, Id::BindTransmitterResp);
+    header
+}
+
+#[tokio::test]
+async fn bind_checks_credentials() {
+    let mut esme = start().await;
+    assert_eq!(bind(&mut esme, "secret").await.command_status, Status::ESME_ROK);
+
+    let mut esme = start().await;
+    assert_eq!(bind(&mut esme, "guess").await.command_status, Status::ESME_RINVPASWD);
+}
+
+#[tokio::test]
+async fn malformed_body_is_rejected() {
+    let mut esme = start().await;
+    bind(&mut esme, "secret").await;
+
+    // A submit_sm whose body ends in the middle of the service_type.
+    let mut bytes = vec![];
+    bytes.extend(19u32.to_be_bytes());
+    bytes.extend(0x00000004u32.to_be_bytes());
+    bytes.extend(0u32.to_be_bytes());
+    bytes.extend(2u32.to_be_bytes());
+    bytes.extend(b"CMT");
+    esme.get_mut().write_all(&bytes).await.unwrap();
+
+    let (header, _) = next(&mut esme).await.unwrap();
+    assert_eq!(header.command_id, Id::SubmitSmResp);
+    assert_ne!(header.command_status, Status::ESME_ROK);
+    assert_eq!(header.sequence_number, 2);
+
+    // The session goes on.
+    let header = Header::new(Id::EnquireLink, Status::ESME_ROK, 3);
+    esme.send((header, Pdu::EnquireLink(EnquireLink {}))).await.unwrap();
+
+    let (header, pdu) = next(&mut esme).await.unwrap();
+    assert!(matches!(pdu, Pdu::EnquireLinkResp(_)));
+    assert_eq!(header.sequence_number, 3);
+}
+
+#[tokio::test]
+async fn malformed_response_fails_request() {
+    let server = Server::bind("127.0.0.1:0", Password, Accept, Config::default())
+        .await
+        .unwrap();
+    let addr = server.local_addr().unwrap();
+    let sessions = server.sessions();
+    tokio::spawn(async move { server.run().await });
+
+    let mut esme = Framed::new(TcpStream::connect(addr).await.unwrap(), SmppCodec::new());
+    let bind = Bind {
+        system_id: COctet::new("esme").unwrap(),
+        password: COctet::new("secret").unwrap(),
+        system_type: COctet::default(),
+        interface_version: 0x34,
+        addr_ton: 0,
+        addr_npi: 0,
+        address_range: COctet::default(),
+    };
+    let header = Header::new(Id::BindReceiver, Status::ESME_ROK, 1);
+    esme.send((header, Pdu::BindReceiver(bind))).await.unwrap();
+    let (header, _) = next(&mut esme).await.unwrap();
+    assert_eq!(header.command_status, Status::ESME_ROK);
+
+    let session = sessions.receivers().pop().unwrap();
+    let deliver_sm = DeliverSm {
+        service_type: COctet::default(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        dest_addr_ton: 0,
+        dest_addr_npi: 0,
+        destination_addr: COctet::new("esme").unwrap(),
+        esm_class: 0,
+        protocol_id: 0,
+        priority_flag: 0,
+        schedule_delivery_time: COctet::default(),
+        validity_period: COctet::default(),
+        registered_delivery: 0,
+        replace_if_present_flag: 0,
+        data_coding: 0,
+        sm_default_msg_id: 0,
+        sm_length: 0,
+        short_message: vec![],
+        msg_delivery_tlv: vec![],
+    };
+
+    let esme = async {
+        let (header, pdu) = next(&mut esme).await.unwrap();
+        assert!(matches!(pdu, Pdu::DeliverSm(_)));
+
+        // A deliver_sm_resp whose message_id is not terminated.
+        let mut bytes = vec![];
+        bytes.extend(20u32.to_be_bytes());
+        bytes.extend(0x80000005u32.to_be_bytes());
+        bytes.extend(0u32.to_be_bytes());
+        bytes.extend(header.sequence_number.to_be_bytes());
+        bytes.extend(b"4711");
+        esme.get_mut().write_all(&bytes).await.unwrap();
+    };
+
+    // The request fails at once rather than after the request timeout.
+    let (deliver, _) = time::timeout(Duration::from_secs(1), async {
+        tokio::join!(session.deliver(deliver_sm), esme)
+    })
+    .await
+    .unwrap();
+    assert!(matches!(
+        deliver,
+        Err(Error::Request(RequestError::Malformed(Id::DeliverSmResp)))
+    ));
+}
@@ -0,0 +1,260 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+#![cfg(all(feature = "client", feature = "simulator"))]
+
+extern crate futures;
+extern crate short_msg;
+extern crate tokio;
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use short_msg::smpp::{
+    client::{Backoff, BindMode, Config, Error, EsmeClient, Supervisor},
+    prelude::*,
+    simulator::{self, Simulator},
+};
+use tokio::{
+    net::TcpListener,
+    time::{self, Instant},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn bind(password: &str) -> Bind {
+    Bind {
+        system_id: COctet::new("test").unwrap(),
+        password: COctet::new(password).unwrap(),
+        system_type: COctet::default(),
+        interface_version: 0x34,
+        addr_ton: 0,
+        addr_npi: 0,
+        address_range: COctet::default(),
+    }
+}
+
+fn config() -> Config {
+    Config {
+        request_timeout: TIMEOUT,
+        enquire_link_interval: None,
+        ..Config::default()
+    }
+}
+
+fn submit_sm(registered_delivery: u8) -> SubmitSm {
+    let EncodedText { data_coding, bytes, .. } = encode_text("hello");
+
+    SubmitSm {
+        service_type: COctet::default(),
+        source_addr_ton: 0,
+        source_addr_npi: 0,
+        source_addr: COctet::new("test").unwrap(),
+        dest_addr_ton: 1,
+        dest_addr_npi: 1,
+        destination_addr: COctet::new("85212345678").unwrap(),
+        esm_class: 0,
+        protocol_id: 0,
+        priority_flag: 0,
+        schedule_delivery_time: COctet::default(),
+        validity_period: COctet::default(),
+        registered_delivery,
+        replace_if_present_flag: 0,
+        data_coding: data_coding.into(),
+        sm_default_msg_id: 0,
+        sm_length: bytes.len() as u8,
+        short_message: bytes,
+        msg_submission_tlv: vec![],
+    }
+}
+
+async fn start() -> Simulator {
+    Simulator::start(simulator::Config {
+        receipt_delay: Some(Duration::from_millis(10)),
+        ..simulator::Config::default()
+    })
+    .await
+    .unwrap()
+}
+
+async fn connect(simulator: &Simulator, mode: BindMode, config: Config) -> EsmeClient {
+    EsmeClient::connect(simulator.local_addr(), mode, bind("test"), config)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn bind_checks_credentials() {
+    let simulator = start().await;
+
+    let client = connect(&simulator, BindMode::Transceiver, config()).await;
+    assert_eq!(client.bind_resp().system_id.as_ref().to_bytes(), b"SIMULATOR");
+    assert_eq!(simulator.sessions().list().len(), 1);
+
+    let result = EsmeClient::connect(simulator.local_addr(), BindMode::Transceiver, bind("guess"), config()).await;
+    assert!(matches!(result, Err(Error::Status(Status::ESME_RINVPASWD))));
+
+    let mut wrong_system_id = bind("test");
+    wrong_system_id.system_id = COctet::new("other").unwrap();
+    let result = EsmeClient::connect(simulator.local_addr(), BindMode::Transceiver, wrong_system_id, config()).await;
+    assert!(matches!(result, Err(Error::Status(Status::ESME_RINVSYSID))));
+}
+
+#[tokio::test]
+async fn supervisor_stops_on_rejected_credentials() {
+    let simulator = start().await;
+    let supervisor = Supervisor::start(
+        simulator.local_addr(),
+        BindMode::Transceiver,
+        bind("guess"),
+        config(),
+        Backoff::default(),
+    );
+
+    let result = time::timeout(TIMEOUT, supervisor.submit(submit_sm(0))).await.unwrap();
+    assert!(matches!(result, Err(Error::Status(Status::ESME_RINVPASWD))));
+    assert!(!supervisor.is_bound());
+    assert!(simulator.submitted().is_empty());
+}
+
+#[tokio::test]
+async fn submit() {
+    let simulator = start().await;
+    let client = connect(&simulator, BindMode::Transmitter, config()).await;
+
+    let first = client.submit(submit_sm(0)).await.unwrap();
+    let second = client.submit(submit_sm(0)).await.unwrap();
+    assert_ne!(first.message_id.as_ref(), second.message_id.as_ref());
+
+    let submitted = simulator.submitted();
+    assert_eq!(submitted.len(), 2);
+    assert_eq!(submitted[0].0.as_bytes(), first.message_id.as_ref().to_bytes());
+    assert_eq!(submitted[0].1.short_message, encode_text("hello").bytes);
+}
+
+#[tokio::test]
+async fn receipt() {
+    let simulator = start().await;
+    let mut client = connect(&simulator, BindMode::Transceiver, config()).await;
+    let mut deliveries = client.deliveries().unwrap();
+
+    let resp = client.submit(submit_sm(0x01)).await.unwrap();
+    let deliver_sm = time::timeout(TIMEOUT, deliveries.next()).await.unwrap().unwrap();
+
+    let receipt = DeliveryReceipt::from_deliver_sm(&deliver_sm).unwrap();
+    assert_eq!(receipt.id.as_bytes(), resp.message_id.as_ref().to_bytes());
+    assert_eq!(receipt.stat, MessageState::Delivered);
+    assert_eq!(receipt.final_state(), Some(MessageState::Delivered));
+    assert_eq!(receipt.text, "hello");
+    assert_eq!(deliver_sm.destination_addr.as_ref().to_bytes(), b"test");
+}
+
+#[tokio::test]
+async fn receipt_to_receiver() {
+    let simulator = start().await;
+    let transmitter = connect(&simulator, BindMode::Transmitter, config()).await;
+    let mut receiver = connect(&simulator, BindMode::Receiver, config()).await;
+    let mut deliveries = receiver.deliveries().unwrap();
+
+    let resp = transmitter.submit(submit_sm(0x01)).await.unwrap();
+    let deliver_sm = time::timeout(TIMEOUT, deliveries.next()).await.unwrap().unwrap();
+    let receipt = DeliveryReceipt::from_deliver_sm(&deliver_sm).unwrap();
+    assert_eq!(receipt.id.as_bytes(), resp.message_id.as_ref().to_bytes());
+}
+
+#[tokio::test]
+async fn inject_error_backs_off() {
+    let simulator = start().await;
+    let backoff = Duration::from_millis(200);
+    let client = connect(&simulator, BindMode::Transmitter, Config {
+        throttle_backoff: backoff,
+        ..config()
+    })
+    .await;
+
+    simulator.inject_error(Status::ESME_RTHROTTLED);
+    let result = client.submit(submit_sm(0)).await;
+    assert!(matches!(result, Err(Error::Status(Status::ESME_RTHROTTLED))));
+    let throttled = Instant::now();
+
+    // The next message waits for the backoff period to end.
+    client.submit(submit_sm(0)).await.unwrap();
+    assert!(throttled.elapsed() >= backoff - Duration::from_millis(20));
+    assert_eq!(simulator.submitted().len(), 1);
+}
+
+#[tokio::test]
+async fn unbind() {
+    let simulator = start().await;
+    let client = connect(&simulator, BindMode::Transceiver, config()).await;
+    assert_eq!(simulator.sessions().list().len(), 1);
+
+    client.unbind().await.unwrap();
+    assert!(client.is_closed());
+
+    time::timeout(TIMEOUT, async {
+        while !simulator.sessions().list().is_empty() {
+            time::sleep(Duration::from_millis(1)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn outbind() {
+    let simulator = start().await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+    let outbind = OutBind {
+        system_id: COctet::new("SIMULATOR").unwrap(),
+        password: COctet::new("secret").unwrap(),
+    };
+
+    let (client, _) = tokio::join!(
+        EsmeClient::accept_outbind(&listener, &outbind, bind("test"), config()),
+        simulator.outbind(listener.local_addr().unwrap(), outbind.clone()),
+    );
+    let mut client = client.unwrap();
+    let mut deliveries = client.deliveries().unwrap();
+
+    let sessions = simulator.sessions().receivers();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].mode(), Some(BindMode::Receiver));
+
+    // The session is served like any other.
+    let transmitter = connect(&simulator, BindMode::Transmitter, config()).await;
+    transmitter.submit(submit_sm(0x01)).await.unwrap();
+    let deliver_sm = time::timeout(TIMEOUT, deliveries.next()).await.unwrap().unwrap();
+    assert!(DeliveryReceipt::from_deliver_sm(&deliver_sm).is_ok());
+}
+
+#[tokio::test]
+async fn receipt_text_is_decoded() {
+    let simulator = start().await;
+    let mut client = connect(&simulator, BindMode::Transceiver, config()).await;
+    let mut deliveries = client.deliveries().unwrap();
+
+    for text in ["你好，世界", "The quick brown fox jumps over the lazy dog"] {
+        let EncodedText { data_coding, bytes, .. } = encode_text(text);
+        let submit_sm = SubmitSm {
+            data_coding: data_coding.into(),
+            sm_length: bytes.len() as u8,
+            short_message: bytes,
+            ..submit_sm(0x01)
+        };
+
+        client.submit(submit_sm).await.unwrap();
+        let deliver_sm = time::timeout(TIMEOUT, deliveries.next()).await.unwrap().unwrap();
+        let receipt = DeliveryReceipt::from_deliver_sm(&deliver_sm).unwrap();
+        // The receipt is in ASCII, and the parser trims the text.
+        let expected: String = text
+            .chars()
+            .take(20)
+            .map(|c| if c.is_ascii() { c } else { '?' })
+            .collect();
+        assert_eq!(deliver_sm.data_coding, u8::from(DataCoding::Ascii));
+        assert!(deliver_sm.short_message.is_ascii());
+        assert_eq!(receipt.text, expected.trim_end());
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate short_msg;
+
+use short_msg::smpp::{
+    prelude::*,
+    session::{BindMode, Role, State, StateMachine},
+};
+
+fn request(id: Id) -> Header {
+    Header::new(id, Status::ESME_ROK, 1)
+}
+
+fn response(id: Id, status: Status) -> Header {
+    Header::new(id.response(), status, 1)
+}
+
+/// Drives a state machine through a successful bind in `mode`.
+fn bound(role: Role, mode: BindMode) -> StateMachine {
+    let bind = match mode {
+        BindMode::Transmitter => Id::BindTransmitter,
+        BindMode::Receiver => Id::BindReceiver,
+        BindMode::Transceiver => Id::BindTransceiver,
+    };
+
+    let mut state = StateMachine::new(role);
+    match role {
+        Role::Esme => {
+            state.outbound(&request(bind.clone())).unwrap();
+            state.inbound(&response(bind, Status::ESME_ROK)).unwrap();
+        }
+        Role::Smsc => {
+            state.inbound(&request(bind.clone())).unwrap();
+            state.outbound(&response(bind, Status::ESME_ROK)).unwrap();
+        }
+    }
+
+    assert_eq!(state.state(), mode.state());
+    state
+}
+
+#[test]
+fn bind() {
+    for mode in [BindMode::Transmitter, BindMode::Receiver, BindMode::Transceiver] {
+        let state = bound(Role::Esme, mode);
+        assert_eq!(state.role(), Role::Esme);
+        assert!(state.state().is_bound());
+
+        let state = bound(Role::Smsc, mode);
+        assert_eq!(state.role(), Role::Smsc);
+        assert!(state.state().is_bound());
+    }
+}
+
+#[test]
+fn failed_bind_stays_open() {
+    let mut state = StateMachine::new(Role::Esme);
+    state.outbound(&request(Id::BindTransmitter)).unwrap();
+    state
+        .inbound(&response(Id::BindTransmitter, Status::ESME_RBINDFAIL))
+        .unwrap();
+    assert_eq!(state.state(), State::Open);
+
+    // The bind may be retried.
+    state.outbound(&request(Id::BindTransmitter)).unwrap();
+}
+
+#[test]
+fn open() {
+    let mut esme = StateMachine::new(Role::Esme);
+    assert_eq!(esme.outbound(&request(Id::SubmitSm)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(esme.outbound(&request(Id::Unbind)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(esme.outbound(&request(Id::Outbind)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(esme.inbound(&request(Id::DeliverSm)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(esme.inbound(&request(Id::Outbind)), Ok(()));
+    assert_eq!(esme.outbound(&request(Id::EnquireLink)), Ok(()));
+    assert_eq!(esme.state(), State::Open);
+
+    let mut smsc = StateMachine::new(Role::Smsc);
+    assert_eq!(smsc.outbound(&request(Id::Outbind)), Ok(()));
+    assert_eq!(
+        smsc.outbound(&request(Id::BindTransmitter)),
+        Err(Status::ESME_RINVBNDSTS)
+    );
+    assert_eq!(smsc.inbound(&request(Id::SubmitSm)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(smsc.inbound(&request(Id::EnquireLink)), Ok(()));
+}
+
+#[test]
+fn esme_outbound() {
+    let transmit = [
+        Id::SubmitSm,
+        Id::SubmitMulti,
+        Id::DataSm,
+        Id::QuerySm,
+        Id::CancelSm,
+        Id::ReplaceSm,
+    ];
+
+    for (mode, allowed) in [
+        (BindMode::Transmitter, true),
+        (BindMode::Receiver, false),
+        (BindMode::Transceiver, true),
+    ] {
+        let mut state = bound(Role::Esme, mode);
+        for id in transmit.clone() {
+            let expected = if allowed { Ok(()) } else { Err(Status::ESME_RINVBNDSTS) };
+            assert_eq!(state.outbound(&request(id.clone())), expected, "{:?} in {:?}", id, mode);
+        }
+
+        // Never sent by an ESME.
+        assert_eq!(state.outbound(&request(Id::DeliverSm)), Err(Status::ESME_RINVBNDSTS));
+        assert_eq!(
+            state.outbound(&request(Id::AlertNotification)),
+            Err(Status::ESME_RINVBNDSTS)
+        );
+        assert_eq!(state.outbound(&request(Id::Outbind)), Err(Status::ESME_RINVBNDSTS));
+
+        assert_eq!(state.outbound(&request(Id::EnquireLink)), Ok(()));
+        assert_eq!(state.outbound(&request(Id::BindTransmitter)), Err(Status::ESME_RALYBND));
+        assert_eq!(state.state(), mode.state());
+    }
+}
+
+#[test]
+fn esme_inbound() {
+    for (mode, allowed) in [
+        (BindMode::Transmitter, false),
+        (BindMode::Receiver, true),
+        (BindMode::Transceiver, true),
+    ] {
+        let mut state = bound(Role::Esme, mode);
+        for id in [Id::DeliverSm, Id::DataSm, Id::AlertNotification] {
+            let expected = if allowed { Ok(()) } else { Err(Status::ESME_RINVBNDSTS) };
+            assert_eq!(state.inbound(&request(id.clone())), expected, "{:?} in {:?}", id, mode);
+        }
+
+        // Never sent by an SMSC.
+        assert_eq!(state.inbound(&request(Id::SubmitSm)), Err(Status::ESME_RINVBNDSTS));
+        assert_eq!(
+            state.inbound(&request(Id::BindTransmitter)),
+            Err(Status::ESME_RINVBNDSTS)
+        );
+
+        assert_eq!(state.inbound(&request(Id::EnquireLink)), Ok(()));
+    }
+}
+
+#[test]
+fn smsc() {
+    let mut state = bound(Role::Smsc, BindMode::Transmitter);
+    assert_eq!(state.inbound(&request(Id::SubmitSm)), Ok(()));
+    assert_eq!(state.inbound(&request(Id::BindReceiver)), Err(Status::ESME_RALYBND));
+    assert_eq!(state.outbound(&request(Id::DeliverSm)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(state.outbound(&request(Id::SubmitSm)), Err(Status::ESME_RINVBNDSTS));
+
+    let mut state = bound(Role::Smsc, BindMode::Receiver);
+    assert_eq!(state.inbound(&request(Id::SubmitSm)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(state.outbound(&request(Id::DeliverSm)), Ok(()));
+    assert_eq!(state.outbound(&request(Id::DataSm)), Ok(()));
+}
+
+#[test]
+fn unbind() {
+    let mut state = bound(Role::Esme, BindMode::Transceiver);
+    state.inbound(&request(Id::Unbind)).unwrap();
+    assert_eq!(state.state(), State::Unbound);
+
+    // Only responses may still be exchanged.
+    assert_eq!(state.outbound(&response(Id::Unbind, Status::ESME_ROK)), Ok(()));
+    assert_eq!(state.outbound(&response(Id::SubmitSm, Status::ESME_ROK)), Ok(()));
+    assert_eq!(state.inbound(&response(Id::SubmitSm, Status::ESME_ROK)), Ok(()));
+    assert_eq!(state.outbound(&request(Id::SubmitSm)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(state.outbound(&request(Id::EnquireLink)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(state.outbound(&request(Id::Unbind)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(
+        state.outbound(&request(Id::BindTransceiver)),
+        Err(Status::ESME_RINVBNDSTS)
+    );
+    assert_eq!(state.state(), State::Unbound);
+}
+
+#[test]
+fn closed() {
+    let mut state = bound(Role::Esme, BindMode::Transceiver);
+    state.close();
+    assert_eq!(state.state(), State::Closed);
+
+    assert_eq!(state.outbound(&request(Id::SubmitSm)), Err(Status::ESME_RINVBNDSTS));
+    assert_eq!(
+        state.inbound(&response(Id::SubmitSm, Status::ESME_ROK)),
+        Err(Status::ESME_RINVBNDSTS)
+    );
+}
+
+#[test]
+fn unknown_command_id() {
+    let mut state = bound(Role::Esme, BindMode::Transceiver);
+    assert_eq!(
+        state.inbound(&request(Id::Reserved(0x00010200))),
+        Err(Status::ESME_RINVCMDID)
+    );
+    assert_eq!(
+        state.inbound(&request(Id::Reserved(0x80010200))),
+        Err(Status::ESME_RINVCMDID)
+    );
+}
+
+#[test]
+fn reject() {
+    // A known request gets its own response.
+    let header = Header::new(Id::SubmitSm, Status::ESME_ROK, 7);
+    let (header, pdu) = StateMachine::reject(&header, Status::ESME_RINVBNDSTS).unwrap();
+    assert_eq!(header.command_id, Id::SubmitSmResp);
+    assert_eq!(header.command_status, Status::ESME_RINVBNDSTS);
+    assert_eq!(header.sequence_number, 7);
+    assert!(matches!(&pdu, Pdu::Raw { command_id: Id::SubmitSmResp, body } if body.is_empty()));
+
+    // Encoded, it is a bare header.
+    let bytes = pdu
+        .encode_with_status(header.command_status, header.sequence_number)
+        .unwrap();
+    assert_eq!(bytes.len(), 16);
+
+    // An unknown one gets a generic_nack.
+    let header = Header::new(Id::Reserved(0x00010200), Status::ESME_ROK, 8);
+    let (header, pdu) = StateMachine::reject(&header, Status::ESME_RINVCMDID).unwrap();
+    assert_eq!(header.command_id, Id::GenericNack);
+    assert_eq!(header.command_status, Status::ESME_RINVCMDID);
+    assert_eq!(header.sequence_number, 8);
+    assert!(matches!(pdu, Pdu::Raw { command_id: Id::GenericNack, body } if body.is_empty()));
+
+    // Responses are never answered.
+    let header = Header::new(Id::SubmitSmResp, Status::ESME_ROK, 9);
+    assert!(StateMachine::reject(&header, Status::ESME_RINVBNDSTS).is_none());
+    let header = Header::new(Id::GenericNack, Status::ESME_RINVCMDID, 9);
+    assert!(StateMachine::reject(&header, Status::ESME_RINVBNDSTS).is_none());
+}
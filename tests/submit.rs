@@ -0,0 +1,145 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate bincode;
+extern crate short_msg;
+
+use short_msg::smpp::prelude::*;
+
+fn config() -> impl bincode::config::Config {
+    bincode::config::standard().with_big_endian().with_fixed_int_encoding()
+}
+
+fn submit_multi(dest_address: Vec<DestAddress>) -> SubmitMulti {
+    SubmitMulti {
+        service_type: COctet::new("").unwrap(),
+        source_addr_ton: 1,
+        source_addr_npi: 1,
+        source_addr: COctet::new("85212345678").unwrap(),
+        dest_address,
+        esm_class: 0,
+        protocol_id: 0,
+        priority_flag: 0,
+        schedule_delivery_time: COctet::new("").unwrap(),
+        validity_period: COctet::new("").unwrap(),
+        registered_delivery: 0,
+        replace_if_present_flag: 0,
+        data_coding: 0,
+        sm_default_msg_id: 0,
+        sm_length: 2,
+        short_message: b"hi".to_vec(),
+        msg_submission_tlv: vec![],
+    }
+}
+
+#[test]
+fn dest_address_sme_address_round_trip() {
+    let dest_address = DestAddress::SmeAddress {
+        dest_addr_ton: 1,
+        dest_addr_npi: 1,
+        destination_addr: COctet::new("85287654321").unwrap(),
+    };
+
+    let bytes = bincode::encode_to_vec(dest_address, config()).unwrap();
+    assert_eq!(bytes, b"\x01\x01\x0185287654321\0");
+
+    let (decoded, len) = bincode::decode_from_slice::<DestAddress, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert!(matches!(
+        &decoded,
+        DestAddress::SmeAddress { dest_addr_ton: 1, dest_addr_npi: 1, destination_addr }
+            if destination_addr.as_ref().to_bytes() == b"85287654321"
+    ));
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn dest_address_distribution_list_round_trip() {
+    let dest_address = DestAddress::DistributionList(COctet::new("staff").unwrap());
+
+    let bytes = bincode::encode_to_vec(dest_address, config()).unwrap();
+    assert_eq!(bytes, b"\x02staff\0");
+
+    let (decoded, len) = bincode::decode_from_slice::<DestAddress, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert!(matches!(&decoded, DestAddress::DistributionList(name) if name.as_ref().to_bytes() == b"staff"));
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn dest_address_invalid_dest_flag() {
+    assert!(bincode::decode_from_slice::<DestAddress, _>(b"\x03staff\0", config()).is_err());
+}
+
+#[test]
+fn submit_multi_round_trip() {
+    let submit_multi = submit_multi(vec![
+        DestAddress::SmeAddress {
+            dest_addr_ton: 1,
+            dest_addr_npi: 1,
+            destination_addr: COctet::new("85287654321").unwrap(),
+        },
+        DestAddress::DistributionList(COctet::new("staff").unwrap()),
+    ]);
+
+    let bytes = bincode::encode_to_vec(submit_multi, config()).unwrap();
+    assert!(bytes.starts_with(b"\0\x01\x0185212345678\0\x02\x01\x01\x0185287654321\0\x02staff\0"));
+
+    let (decoded, len) = bincode::decode_from_slice::<SubmitMulti, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.dest_address.len(), 2);
+    assert_eq!(decoded.short_message, b"hi");
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
+
+#[test]
+fn submit_multi_without_destination() {
+    assert!(bincode::encode_to_vec(submit_multi(vec![]), config()).is_err());
+
+    let bytes = b"\0\x01\x0185212345678\0\x00\0\0\0\0\0\0\0\0\0\x02hi";
+    assert!(bincode::decode_from_slice::<SubmitMulti, _>(bytes, config()).is_err());
+}
+
+#[test]
+fn submit_multi_too_many_destinations() {
+    let dest_address = DestAddress::DistributionList(COctet::new("staff").unwrap());
+    let submit_multi = submit_multi(vec![dest_address; SubmitMulti::MAX_DESTS + 1]);
+
+    assert!(bincode::encode_to_vec(submit_multi, config()).is_err());
+}
+
+#[test]
+fn submit_multi_resp_round_trip() {
+    let submit_multi_resp = SubmitMultiResp {
+        message_id: COctet::new("4711").unwrap(),
+        unsuccess_sme: vec![
+            UnsuccessSme {
+                dest_addr_ton: 1,
+                dest_addr_npi: 1,
+                destination_addr: COctet::new("85287654321").unwrap(),
+                error_status_code: Status::ESME_RINVDSTADR,
+            },
+            UnsuccessSme {
+                dest_addr_ton: 0,
+                dest_addr_npi: 0,
+                destination_addr: COctet::new("123").unwrap(),
+                error_status_code: Status::ESME_RTHROTTLED,
+            },
+        ],
+
+        #[cfg(feature = "v5")]
+        msg_submission_resp_tlv: vec![],
+    };
+
+    let bytes = bincode::encode_to_vec(submit_multi_resp, config()).unwrap();
+    assert_eq!(bytes, b"4711\0\x02\x01\x0185287654321\0\0\0\0\x0b\0\x00123\0\0\0\0\x58");
+
+    let (decoded, len) = bincode::decode_from_slice::<SubmitMultiResp, _>(&bytes, config()).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(decoded.message_id.as_ref().to_bytes(), b"4711");
+    assert_eq!(decoded.unsuccess_sme.len(), 2);
+    assert_eq!(decoded.unsuccess_sme[0].error_status_code, Status::ESME_RINVDSTADR);
+    assert_eq!(decoded.unsuccess_sme[1].destination_addr.as_ref().to_bytes(), b"123");
+    assert_eq!(decoded.unsuccess_sme[1].error_status_code, Status::ESME_RTHROTTLED);
+    assert_eq!(bincode::encode_to_vec(decoded, config()).unwrap(), bytes);
+}
@@ -0,0 +1,220 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate short_msg;
+
+use short_msg::smpp::prelude::*;
+
+fn segments(s: &str) -> usize {
+    encode_text(s).segments
+}
+
+#[test]
+fn gsm_boundary() {
+    let text = "a".repeat(160);
+    let encoded = encode_text(&text);
+    assert_eq!(encoded.data_coding, DataCoding::GSM);
+    assert_eq!(encoded.bytes.len(), 140);
+    assert_eq!(encoded.segments, 1);
+
+    assert_eq!(segments(&"a".repeat(161)), 2);
+
+    // Concatenated parts hold 153 septets after the UDH.
+    assert_eq!(segments(&"a".repeat(306)), 2);
+    assert_eq!(segments(&"a".repeat(307)), 3);
+
+    let encoded = encode_text("");
+    assert_eq!(encoded.data_coding, DataCoding::GSM);
+    assert!(encoded.bytes.is_empty());
+    assert_eq!(encoded.segments, 1);
+}
+
+#[test]
+fn gsm_final_cr() {
+    // A CR ending on an octet boundary is doubled and no longer fits.
+    assert_eq!(segments(&format!("{}\r", "a".repeat(158))), 1);
+    assert_eq!(segments(&format!("{}\r", "a".repeat(159))), 2);
+}
+
+#[test]
+fn escapes_take_two_septets() {
+    let encoded = encode_text(&"€".repeat(80));
+    assert_eq!(encoded.data_coding, DataCoding::GSM);
+    assert_eq!(encoded.bytes.len(), 140);
+    assert_eq!(encoded.segments, 1);
+
+    assert_eq!(segments(&"€".repeat(81)), 2);
+    assert_eq!(segments(&format!("{}{{", "a".repeat(159))), 2);
+
+    // The escape sequence is not split, so the second part starts with it.
+    let a = "a".repeat(152);
+    assert_eq!(segments(&format!("{a}a{a}a")), 2);
+    assert_eq!(segments(&format!("{a}€{a}")), 3);
+}
+
+#[test]
+fn ucs2_boundary() {
+    let text = "你".repeat(70);
+    let encoded = encode_text(&text);
+    assert_eq!(encoded.data_coding, DataCoding::Ucs2);
+    assert_eq!(encoded.bytes.len(), 140);
+    assert_eq!(&encoded.bytes[..2], b"\x4F\x60");
+    assert_eq!(encoded.segments, 1);
+
+    assert_eq!(segments(&"你".repeat(71)), 2);
+
+    // Concatenated parts hold 67 characters after the UDH.
+    assert_eq!(segments(&"你".repeat(134)), 2);
+    assert_eq!(segments(&"你".repeat(135)), 3);
+}
+
+#[test]
+fn surrogate_pairs_take_four_octets() {
+    let encoded = encode_text("😀");
+    assert_eq!(encoded.data_coding, DataCoding::Ucs2);
+    assert_eq!(encoded.bytes, b"\xD8\x3D\xDE\x00");
+
+    assert_eq!(segments(&"😀".repeat(35)), 1);
+    assert_eq!(segments(&"😀".repeat(36)), 2);
+
+    // The pair is not split, so the second part starts with it.
+    let a = "你".repeat(66);
+    assert_eq!(segments(&format!("{a}你{a}你")), 2);
+    assert_eq!(segments(&format!("{a}😀{a}")), 3);
+}
+
+#[test]
+fn latin1_fallback() {
+    // 'â' is not in the GSM 03.38 default alphabet.
+    let encoded = encode_text("château");
+    assert_eq!(encoded.data_coding, DataCoding::Latin1);
+    assert_eq!(encoded.bytes, b"ch\xE2teau");
+    assert_eq!(encoded.segments, 1);
+
+    assert_eq!(segments(&"â".repeat(140)), 1);
+    assert_eq!(segments(&"â".repeat(141)), 2);
+    assert_eq!(segments(&"â".repeat(268)), 2);
+    assert_eq!(segments(&"â".repeat(269)), 3);
+
+    // Characters of the GSM 03.38 default alphabet only are preferred.
+    assert_eq!(encode_text("café").data_coding, DataCoding::GSM);
+}
+
+#[test]
+fn ucs2_fallback() {
+    // '€' is in the GSM 03.38 extension table but not in ISO-8859-1.
+    let encoded = encode_text("château €");
+    assert_eq!(encoded.data_coding, DataCoding::Ucs2);
+    assert_eq!(encoded.bytes.len(), 18);
+    assert_eq!(&encoded.bytes[16..], b"\x20\xAC");
+
+    assert_eq!(encode_text("€").data_coding, DataCoding::GSM);
+    assert_eq!(encode_text("你").data_coding, DataCoding::Ucs2);
+}
+
+#[test]
+fn charset_round_trip() {
+    for (data_coding, text, bytes) in [
+        (DataCoding::GSM, "hello €", &b"\xE8\x32\x9B\xFD\x06\x6D\xCA"[..]),
+        (DataCoding::Ascii, "hello", b"hello"),
+        (DataCoding::Latin1, "château", b"ch\xE2teau"),
+        (DataCoding::ShiftJis, "あ", b"\x82\xA0"),
+        (DataCoding::Cyrillic, "Привет", b"\xBF\xE0\xD8\xD2\xD5\xE2"),
+        (DataCoding::Hebrew, "שלום", b"\xF9\xEC\xE5\xED"),
+        (DataCoding::Ucs2, "你好", b"\x4F\x60\x59\x7D"),
+        (DataCoding::Iso2022Jp, "あ", b"\x1B\x24\x42\x24\x22\x1B\x28\x42"),
+        (DataCoding::EucJp, "あ", b"\xA4\xA2"),
+        (DataCoding::EucKr, "가", b"\xB0\xA1"),
+    ] {
+        assert_eq!(
+            encode_text_as(data_coding, text).as_deref(),
+            Ok(bytes),
+            "{data_coding:?}"
+        );
+        assert_eq!(decode_text(data_coding, bytes).as_deref(), Ok(text), "{data_coding:?}");
+    }
+
+    for (data_coding, text) in [
+        (DataCoding::ShiftJis, "こんにちは、世界"),
+        (DataCoding::Iso2022Jp, "こんにちは、世界"),
+        (DataCoding::EucJp, "こんにちは、世界"),
+        (DataCoding::EucKr, "안녕하세요"),
+    ] {
+        let bytes = encode_text_as(data_coding, text).unwrap();
+        assert_eq!(decode_text(data_coding, &bytes).as_deref(), Ok(text), "{data_coding:?}");
+    }
+}
+
+#[test]
+fn ucs2_surrogate_pairs() {
+    let bytes = encode_text_as(DataCoding::Ucs2, "a😀").unwrap();
+    assert_eq!(bytes, b"\x00\x61\xD8\x3D\xDE\x00");
+    assert_eq!(decode_text(DataCoding::Ucs2, &bytes).as_deref(), Ok("a😀"));
+
+    let malformed = Err(TextError::Malformed(DataCoding::Ucs2));
+
+    // A lone high or low surrogate.
+    assert_eq!(decode_text(DataCoding::Ucs2, b"\xD8\x3D"), malformed);
+    assert_eq!(decode_text(DataCoding::Ucs2, b"\xD8\x3D\x00\x61"), malformed);
+    assert_eq!(decode_text(DataCoding::Ucs2, b"\xDE\x00\xD8\x3D"), malformed);
+}
+
+#[test]
+fn ucs2_odd_length() {
+    let malformed = Err(TextError::Malformed(DataCoding::Ucs2));
+    assert_eq!(decode_text(DataCoding::Ucs2, b"\x00"), malformed);
+    assert_eq!(decode_text(DataCoding::Ucs2, b"\x00\x61\x00"), malformed);
+    assert_eq!(decode_text(DataCoding::Ucs2, b"").as_deref(), Ok(""));
+}
+
+#[test]
+fn malformed() {
+    assert_eq!(
+        decode_text(DataCoding::Ascii, b"caf\xE9"),
+        Err(TextError::Malformed(DataCoding::Ascii))
+    );
+    assert_eq!(
+        decode_text(DataCoding::ShiftJis, b"\x82"),
+        Err(TextError::Malformed(DataCoding::ShiftJis))
+    );
+    assert_eq!(
+        decode_text(DataCoding::EucKr, b"\xB0"),
+        Err(TextError::Malformed(DataCoding::EucKr))
+    );
+}
+
+#[test]
+fn unrepresentable_index() {
+    let unrepresentable = |index, char| Err(TextError::Unrepresentable { index, char });
+
+    assert_eq!(encode_text_as(DataCoding::GSM, "ab你"), unrepresentable(2, '你'));
+    assert_eq!(encode_text_as(DataCoding::Ascii, "abé"), unrepresentable(2, 'é'));
+    assert_eq!(encode_text_as(DataCoding::Latin1, "éa€"), unrepresentable(2, '€'));
+
+    // The index counts characters, not the bytes of multi-byte characters
+    // before it.
+    assert_eq!(encode_text_as(DataCoding::Cyrillic, "aПж你"), unrepresentable(3, '你'));
+    assert_eq!(encode_text_as(DataCoding::ShiftJis, "あい😀"), unrepresentable(2, '😀'));
+    assert_eq!(encode_text_as(DataCoding::EucKr, "가😀나"), unrepresentable(1, '😀'));
+    assert_eq!(encode_text_as(DataCoding::Hebrew, "😀"), unrepresentable(0, '😀'));
+
+    // Nor by the escape sequences of ISO-2022-JP.
+    let text = format!("{}😀", "あ".repeat(100));
+    assert_eq!(encode_text_as(DataCoding::Iso2022Jp, &text), unrepresentable(100, '😀'));
+}
+
+#[test]
+fn reserved_is_unsupported() {
+    for u in [0x02, 0x04, 0x09, 0x0B, 0x0C, 0x0F, 0xF0] {
+        let data_coding = DataCoding::from(u);
+        assert_eq!(data_coding, DataCoding::Reserved(u));
+        assert_eq!(
+            decode_text(data_coding, b"hello"),
+            Err(TextError::Unsupported(data_coding))
+        );
+        assert_eq!(
+            encode_text_as(data_coding, "hello"),
+            Err(TextError::Unsupported(data_coding))
+        );
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+extern crate short_msg;
+
+use short_msg::smpp::prelude::*;
+
+#[test]
+fn split_and_join() {
+    let udh = UserDataHeader {
+        elements: vec![
+            InformationElement::Concatenated8 {
+                reference: 0x42,
+                total: 3,
+                sequence: 1,
+            },
+            InformationElement::ApplicationPort16 {
+                destination: 2948,
+                source: 9200,
+            },
+        ],
+    };
+    assert_eq!(udh.encoded_len(), 12);
+
+    let short_message = udh.join(b"data").unwrap();
+    assert_eq!(short_message, b"\x0B\x00\x03\x42\x03\x01\x05\x04\x0B\x84\x23\xF0data");
+    assert_eq!(UserDataHeader::split(&short_message), Ok((udh, &b"data"[..])));
+}
+
+#[test]
+fn split_gsm_and_join_gsm() {
+    let udh = UserDataHeader {
+        elements: vec![
+            InformationElement::Concatenated8 {
+                reference: 1,
+                total: 2,
+                sequence: 2,
+            },
+            InformationElement::NationalLockingShift(NationalLanguage::Turkish),
+        ],
+    };
+
+    // Nine octets of UDH take 72 bits, so the text starts after 5 fill bits.
+    assert_eq!(udh.encoded_len(), 9);
+    assert_eq!(udh.fill_bits(), 5);
+
+    let short_message = udh.join_gsm("Ağaç").unwrap();
+    assert_eq!(short_message.len(), (72 + 5 + 4 * 7usize).div_ceil(8));
+    assert_eq!(
+        UserDataHeader::split_gsm(&short_message),
+        Ok((udh.clone(), "Ağaç".to_string()))
+    );
+
+    assert_eq!(
+        udh.join_gsm("你"),
+        Err(UdhError::Unrepresentable(GsmEncodeError { index: 0, char: '你' }))
+    );
+}
+
+#[test]
+fn empty() {
+    let udh = UserDataHeader::default();
+    assert!(udh.is_empty());
+
+    // The length octet is still written.
+    assert_eq!(udh.encoded_len(), 1);
+    assert_eq!(udh.to_bytes(), Ok(vec![0]));
+}
+
+#[test]
+fn unknown_element() {
+    let (udh, data) = UserDataHeader::split(b"\x04\x70\x02\xAB\xCDdata").unwrap();
+    assert_eq!(udh.elements, [InformationElement::Unknown {
+        iei: 0x70,
+        data: vec![0xAB, 0xCD]
+    }]);
+    assert_eq!(data, b"data");
+    assert_eq!(udh.to_bytes(), Ok(b"\x04\x70\x02\xAB\xCD".to_vec()));
+}
+
+#[test]
+fn too_long() {
+    let unknown = |len| InformationElement::Unknown {
+        iei: 0x70,
+        data: vec![0; len],
+    };
+
+    // 2 + 253 octets fill the UDH.
+    let udh = UserDataHeader {
+        elements: vec![unknown(253)],
+    };
+    assert_eq!(udh.encoded_len(), 256);
+    assert_eq!(udh.to_bytes().map(|v| v[..3].to_vec()), Ok(vec![255, 0x70, 253]));
+
+    let udh = UserDataHeader {
+        elements: vec![unknown(254)],
+    };
+    assert_eq!(udh.to_bytes(), Err(UdhError::TooLong));
+    assert_eq!(udh.join(b"data"), Err(UdhError::TooLong));
+
+    // An element longer than 255 octets.
+    let udh = UserDataHeader {
+        elements: vec![unknown(300)],
+    };
+    assert_eq!(udh.to_bytes(), Err(UdhError::TooLong));
+
+    let udh = UserDataHeader {
+        elements: vec![unknown(200), unknown(200)],
+    };
+    assert_eq!(udh.to_bytes(), Err(UdhError::TooLong));
+    assert_eq!(udh.join_gsm("hello"), Err(UdhError::TooLong));
+}
+
+#[test]
+fn truncated() {
+    assert_eq!(UserDataHeader::split(b""), Err(UdhError::Truncated));
+    assert_eq!(UserDataHeader::split(b"\x05\x00\x03"), Err(UdhError::Truncated));
+    assert_eq!(UserDataHeader::split(b"\x03\x00\x03\x01"), Err(UdhError::Truncated));
+}
@@ -0,0 +1,98 @@
+// Copyright (c) 2022 GreenYun Organization
+// SPDX-License-Identifier: MIT
+
+#![cfg(feature = "client")]
+
+extern crate short_msg;
+extern crate tokio;
+
+use std::time::Duration;
+
+use short_msg::smpp::{prelude::*, session::Window};
+use tokio::time::{self, Instant};
+
+const BACKOFF: Duration = Duration::from_secs(10);
+const MAX_BACKOFF: Duration = Duration::from_secs(25);
+
+/// Asserts that the backoff period ends about `delay` from now.
+fn assert_backoff(window: &Window, delay: Duration) {
+    let remaining = window.backoff_until().unwrap() - Instant::now();
+    assert!(remaining <= delay, "{:?} > {:?}", remaining, delay);
+    assert!(
+        remaining > delay - Duration::from_secs(1),
+        "{:?} < {:?}",
+        remaining,
+        delay
+    );
+}
+
+#[tokio::test]
+async fn slots() {
+    let window = Window::new(2, BACKOFF, MAX_BACKOFF);
+    assert_eq!(window.size(), 2);
+    assert_eq!(window.in_flight(), 0);
+
+    let first = window.acquire().await;
+    let second = window.try_acquire().unwrap();
+    assert_eq!(window.in_flight(), 2);
+
+    // The window is full.
+    assert!(window.try_acquire().is_none());
+    assert!(time::timeout(Duration::from_millis(10), window.acquire())
+        .await
+        .is_err());
+
+    drop(first);
+    assert_eq!(window.in_flight(), 1);
+    let _third = window.try_acquire().unwrap();
+
+    drop(second);
+    let _fourth = time::timeout(Duration::from_secs(5), window.acquire()).await.unwrap();
+}
+
+#[test]
+fn at_least_one_slot() {
+    let window = Window::new(0, BACKOFF, MAX_BACKOFF);
+    assert_eq!(window.size(), 1);
+    assert!(window.try_acquire().is_some());
+}
+
+#[test]
+fn backoff_doubles() {
+    let window = Window::new(10, BACKOFF, MAX_BACKOFF);
+    assert!(window.backoff_until().is_none());
+
+    window.record(&Status::ESME_RTHROTTLED);
+    assert_backoff(&window, BACKOFF);
+    assert!(window.try_acquire().is_none());
+
+    window.record(&Status::ESME_RMSGQFUL);
+    assert_backoff(&window, BACKOFF * 2);
+
+    // Capped at the maximum.
+    window.record(&Status::ESME_RTHROTTLED);
+    assert_backoff(&window, MAX_BACKOFF);
+
+    // Other errors neither extend nor clear the backoff period.
+    window.record(&Status::ESME_RSYSERR);
+    assert_backoff(&window, MAX_BACKOFF);
+
+    window.record(&Status::ESME_ROK);
+    assert!(window.backoff_until().is_none());
+    assert!(window.try_acquire().is_some());
+
+    // The next backoff period starts over.
+    window.record(&Status::ESME_RTHROTTLED);
+    assert_backoff(&window, BACKOFF);
+}
+
+#[tokio::test]
+async fn acquire_waits_for_backoff() {
+    let window = Window::new(1, Duration::from_millis(50), MAX_BACKOFF);
+    window.record(&Status::ESME_RTHROTTLED);
+
+    let start = Instant::now();
+    let _slot = window.acquire().await;
+    assert!(start.elapsed() >= Duration::from_millis(40));
+    assert!(window.backoff_until().is_none());
+}